@@ -0,0 +1,85 @@
+// Dashboard analytics. The counting happens in SQL (see the analytics
+// methods in database.rs); this module holds the shapes sent to the
+// frontend and the bits that are easier to express in Rust.
+// Series are compact: periods and labels without any items are left out.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::goals::{self, WorkCalendar};
+
+/// How many entries the "top" lists return.
+pub const TOP_LIMIT: i64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Day,
+    Week,
+    Month,
+}
+
+impl Granularity {
+    /// SQLite expression for the local start of the period containing `column`.
+    /// Weeks start on Monday.
+    pub fn bucket_sql(self, column: &str) -> String {
+        match self {
+            Granularity::Day => format!("date({}, 'localtime')", column),
+            Granularity::Week => format!("date({}, 'localtime', '-6 days', 'weekday 1')", column),
+            Granularity::Month => format!("date({}, 'localtime', 'start of month')", column),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountPoint {
+    pub key: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Streaks {
+    pub current: i64,
+    pub longest: i64,
+    pub last_active: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Analytics {
+    pub range_start: Option<String>,
+    pub range_end: Option<String>,
+    pub granularity: Granularity,
+    pub total_entries: i64,
+    pub total_items: i64,
+    pub items_per_period: Vec<CountPoint>,
+    pub items_per_type: Vec<CountPoint>,
+    pub top_tags: Vec<CountPoint>,
+    pub top_people: Vec<CountPoint>,
+    pub top_projects: Vec<CountPoint>,
+    /// Item counts by local weekday (0 = Monday) and hour of day.
+    pub heatmap: [[i64; 24]; 7],
+    pub streaks: Streaks,
+}
+
+/// Logging streaks over working days; see `goals::logging_streak`.
+pub fn streaks(days: &[NaiveDate], calendar: &WorkCalendar, today: NaiveDate) -> Streaks {
+    let (current, longest) = goals::logging_streak(days, calendar, today);
+    Streaks {
+        current,
+        longest,
+        last_active: days.last().copied(),
+    }
+}
+
+/// Fold `(sqlite weekday, hour, count)` rows into a Monday-first grid.
+pub fn heatmap(cells: &[(i64, i64, i64)]) -> [[i64; 24]; 7] {
+    let mut grid = [[0; 24]; 7];
+    for &(weekday, hour, count) in cells {
+        // SQLite's %w counts from Sunday = 0
+        let day = ((weekday + 6) % 7) as usize;
+        if let Some(cell) = grid.get_mut(day).and_then(|row| row.get_mut(hour as usize)) {
+            *cell += count;
+        }
+    }
+    grid
+}
@@ -0,0 +1,163 @@
+// Suggestions for tags, people, projects and Jira keys while typing. Each
+// value is scored by frecency: every use counts, weighted by how long ago
+// the entry it was used in was logged, so values used often and lately
+// come first. Uses older than the recent window all weigh the same, so only
+// recent entries are read day by day and older uses are plain counts; the
+// cost follows recent activity rather than the size of the log. Typed text
+// matches fuzzily; closer matches always rank above looser ones, and
+// frecency orders values within the same kind of match.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+
+/// Suggestions returned when the caller does not ask for a number.
+pub const DEFAULT_LIMIT: usize = 10;
+
+/// Most suggestions returned at once.
+pub const MAX_LIMIT: usize = 100;
+
+/// Weight of a use by its age in days, newest first.
+const AGE_WEIGHTS: [(i64, f64); 4] = [(4, 100.0), (14, 70.0), (31, 50.0), (90, 30.0)];
+
+/// Weight of a use older than every entry in `AGE_WEIGHTS`.
+const OLD_WEIGHT: f64 = 10.0;
+
+/// Start of the window whose uses are weighed by age.
+pub fn recent_since(now: DateTime<Utc>) -> DateTime<Utc> {
+    let days = AGE_WEIGHTS.last().map(|(max_age, _)| *max_age).unwrap_or(0);
+    now - Duration::days(days + 1)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuggestionKind {
+    Tag,
+    Person,
+    Project,
+    Jira,
+}
+
+impl SuggestionKind {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "tag" => Ok(SuggestionKind::Tag),
+            "person" => Ok(SuggestionKind::Person),
+            "project" => Ok(SuggestionKind::Project),
+            "jira" => Ok(SuggestionKind::Jira),
+            other => Err(format!("Invalid kind '{}'; use tag, person, project or jira", other)),
+        }
+    }
+}
+
+/// How a value matched the typed text, loosest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchQuality {
+    /// The typed characters appear in order, with gaps.
+    Fuzzy,
+    Substring,
+    /// A word inside the value starts with the typed text.
+    WordStart,
+    Prefix,
+    Exact,
+}
+
+/// How `query` matches `candidate`, ignoring case, or `None` if it does not.
+/// An empty query matches everything as a prefix.
+pub fn match_quality(query: &str, candidate: &str) -> Option<MatchQuality> {
+    let query = query.trim().to_lowercase();
+    let candidate = candidate.to_lowercase();
+    if query.is_empty() {
+        return Some(MatchQuality::Prefix);
+    }
+    if candidate == query {
+        return Some(MatchQuality::Exact);
+    }
+    if candidate.starts_with(&query) {
+        return Some(MatchQuality::Prefix);
+    }
+    if candidate.split(|c: char| !c.is_alphanumeric()).any(|word| word.starts_with(&query)) {
+        return Some(MatchQuality::WordStart);
+    }
+    if candidate.contains(&query) {
+        return Some(MatchQuality::Substring);
+    }
+
+    let mut remaining = candidate.chars();
+    query.chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|wanted| remaining.any(|c| c == wanted))
+        .then_some(MatchQuality::Fuzzy)
+}
+
+fn age_weight(age_days: i64) -> f64 {
+    AGE_WEIGHTS.iter()
+        .find(|(max_age, _)| age_days <= *max_age)
+        .map(|(_, weight)| *weight)
+        .unwrap_or(OLD_WEIGHT)
+}
+
+/// Uses of a value on one day of the recent window, or with no day, all
+/// uses ever (zero for values that exist but were never used).
+#[derive(Debug, Clone)]
+pub struct UsageDay {
+    pub value: String,
+    pub day: Option<NaiveDate>,
+    pub uses: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Suggestion {
+    pub value: String,
+    pub uses: i64,
+    /// Last day used, if within the recent window.
+    pub last_used: Option<NaiveDate>,
+    pub frecency: f64,
+    pub quality: MatchQuality,
+}
+
+/// The best `limit` values matching `query`, as of `today`.
+pub fn rank(usage: Vec<UsageDay>, query: &str, limit: usize, today: NaiveDate) -> Vec<Suggestion> {
+    let mut by_value: HashMap<String, Suggestion> = HashMap::new();
+    let mut recent_uses: HashMap<String, i64> = HashMap::new();
+    for row in usage {
+        let suggestion = match by_value.get_mut(&row.value) {
+            Some(existing) => existing,
+            None => {
+                let Some(quality) = match_quality(query, &row.value) else {
+                    continue;
+                };
+                by_value.entry(row.value.clone()).or_insert(Suggestion {
+                    value: row.value,
+                    uses: 0,
+                    last_used: None,
+                    frecency: 0.0,
+                    quality,
+                })
+            }
+        };
+        match row.day {
+            Some(day) => {
+                suggestion.frecency += row.uses as f64 * age_weight((today - day).num_days().max(0));
+                suggestion.last_used = suggestion.last_used.max(Some(day));
+                *recent_uses.entry(suggestion.value.clone()).or_default() += row.uses;
+            }
+            None => suggestion.uses += row.uses,
+        }
+    }
+
+    let mut suggestions: Vec<Suggestion> = by_value.into_values().collect();
+    for suggestion in &mut suggestions {
+        let recent = recent_uses.get(&suggestion.value).copied().unwrap_or(0);
+        suggestion.frecency += (suggestion.uses - recent).max(0) as f64 * OLD_WEIGHT;
+    }
+    suggestions.sort_by(|a, b| {
+        b.quality.cmp(&a.quality)
+            .then_with(|| b.frecency.total_cmp(&a.frecency))
+            .then_with(|| b.uses.cmp(&a.uses))
+            .then_with(|| a.value.to_lowercase().cmp(&b.value.to_lowercase()))
+    });
+    suggestions.truncate(limit);
+    suggestions
+}
@@ -0,0 +1,231 @@
+// Full JSON export and restore of the logbook. The export is the database
+// row for row, so nothing is lost: ids, timestamps, tag colors, projects,
+// meetings, time tracking, invoices and settings all come back on import.
+//
+// Format, version 1:
+//
+//     {
+//       "format": "scobro-logbook",
+//       "version": 1,
+//       "exported_at": "2026-10-18T09:00:00+00:00",
+//       "tables": {
+//         "entries": [{ "id": "…", "timestamp": "…", "created_at": "…", "updated_at": "…" }],
+//         "entry_items": [ … ],
+//         …
+//       }
+//     }
+//
+// `tables` holds one array per table in `TABLES`, each row an object of the
+// table's columns as stored: times are RFC 3339 text, flags are 0 or 1 and
+// list-valued columns (recurrence exceptions, enum options, reminder
+// offsets) are JSON text. Tables or columns an export leaves out are taken
+// as empty or as the column's default, so older exports keep importing.
+// Undo history for bulk updates is not exported.
+//
+// Importing either replaces everything, or merges by id: rows whose id
+// exists are updated and others added. When merging, a tag, person,
+// project or custom field whose name is already taken under another id is
+// matched to the existing one, and rows pointing at it follow.
+
+use serde::{Deserialize, Serialize};
+
+/// Value of `format` in every export.
+pub const FORMAT: &str = "scobro-logbook";
+
+/// Version written by this build; imports accept it and older versions.
+pub const VERSION: u32 = 1;
+
+/// Exported tables, parents before the tables that refer to them.
+pub const TABLES: [&str; 25] = [
+    "entries",
+    "entry_items",
+    "tags",
+    "people",
+    "projects",
+    "custom_fields",
+    "jira_refs",
+    "item_tags",
+    "item_people",
+    "checklist_rows",
+    "item_field_values",
+    "decision_records",
+    "waiting_for",
+    "meetings",
+    "meeting_attendees",
+    "meeting_actions",
+    "meeting_notes",
+    "invoices",
+    "time_entries",
+    "invoice_lines",
+    "project_person_rates",
+    "focus_sessions",
+    "goals",
+    "reminder_states",
+    "settings",
+];
+
+/// Tables emptied by a replacing import besides `TABLES`.
+pub const CLEARED_ON_REPLACE: [&str; 1] = ["bulk_batches"];
+
+/// Unique names that identify a row when merging, by table.
+pub const NATURAL_KEYS: [(&str, &str); 4] = [
+    ("tags", "name"),
+    ("people", "name"),
+    ("projects", "name"),
+    ("custom_fields", "name"),
+];
+
+pub type JsonRow = serde_json::Map<String, serde_json::Value>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backup {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    pub tables: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    Merge,
+    Replace,
+}
+
+impl ImportMode {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "merge" => Ok(ImportMode::Merge),
+            "replace" => Ok(ImportMode::Replace),
+            other => Err(format!("Invalid import mode '{}'; use merge or replace", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableImport {
+    pub table: String,
+    pub rows: usize,
+    /// Rows matched by name to an existing row instead of being written.
+    pub matched: usize,
+    /// Rows changed to fit existing ones: invoices renumbered and running
+    /// timers stopped.
+    pub adjusted: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub mode: ImportMode,
+    pub tables: Vec<TableImport>,
+}
+
+/// Parse an export and check it is one this build can read. Returns its
+/// rows per table in `TABLES` order, leaving out tables it does not have.
+pub fn parse_backup(json: &str) -> Result<Vec<(&'static str, Vec<JsonRow>)>, String> {
+    let backup: Backup = serde_json::from_str(json)
+        .map_err(|e| format!("Not a logbook export: {}", e))?;
+    if backup.format != FORMAT {
+        return Err(format!("Not a logbook export: format is '{}'", backup.format));
+    }
+    if backup.version == 0 || backup.version > VERSION {
+        return Err(format!(
+            "Export version {} is not supported; this version reads up to {}",
+            backup.version, VERSION
+        ));
+    }
+    if let Some(unknown) = backup.tables.keys().find(|name| !TABLES.contains(&name.as_str())) {
+        return Err(format!("Unknown table in export: {}", unknown));
+    }
+
+    let mut tables = backup.tables;
+    TABLES.iter()
+        .filter_map(|table| tables.remove(*table).map(|rows| (*table, rows)))
+        .map(|(table, rows)| {
+            let rows: Vec<JsonRow> = serde_json::from_value(rows)
+                .map_err(|e| format!("Invalid rows for {}: {}", table, e))?;
+            Ok((table, rows))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    use crate::database::{Database, RowValues};
+
+    fn row(values: serde_json::Value) -> RowValues {
+        values.as_object().unwrap().clone().into_iter().collect()
+    }
+
+    /// A logbook with invoice 1 and a running timer, under ids ending in
+    /// `suffix`. It is deleted when the directory is dropped.
+    async fn logbook(suffix: &str) -> (TempDir, Database) {
+        let dir = TempDir::new().unwrap();
+        let db = Database::new(Some(dir.path())).await.unwrap();
+        let invoice = row(serde_json::json!({
+            "id": format!("invoice-{}", suffix),
+            "number": 1,
+            "project_id": "project",
+            "project_name": "Project",
+            "period_start": "2026-09-01T00:00:00+00:00",
+            "period_end": "2026-10-01T00:00:00+00:00",
+            "currency": "EUR",
+            "total": 100.0,
+            "created_at": "2026-10-01T09:00:00+00:00",
+        }));
+        let timer = row(serde_json::json!({
+            "id": format!("timer-{}", suffix),
+            "start_time": "2026-10-05T09:00:00+00:00",
+            "end_time": null,
+            "last_heartbeat": "2026-10-05T09:45:00+00:00",
+            "created_at": "2026-10-05T09:00:00+00:00",
+            "updated_at": "2026-10-05T09:00:00+00:00",
+        }));
+        db.load_tables(&[("time_entries", vec![timer]), ("invoices", vec![invoice])], ImportMode::Replace)
+            .await
+            .unwrap();
+        (dir, db)
+    }
+
+    #[tokio::test]
+    async fn merging_into_a_non_empty_logbook_renumbers_invoices_and_stops_timers() {
+        let (_target_dir, target) = logbook("here").await;
+        let (_source_dir, source) = logbook("there").await;
+
+        let dump = source.dump_tables(&TABLES).await.unwrap();
+        let tables: Vec<(&str, Vec<RowValues>)> = dump.iter().map(|(t, rows)| (t.as_str(), rows.clone())).collect();
+        let summary = target.load_tables(&tables, ImportMode::Merge).await.unwrap();
+
+        let adjusted = |table: &str| summary.iter().find(|t| t.table == table).unwrap().adjusted;
+        assert_eq!(adjusted("invoices"), 1);
+        assert_eq!(adjusted("time_entries"), 1);
+
+        let mut numbers: Vec<(String, i64)> = target.get_invoices(None).await.unwrap()
+            .into_iter()
+            .map(|invoice| (invoice.id, invoice.number))
+            .collect();
+        numbers.sort();
+        assert_eq!(numbers, vec![("invoice-here".to_string(), 1), ("invoice-there".to_string(), 2)]);
+
+        assert_eq!(target.get_active_timer().await.unwrap().unwrap().id, "timer-here");
+        let stopped = target.get_time_entries(None, None, None).await.unwrap()
+            .into_iter()
+            .find(|entry| entry.id == "timer-there")
+            .unwrap();
+        assert_eq!(stopped.end_time, stopped.last_heartbeat);
+    }
+
+    #[tokio::test]
+    async fn merging_an_export_into_itself_changes_nothing() {
+        let (_dir, db) = logbook("here").await;
+        let dump = db.dump_tables(&TABLES).await.unwrap();
+        let tables: Vec<(&str, Vec<RowValues>)> = dump.iter().map(|(t, rows)| (t.as_str(), rows.clone())).collect();
+        let summary = db.load_tables(&tables, ImportMode::Merge).await.unwrap();
+
+        assert!(summary.iter().all(|t| t.adjusted == 0));
+        assert_eq!(db.get_invoices(None).await.unwrap().len(), 1);
+        assert_eq!(db.get_active_timer().await.unwrap().unwrap().id, "timer-here");
+    }
+}
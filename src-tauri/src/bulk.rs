@@ -0,0 +1,98 @@
+// Bulk edits on a selection of items. A batch applies the same operations to
+// every item in one transaction; items that cannot be changed are reported
+// and skipped rather than failing the batch. Before an item is touched its
+// rows are copied into the batch's undo snapshot, so the whole batch can be
+// reverted in one step, including items it deleted.
+
+use serde::{Deserialize, Serialize};
+
+/// How many batches are kept for undo.
+pub const UNDO_HISTORY: i64 = 20;
+
+/// What to do to every selected item. Fields left out are left alone.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BulkOps {
+    pub add_tags: Vec<String>,
+    pub remove_tags: Vec<String>,
+    /// New project for every item; an empty string clears it.
+    pub set_project: Option<String>,
+    pub add_people: Vec<String>,
+    pub set_type: Option<String>,
+    /// Entry to move the items to, appended in the order selected.
+    pub move_to_entry: Option<String>,
+    pub delete: bool,
+}
+
+/// Trimmed, non-empty names without exact duplicates.
+pub fn clean_names(names: &[String]) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for name in names.iter().map(|n| n.trim()).filter(|n| !n.is_empty()) {
+        if !cleaned.iter().any(|c| c == name) {
+            cleaned.push(name.to_string());
+        }
+    }
+    cleaned
+}
+
+impl BulkOps {
+    /// Reject batches that would do nothing or mix deleting with changes.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.set_type.as_deref().is_some_and(|t| t.trim().is_empty()) {
+            return Err("The new item type cannot be empty".to_string());
+        }
+
+        let changes = !clean_names(&self.add_tags).is_empty()
+            || !clean_names(&self.remove_tags).is_empty()
+            || self.set_project.is_some()
+            || !clean_names(&self.add_people).is_empty()
+            || self.set_type.is_some()
+            || self.move_to_entry.is_some();
+        match (self.delete, changes) {
+            (true, true) => Err("Deleting items cannot be combined with other changes".to_string()),
+            (false, false) => Err("Choose at least one change to apply".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `BulkOps` with tags and people looked up, as the database applies them.
+#[derive(Debug, Default, Clone)]
+pub struct ResolvedOps {
+    pub add_tag_names: Vec<String>,
+    pub remove_tag_names: Vec<String>,
+    /// `Some(None)` clears the project.
+    pub set_project: Option<Option<String>>,
+    pub add_person_names: Vec<String>,
+    pub set_type: Option<String>,
+    pub move_to_entry: Option<String>,
+    pub delete: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkItemResult {
+    pub item_id: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+impl BulkItemResult {
+    pub fn ok(item_id: &str) -> Self {
+        BulkItemResult { item_id: item_id.to_string(), ok: true, error: None }
+    }
+
+    pub fn failed(item_id: &str, error: impl Into<String>) -> Self {
+        BulkItemResult { item_id: item_id.to_string(), ok: false, error: Some(error.into()) }
+    }
+}
+
+/// Ids in selection order with repeats dropped.
+pub fn unique_ids(item_ids: &[String]) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for id in item_ids {
+        if !unique.contains(id) {
+            unique.push(id.clone());
+        }
+    }
+    unique
+}
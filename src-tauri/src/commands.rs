@@ -1,10 +1,34 @@
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use crate::database::Database;
+use crate::csv_export::{self, CsvExportOptions};
+use crate::csv_import::{self, CsvImportReport, CsvMapping, PreviewEntry, ResolvedItem};
+use crate::custom_fields::{self, FieldFilter, FieldType};
+use crate::database::{ChecklistRow, CustomField, Database, DecisionRecord, EntryItemWithMetadata, EntryWithItems, FocusSessionRecord, Goal, Invoice, InvoiceLine, Meeting, MeetingAction, MeetingAttendee, NewMeeting, NewMeetingAction, Project, ReminderState, TimeEntry, WaitingFor};
+use crate::analytics::{self, Analytics, Granularity};
+use crate::autocomplete::{self, Suggestion, SuggestionKind};
+use crate::backup::{self, Backup, ImportMode, ImportSummary};
+use crate::bulk::{self, BulkItemResult, BulkOps, ResolvedOps};
+use crate::decisions::{self, Decision, DecisionStatus};
+use crate::digest::{self, Digest};
+use crate::goals::{self, GoalProgress, WorkCalendar};
+use crate::focus::{self, FocusPhase, FocusSession, FocusSettings, FocusState, FocusStatus};
+use crate::invoice;
+use crate::notifications;
+use crate::one_on_one::{self, OneOnOneAgenda};
+use crate::nudges::{self, NudgeSettings, NudgeState, SmartPrompt};
+use crate::recurrence::RecurrenceRule;
+use crate::standup::{self, Standup};
+use crate::reminders::{self, Reminder, ReminderSettings, ReminderTarget};
+use crate::timer_recovery::{self, GapDecision, RecoveryPlan};
+use crate::timesheet::{self, Timesheet, TimesheetGrouping};
+use crate::tray;
+use crate::waiting::{self, WaitingForGroup};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateEntryRequest {
@@ -20,6 +44,9 @@ pub struct CreateItemRequest {
     pub tags: Vec<String>,
     pub jira: Vec<String>,
     pub people: Vec<String>,
+    /// Custom field values by field name.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -29,15 +56,63 @@ pub struct EntryResponse {
     pub items: Vec<ItemResponse>,
 }
 
+impl From<EntryWithItems> for EntryResponse {
+    fn from(entry_with_items: EntryWithItems) -> Self {
+        EntryResponse {
+            id: entry_with_items.entry.id,
+            timestamp: entry_with_items.entry.timestamp.to_rfc3339(),
+            items: entry_with_items.items.into_iter().map(ItemResponse::from).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ItemResponse {
     pub id: String,
     pub item_type: String,
     pub content: String,
     pub project: Option<String>,
+    pub position: i64,
     pub tags: Vec<String>,
     pub jira: Vec<String>,
     pub people: Vec<String>,
+    pub checklist: Vec<ChecklistRow>,
+    pub checklist_progress: ChecklistProgress,
+    /// Custom field values by field name.
+    pub fields: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ChecklistProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl ChecklistProgress {
+    fn of(rows: &[ChecklistRow]) -> Self {
+        ChecklistProgress {
+            done: rows.iter().filter(|row| row.done).count(),
+            total: rows.len(),
+        }
+    }
+}
+
+impl From<EntryItemWithMetadata> for ItemResponse {
+    fn from(item_with_metadata: EntryItemWithMetadata) -> Self {
+        ItemResponse {
+            id: item_with_metadata.item.id,
+            item_type: item_with_metadata.item.item_type,
+            content: item_with_metadata.item.content,
+            project: item_with_metadata.item.project,
+            position: item_with_metadata.item.position,
+            tags: item_with_metadata.tags.into_iter().map(|t| t.name).collect(),
+            jira: item_with_metadata.jira_refs.into_iter().map(|j| j.jira_key).collect(),
+            people: item_with_metadata.people.into_iter().map(|p| p.name).collect(),
+            checklist_progress: ChecklistProgress::of(&item_with_metadata.checklist),
+            checklist: item_with_metadata.checklist,
+            fields: item_with_metadata.fields.into_iter().map(|f| (f.name, f.value)).collect(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -45,6 +120,9 @@ pub struct CreateProjectRequest {
     pub name: String,
     pub description: Option<String>,
     pub color: Option<String>,
+    pub hourly_rate: Option<f64>,
+    pub currency: Option<String>,
+    pub billable: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -53,6 +131,9 @@ pub struct UpdateProjectRequest {
     pub name: Option<String>,
     pub description: Option<String>,
     pub color: Option<String>,
+    pub hourly_rate: Option<f64>,
+    pub currency: Option<String>,
+    pub billable: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -61,10 +142,29 @@ pub struct ProjectResponse {
     pub name: String,
     pub description: Option<String>,
     pub color: String,
+    pub hourly_rate: Option<f64>,
+    pub currency: String,
+    pub billable: bool,
     pub created_at: String,
     pub updated_at: String,
 }
 
+impl From<Project> for ProjectResponse {
+    fn from(project: Project) -> Self {
+        ProjectResponse {
+            id: project.id,
+            name: project.name,
+            description: project.description,
+            color: project.color,
+            hourly_rate: project.hourly_rate,
+            currency: project.currency,
+            billable: project.billable,
+            created_at: project.created_at.to_rfc3339(),
+            updated_at: project.updated_at.to_rfc3339(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateTagRequest {
     pub name: String,
@@ -106,15 +206,40 @@ pub async fn create_entry(
         .map_err(|e| format!("Invalid timestamp: {}", e))?
         .with_timezone(&Utc);
 
+    // Check custom field values before anything is written
+    let custom_fields = db.get_custom_fields()
+        .await
+        .map_err(|e| format!("Failed to get custom fields: {}", e))?;
+    let field_values = request.items.iter()
+        .map(|item_req| custom_fields::resolve_values(&custom_fields, &item_req.fields))
+        .collect::<Result<Vec<_>, _>>()?;
+
     let entry = db.create_entry(timestamp)
         .await
         .map_err(|e| format!("Failed to create entry: {}", e))?;
 
-    let mut items = Vec::new();
-    
-    for item_req in request.items {
+    let items = create_items(&db, &entry.id, request.items.into_iter().zip(field_values).collect(), &custom_fields).await?;
+
+    Ok(EntryResponse {
+        id: entry.id,
+        timestamp: entry.timestamp.to_rfc3339(),
+        items,
+    })
+}
+
+/// Create items in an entry with their tags, people, Jira refs and custom
+/// field values, which must already be checked.
+async fn create_items(
+    db: &Database,
+    entry_id: &str,
+    items: Vec<(CreateItemRequest, Vec<(String, String)>)>,
+    custom_fields: &[CustomField],
+) -> Result<Vec<ItemResponse>, String> {
+    let mut responses = Vec::new();
+
+    for (item_req, values) in items {
         let entry_item = db.create_entry_item(
-            &entry.id,
+            entry_id,
             &item_req.item_type,
             &item_req.content,
             item_req.project.as_deref(),
@@ -149,46 +274,61 @@ pub async fn create_entry(
                 .map_err(|e| format!("Failed to create Jira ref: {}", e))?;
         }
 
-        items.push(ItemResponse {
+        if !values.is_empty() {
+            db.set_item_field_values(&entry_item.id, &values)
+                .await
+                .map_err(|e| format!("Failed to set custom fields: {}", e))?;
+        }
+        let fields = values.into_iter()
+            .filter_map(|(field_id, value)| {
+                custom_fields.iter().find(|f| f.id == field_id).map(|f| (f.name.clone(), value))
+            })
+            .collect();
+
+        responses.push(ItemResponse {
             id: entry_item.id,
             item_type: entry_item.item_type,
             content: entry_item.content,
             project: entry_item.project,
+            position: entry_item.position,
             tags: item_req.tags.clone(),
             jira: item_req.jira.clone(),
             people: item_req.people.clone(),
+            checklist: Vec::new(),
+            checklist_progress: ChecklistProgress::default(),
+            fields,
         });
     }
 
-    Ok(EntryResponse {
-        id: entry.id,
-        timestamp: entry.timestamp.to_rfc3339(),
-        items,
-    })
+    Ok(responses)
 }
 
+/// All entries, newest first. With `field_filters` only items matching
+/// every filter are listed, and entries left without items are dropped.
 #[tauri::command]
-pub async fn get_all_entries(state: State<'_, AppState>) -> Result<Vec<EntryResponse>, String> {
+pub async fn get_all_entries(
+    state: State<'_, AppState>,
+    field_filters: Option<Vec<FieldFilter>>,
+) -> Result<Vec<EntryResponse>, String> {
     let db = state.lock().await;
-    
-    let entries_with_items = db.get_all_entries_with_items()
-        .await
-        .map_err(|e| format!("Failed to get entries: {}", e))?;
+    let field_filters = field_filters.unwrap_or_default();
+    let entries_with_items = if field_filters.is_empty() {
+        db.get_all_entries_with_items().await
+    } else {
+        let custom_fields = db.get_custom_fields()
+            .await
+            .map_err(|e| format!("Failed to get custom fields: {}", e))?;
+        let filters = custom_fields::resolve_filters(&custom_fields, &field_filters)?;
+        db.get_entries_with_matching_items(&filters).await
+    }
+    .map_err(|e| format!("Failed to get entries: {}", e))?;
 
     let mut result = Vec::new();
     
     for entry_with_items in entries_with_items {
         let items: Vec<ItemResponse> = entry_with_items.items
             .into_iter()
-            .map(|item_with_metadata| ItemResponse {
-                id: item_with_metadata.item.id,
-                item_type: item_with_metadata.item.item_type,
-                content: item_with_metadata.item.content,
-                project: item_with_metadata.item.project,
-                tags: item_with_metadata.tags.into_iter().map(|t| t.name).collect(),
-                jira: item_with_metadata.jira_refs.into_iter().map(|j| j.jira_key).collect(),
-                people: item_with_metadata.people.into_iter().map(|p| p.name).collect(),
-            })
+            .map(ItemResponse::from)
             .collect();
 
         result.push(EntryResponse {
@@ -208,6 +348,8 @@ pub struct UpdateEntryItemRequest {
     pub tags: Option<Vec<String>>,
     pub jira: Option<Vec<String>>,
     pub people: Option<Vec<String>>,
+    /// Replaces all custom field values, by field name.
+    pub fields: Option<BTreeMap<String, String>>,
 }
 
 #[tauri::command]
@@ -217,6 +359,17 @@ pub async fn update_entry_item(
     updates: UpdateEntryItemRequest,
 ) -> Result<ItemResponse, String> {
     let db = state.lock().await;
+
+    // Check custom field values before anything is written
+    let field_values = match &updates.fields {
+        Some(fields) => {
+            let custom_fields = db.get_custom_fields()
+                .await
+                .map_err(|e| format!("Failed to get custom fields: {}", e))?;
+            Some(custom_fields::resolve_values(&custom_fields, fields)?)
+        }
+        None => None,
+    };
     
     // Update the entry item content if provided
     if let Some(content) = updates.content {
@@ -283,24 +436,23 @@ pub async fn update_entry_item(
         }
     }
     
+    // Update custom fields if provided
+    if let Some(values) = field_values {
+        db.set_item_field_values(&entry_item_id, &values)
+            .await
+            .map_err(|e| format!("Failed to set custom fields: {}", e))?;
+    }
+    
     // Get the updated item with metadata
     let entry_with_items = db.get_entry_with_items(&entry_item_id)
         .await
         .map_err(|e| format!("Failed to get updated entry item: {}", e))?;
     
-    if let Some(item_with_metadata) = entry_with_items.items.first() {
-        Ok(ItemResponse {
-            id: item_with_metadata.item.id.clone(),
-            item_type: item_with_metadata.item.item_type.clone(),
-            content: item_with_metadata.item.content.clone(),
-            project: item_with_metadata.item.project.clone(),
-            tags: item_with_metadata.tags.iter().map(|t| t.name.clone()).collect(),
-            jira: item_with_metadata.jira_refs.iter().map(|j| j.jira_key.clone()).collect(),
-            people: item_with_metadata.people.iter().map(|p| p.name.clone()).collect(),
-        })
-    } else {
-        Err("Entry item not found".to_string())
-    }
+    entry_with_items.items
+        .into_iter()
+        .find(|item_with_metadata| item_with_metadata.item.id == entry_item_id)
+        .map(ItemResponse::from)
+        .ok_or_else(|| "Entry item not found".to_string())
 }
 
 #[tauri::command]
@@ -331,40 +483,371 @@ pub async fn delete_entry(
     Ok(())
 }
 
+async fn entry_response(db: &Database, entry_id: &str) -> Result<EntryResponse, String> {
+    db.get_entry(entry_id)
+        .await
+        .map_err(|e| format!("Failed to get entry: {}", e))?
+        .map(EntryResponse::from)
+        .ok_or_else(|| format!("Entry not found: {}", entry_id))
+}
+
+/// Back-date or correct when an entry was logged.
+#[tauri::command]
+pub async fn update_entry_timestamp(
+    state: State<'_, AppState>,
+    entry_id: String,
+    timestamp: String,
+) -> Result<EntryResponse, String> {
+    let db = state.lock().await;
+    let timestamp = parse_timestamp(&timestamp, "timestamp")?;
+
+    let updated = db.update_entry_timestamp(&entry_id, timestamp)
+        .await
+        .map_err(|e| format!("Failed to update entry timestamp: {}", e))?;
+    if !updated {
+        return Err(format!("Entry not found: {}", entry_id));
+    }
+
+    entry_response(&db, &entry_id).await
+}
+
+/// Move an item into another entry. An entry left without items is deleted.
 #[tauri::command]
-pub async fn export_entries_csv(state: State<'_, AppState>) -> Result<String, String> {
+pub async fn move_item(
+    state: State<'_, AppState>,
+    item_id: String,
+    target_entry_id: String,
+) -> Result<EntryResponse, String> {
     let db = state.lock().await;
-    
-    let entries_with_items = db.get_all_entries_with_items()
+    entry_response(&db, &target_entry_id).await?;
+    if db.get_item_entry_ids(std::slice::from_ref(&item_id))
+        .await
+        .map_err(|e| format!("Failed to get item: {}", e))?
+        .is_empty()
+    {
+        return Err(format!("Item not found: {}", item_id));
+    }
+
+    db.move_items(&[item_id], &target_entry_id)
+        .await
+        .map_err(|e| format!("Failed to move item: {}", e))?;
+
+    entry_response(&db, &target_entry_id).await
+}
+
+/// Move some of an entry's items into a new entry, at `timestamp` or the
+/// original entry's time. Returns the original entry and the new one.
+#[tauri::command]
+pub async fn split_entry(
+    state: State<'_, AppState>,
+    entry_id: String,
+    item_ids: Vec<String>,
+    timestamp: Option<String>,
+) -> Result<Vec<EntryResponse>, String> {
+    let db = state.lock().await;
+    let original = db.get_entry(&entry_id)
+        .await
+        .map_err(|e| format!("Failed to get entry: {}", e))?
+        .ok_or_else(|| format!("Entry not found: {}", entry_id))?;
+
+    if item_ids.is_empty() {
+        return Err("Choose the items to split off".to_string());
+    }
+    if let Some(stray) = item_ids.iter().find(|id| !original.items.iter().any(|i| &i.item.id == *id)) {
+        return Err(format!("Item {} is not in this entry", stray));
+    }
+    if original.items.iter().all(|i| item_ids.contains(&i.item.id)) {
+        return Err("Leave at least one item in the original entry".to_string());
+    }
+    let timestamp = match timestamp {
+        Some(timestamp) => parse_timestamp(&timestamp, "timestamp")?,
+        None => original.entry.timestamp,
+    };
+
+    let new_entry = db.split_entry(&item_ids, timestamp)
+        .await
+        .map_err(|e| format!("Failed to split entry: {}", e))?;
+
+    Ok(vec![
+        entry_response(&db, &entry_id).await?,
+        entry_response(&db, &new_entry.id).await?,
+    ])
+}
+
+/// Move every item of `source_entry_ids` into the target entry, which keeps
+/// its timestamp. The source entries are deleted.
+#[tauri::command]
+pub async fn merge_entries(
+    state: State<'_, AppState>,
+    target_entry_id: String,
+    source_entry_ids: Vec<String>,
+) -> Result<EntryResponse, String> {
+    let db = state.lock().await;
+    entry_response(&db, &target_entry_id).await?;
+    if source_entry_ids.is_empty() {
+        return Err("Choose the entries to merge".to_string());
+    }
+    if source_entry_ids.contains(&target_entry_id) {
+        return Err("An entry cannot be merged into itself".to_string());
+    }
+
+    for source_id in &source_entry_ids {
+        db.get_entry(source_id)
+            .await
+            .map_err(|e| format!("Failed to get entry: {}", e))?
+            .ok_or_else(|| format!("Entry not found: {}", source_id))?;
+    }
+
+    db.merge_entries(&source_entry_ids, &target_entry_id)
+        .await
+        .map_err(|e| format!("Failed to merge entries: {}", e))?;
+
+    entry_response(&db, &target_entry_id).await
+}
+
+/// Put an entry's items in a new order. `ordered_ids` must list every item
+/// of the entry exactly once.
+#[tauri::command]
+pub async fn reorder_items(
+    state: State<'_, AppState>,
+    entry_id: String,
+    ordered_ids: Vec<String>,
+) -> Result<EntryResponse, String> {
+    let db = state.lock().await;
+    let entry = entry_response(&db, &entry_id).await?;
+
+    let mut current: Vec<&str> = entry.items.iter().map(|item| item.id.as_str()).collect();
+    let mut requested: Vec<&str> = ordered_ids.iter().map(String::as_str).collect();
+    current.sort_unstable();
+    requested.sort_unstable();
+    if current != requested {
+        return Err("The new order must list every item of the entry exactly once".to_string());
+    }
+
+    db.reorder_items(&entry_id, &ordered_ids)
+        .await
+        .map_err(|e| format!("Failed to reorder items: {}", e))?;
+
+    entry_response(&db, &entry_id).await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkUpdateResponse {
+    /// Pass to `undo_bulk_update`; absent when nothing was changed.
+    pub batch_id: Option<String>,
+    pub updated: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
+}
+
+/// Apply the same changes to many items at once, in one transaction. Items
+/// that cannot be found are reported and skipped. Entries left without
+/// items by a move or delete are removed.
+#[tauri::command]
+pub async fn bulk_update_items(
+    state: State<'_, AppState>,
+    item_ids: Vec<String>,
+    ops: BulkOps,
+) -> Result<BulkUpdateResponse, String> {
+    let db = state.lock().await;
+    ops.validate()?;
+    let item_ids = bulk::unique_ids(&item_ids);
+    if item_ids.is_empty() {
+        return Err("Choose the items to change".to_string());
+    }
+    if let Some(target_entry_id) = &ops.move_to_entry {
+        entry_response(&db, target_entry_id).await?;
+    }
+
+    let resolved = ResolvedOps {
+        add_tag_names: bulk::clean_names(&ops.add_tags),
+        remove_tag_names: bulk::clean_names(&ops.remove_tags),
+        add_person_names: bulk::clean_names(&ops.add_people),
+        set_project: ops.set_project.as_deref().map(|p| Some(p.trim().to_string()).filter(|p| !p.is_empty())),
+        set_type: ops.set_type.as_deref().map(|t| t.trim().to_string()),
+        move_to_entry: ops.move_to_entry.clone(),
+        delete: ops.delete,
+    };
+
+    let (batch_id, results) = db.bulk_update_items(&item_ids, &resolved)
+        .await
+        .map_err(|e| format!("Failed to update items: {}", e))?;
+
+    let updated = results.iter().filter(|r| r.ok).count();
+    Ok(BulkUpdateResponse {
+        batch_id,
+        updated,
+        failed: results.len() - updated,
+        results,
+    })
+}
+
+/// Revert a bulk update, bringing back any items it deleted. Batches are
+/// undone newest first. Returns the ids of the restored items.
+#[tauri::command]
+pub async fn undo_bulk_update(state: State<'_, AppState>, batch_id: String) -> Result<Vec<String>, String> {
+    let db = state.lock().await;
+    let latest = db.get_latest_bulk_batch_id()
+        .await
+        .map_err(|e| format!("Failed to get bulk updates: {}", e))?;
+    match latest {
+        None => return Err("There is no bulk update to undo".to_string()),
+        Some(latest) if latest != batch_id => {
+            return Err("Only the most recent bulk update can be undone".to_string());
+        }
+        Some(_) => {}
+    }
+
+    db.undo_bulk_batch(&batch_id)
+        .await
+        .map_err(|e| format!("Failed to undo bulk update: {}", e))?
+        .ok_or_else(|| format!("Bulk update not found: {}", batch_id))
+}
+
+/// Export items as CSV, newest entry first. Without options every item is
+/// written with all columns.
+#[tauri::command]
+pub async fn export_entries_csv(
+    state: State<'_, AppState>,
+    options: Option<CsvExportOptions>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let from = options.from.as_deref().map(|d| parse_date(d, "start date")).transpose()?;
+    let to = options.to.as_deref().map(|d| parse_date(d, "end date")).transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err("The start date must not be after the end date".to_string());
+        }
+    }
+
+    let db = state.lock().await;
+    let custom_fields = db.get_custom_fields()
+        .await
+        .map_err(|e| format!("Failed to get custom fields: {}", e))?;
+    let columns = csv_export::resolve_columns(options.columns.as_deref(), &custom_fields)?;
+
+    let (start, end) = csv_export::day_range(from, to);
+    let mut rows = db.get_items_between(start, end)
         .await
         .map_err(|e| format!("Failed to get entries: {}", e))?;
+    let filter = csv_export::ItemFilter::from_options(&options);
+    rows.retain(|(_, item)| filter.matches(item));
+    // Newest entry first, keeping each entry's items in order
+    rows.sort_by_key(|(timestamp, _)| std::cmp::Reverse(*timestamp));
 
-    let mut csv = String::from("Date,Time,Type,Content,Project,Tags,Jira,People\n");
-    
-    for entry_with_items in entries_with_items {
-        let date = entry_with_items.entry.timestamp.format("%Y-%m-%d").to_string();
-        let time = entry_with_items.entry.timestamp.format("%H:%M:%S").to_string();
-        
-        for item_with_metadata in entry_with_items.items {
-            let tags = item_with_metadata.tags.into_iter().map(|t| t.name).collect::<Vec<_>>().join(";");
-            let jira = item_with_metadata.jira_refs.into_iter().map(|j| j.jira_key).collect::<Vec<_>>().join(";");
-            let people = item_with_metadata.people.into_iter().map(|p| p.name).collect::<Vec<_>>().join(";");
-            
-            csv.push_str(&format!(
-                "{},{},{},\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
-                date,
-                time,
-                item_with_metadata.item.item_type,
-                item_with_metadata.item.content.replace("\"", "\"\""),
-                item_with_metadata.item.project.unwrap_or_default(),
-                tags,
-                jira,
-                people
-            ));
+    csv_export::write_csv(&columns, &rows, options.bom)
+}
+
+/// Import entries from the CSV file at `path`, read with `mapping` or by the
+/// headers `export_entries_csv` writes. A dry run only reports what would
+/// be imported; nothing is imported while any row has an error.
+#[tauri::command]
+pub async fn import_csv(
+    state: State<'_, AppState>,
+    path: String,
+    mapping: Option<CsvMapping>,
+    dry_run: bool,
+) -> Result<CsvImportReport, String> {
+    if path.trim().is_empty() {
+        return Err("Choose a CSV file to import".to_string());
+    }
+    let text = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mapping = mapping.unwrap_or_default();
+
+    let db = state.lock().await;
+    let custom_fields = db.get_custom_fields()
+        .await
+        .map_err(|e| format!("Failed to get custom fields: {}", e))?;
+    let (rows, errors) = csv_import::read_rows(&text, &mapping, &custom_fields)?;
+    let row_count = rows.len() + errors.len();
+    let entries = csv_import::group_entries(rows);
+
+    let mut report = CsvImportReport {
+        dry_run,
+        imported: false,
+        rows: row_count,
+        entries: entries.len(),
+        errors,
+        preview: entries.iter()
+            .take(csv_import::PREVIEW_ENTRIES)
+            .map(|(timestamp, items)| PreviewEntry { timestamp: timestamp.to_rfc3339(), items: items.clone() })
+            .collect(),
+        entry_ids: Vec::new(),
+    };
+    if dry_run || !report.errors.is_empty() {
+        return Ok(report);
+    }
+
+    let entries = entries.into_iter()
+        .map(|(timestamp, items)| {
+            let items = items.into_iter()
+                .map(|item| {
+                    let values = custom_fields::resolve_values(&custom_fields, &item.fields)?;
+                    Ok(ResolvedItem { item, values })
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok((timestamp, items))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    report.entry_ids = db.import_entries(&entries)
+        .await
+        .map_err(|e| format!("Failed to import entries: {}", e))?;
+    report.imported = true;
+    Ok(report)
+}
+
+/// Export the whole logbook as JSON in the `backup` format.
+#[tauri::command]
+pub async fn export_json(state: State<'_, AppState>) -> Result<String, String> {
+    let db = state.lock().await;
+    let dump = db.dump_tables(&backup::TABLES)
+        .await
+        .map_err(|e| format!("Failed to read logbook: {}", e))?;
+
+    let tables = dump.into_iter()
+        .map(|(table, rows)| {
+            let rows = rows.into_iter()
+                .map(|row| serde_json::Value::Object(row.into_iter().collect()))
+                .collect();
+            (table, serde_json::Value::Array(rows))
+        })
+        .collect();
+    let export = Backup {
+        format: backup::FORMAT.to_string(),
+        version: backup::VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        tables,
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| format!("Failed to write JSON: {}", e))
+}
+
+/// Restore a JSON export, in `merge` or `replace` mode. Nothing is written
+/// unless the whole export can be imported.
+#[tauri::command]
+pub async fn import_json(state: State<'_, AppState>, data: String, mode: String) -> Result<ImportSummary, String> {
+    let mode = ImportMode::parse(&mode)?;
+    let tables = backup::parse_backup(&data)?;
+
+    let db = state.lock().await;
+    let mut rows_by_table = Vec::new();
+    for (table, rows) in tables {
+        let columns = db.get_table_columns(table)
+            .await
+            .map_err(|e| format!("Failed to read logbook: {}", e))?;
+        for row in &rows {
+            if let Some(unknown) = row.keys().find(|column| !columns.contains(column)) {
+                return Err(format!("Unknown column in {}: {}", table, unknown));
+            }
         }
+        rows_by_table.push((table, rows.into_iter().map(|row| row.into_iter().collect()).collect()));
     }
-    
-    Ok(csv)
+
+    let tables = db.load_tables(&rows_by_table, mode)
+        .await
+        .map_err(|e| format!("Failed to import: {}", e))?;
+    Ok(ImportSummary { mode, tables })
 }
 
 #[tauri::command]
@@ -384,16 +867,21 @@ pub async fn export_entries_markdown(state: State<'_, AppState>) -> Result<Strin
         markdown.push_str(&format!("## {} {}\n\n", date, time));
         
         for item_with_metadata in entry_with_items.items {
-            let type_emoji = match item_with_metadata.item.item_type.as_str() {
-                "Action" => "🔴",
-                "Decision" => "🔵", 
-                "Note" => "🟢",
-                "Meeting" => "🟣",
-                _ => "📝",
-            };
+            let type_emoji = item_type_emoji(&item_with_metadata.item.item_type);
             
             markdown.push_str(&format!("### {} {}\n", type_emoji, item_with_metadata.item.item_type));
             markdown.push_str(&format!("{}\n\n", item_with_metadata.item.content));
+
+            if !item_with_metadata.checklist.is_empty() {
+                for row in &item_with_metadata.checklist {
+                    let mark = if row.done { "x" } else { " " };
+                    match &row.assignee {
+                        Some(assignee) => markdown.push_str(&format!("- [{}] {} (👤 {})\n", mark, row.text, assignee)),
+                        None => markdown.push_str(&format!("- [{}] {}\n", mark, row.text)),
+                    }
+                }
+                markdown.push('\n');
+            }
             
             if let Some(project) = &item_with_metadata.item.project {
                 if !project.is_empty() {
@@ -435,18 +923,14 @@ pub async fn create_project(
         &request.name,
         request.description.as_deref(),
         request.color.as_deref(),
+        request.hourly_rate,
+        request.currency.as_deref(),
+        request.billable,
     )
     .await
     .map_err(|e| format!("Failed to create project: {}", e))?;
 
-    Ok(ProjectResponse {
-        id: project.id,
-        name: project.name,
-        description: project.description,
-        color: project.color,
-        created_at: project.created_at.to_rfc3339(),
-        updated_at: project.updated_at.to_rfc3339(),
-    })
+    Ok(ProjectResponse::from(project))
 }
 
 #[tauri::command]
@@ -457,16 +941,7 @@ pub async fn get_all_projects(state: State<'_, AppState>) -> Result<Vec<ProjectR
         .await
         .map_err(|e| format!("Failed to get projects: {}", e))?;
 
-    let response = projects.into_iter().map(|project| ProjectResponse {
-        id: project.id,
-        name: project.name,
-        description: project.description,
-        color: project.color,
-        created_at: project.created_at.to_rfc3339(),
-        updated_at: project.updated_at.to_rfc3339(),
-    }).collect();
-
-    Ok(response)
+    Ok(projects.into_iter().map(ProjectResponse::from).collect())
 }
 
 #[tauri::command]
@@ -481,18 +956,14 @@ pub async fn update_project(
         request.name.as_deref(),
         request.description.as_deref(),
         request.color.as_deref(),
+        request.hourly_rate,
+        request.currency.as_deref(),
+        request.billable,
     )
     .await
     .map_err(|e| format!("Failed to update project: {}", e))?;
 
-    Ok(ProjectResponse {
-        id: project.id,
-        name: project.name,
-        description: project.description,
-        color: project.color,
-        created_at: project.created_at.to_rfc3339(),
-        updated_at: project.updated_at.to_rfc3339(),
-    })
+    Ok(ProjectResponse::from(project))
 }
 
 #[tauri::command]
@@ -609,6 +1080,7 @@ pub struct CreateMeetingRequest {
     pub end_time: Option<String>,
     pub location: Option<String>,
     pub meeting_type: Option<String>,
+    pub recurrence_rule: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -621,10 +1093,43 @@ pub struct MeetingResponse {
     pub location: Option<String>,
     pub meeting_type: String,
     pub status: String,
+    pub recurrence_rule: Option<String>,
+    pub recurrence_exceptions: Vec<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+impl From<Meeting> for MeetingResponse {
+    fn from(meeting: Meeting) -> Self {
+        MeetingResponse {
+            id: meeting.id,
+            title: meeting.title,
+            description: meeting.description,
+            start_time: meeting.start_time.map(|t| t.to_rfc3339()),
+            end_time: meeting.end_time.map(|t| t.to_rfc3339()),
+            location: meeting.location,
+            meeting_type: meeting.meeting_type,
+            status: meeting.status,
+            recurrence_rule: meeting.recurrence_rule,
+            recurrence_exceptions: meeting.recurrence_exceptions.iter().map(|t| t.to_rfc3339()).collect(),
+            created_at: meeting.created_at.to_rfc3339(),
+            updated_at: meeting.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MeetingOccurrenceResponse {
+    pub meeting_id: String,
+    pub title: String,
+    pub occurrence_start: String,
+    pub occurrence_end: Option<String>,
+    pub location: Option<String>,
+    pub meeting_type: String,
+    pub status: String,
+    pub is_recurring: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AddAttendeeRequest {
     pub meeting_id: String,
@@ -652,6 +1157,7 @@ pub struct CreateActionRequest {
     pub assignee: Option<String>,
     pub due_date: Option<String>,
     pub priority: Option<String>,
+    pub occurrence_start: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -665,6 +1171,7 @@ pub struct ActionResponse {
     pub due_date: Option<String>,
     pub status: String,
     pub priority: String,
+    pub occurrence_start: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -684,29 +1191,28 @@ pub async fn create_meeting(
         .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
         .map(|dt| dt.with_timezone(&Utc));
 
-    let meeting = db.create_meeting(
-        &request.title,
-        request.description.as_deref(),
+    let recurrence_rule = request.recurrence_rule
+        .filter(|rule| !rule.trim().is_empty());
+    if let Some(rule) = &recurrence_rule {
+        RecurrenceRule::parse(rule).map_err(|e| format!("Invalid recurrence rule: {}", e))?;
+        if start_time.is_none() {
+            return Err("Recurring meetings need a start time".to_string());
+        }
+    }
+
+    let meeting = db.create_meeting(NewMeeting {
+        title: &request.title,
+        description: request.description.as_deref(),
         start_time,
         end_time,
-        request.location.as_deref(),
-        request.meeting_type.as_deref(),
-    )
+        location: request.location.as_deref(),
+        meeting_type: request.meeting_type.as_deref(),
+        recurrence_rule: recurrence_rule.as_deref(),
+    })
     .await
     .map_err(|e| format!("Failed to create meeting: {}", e))?;
 
-    Ok(MeetingResponse {
-        id: meeting.id,
-        title: meeting.title,
-        description: meeting.description,
-        start_time: meeting.start_time.map(|t| t.to_rfc3339()),
-        end_time: meeting.end_time.map(|t| t.to_rfc3339()),
-        location: meeting.location,
-        meeting_type: meeting.meeting_type,
-        status: meeting.status,
-        created_at: meeting.created_at.to_rfc3339(),
-        updated_at: meeting.updated_at.to_rfc3339(),
-    })
+    Ok(MeetingResponse::from(meeting))
 }
 
 #[tauri::command]
@@ -717,18 +1223,7 @@ pub async fn get_all_meetings(state: State<'_, AppState>) -> Result<Vec<MeetingR
         .await
         .map_err(|e| format!("Failed to get meetings: {}", e))?;
 
-    let response = meetings.into_iter().map(|meeting| MeetingResponse {
-        id: meeting.id,
-        title: meeting.title,
-        description: meeting.description,
-        start_time: meeting.start_time.map(|t| t.to_rfc3339()),
-        end_time: meeting.end_time.map(|t| t.to_rfc3339()),
-        location: meeting.location,
-        meeting_type: meeting.meeting_type,
-        status: meeting.status,
-        created_at: meeting.created_at.to_rfc3339(),
-        updated_at: meeting.updated_at.to_rfc3339(),
-    }).collect();
+    let response = meetings.into_iter().map(MeetingResponse::from).collect();
 
     Ok(response)
 }
@@ -791,18 +1286,18 @@ pub async fn create_meeting_action(
 ) -> Result<ActionResponse, String> {
     let db = state.lock().await;
     
-    let due_date = request.due_date
-        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-        .map(|dt| dt.with_timezone(&Utc));
-
-    let action = db.create_meeting_action(
-        &request.meeting_id,
-        &request.title,
-        request.description.as_deref(),
-        request.assignee.as_deref(),
+    let due_date = request.due_date.as_deref().map(parse_due_date).transpose()?;
+    let occurrence_start = parse_optional_timestamp(request.occurrence_start.as_deref(), "occurrence start")?;
+
+    let action = db.create_meeting_action(NewMeetingAction {
+        meeting_id: &request.meeting_id,
+        title: &request.title,
+        description: request.description.as_deref(),
+        assignee: request.assignee.as_deref(),
         due_date,
-        request.priority.as_deref(),
-    )
+        priority: request.priority.as_deref(),
+        occurrence_start,
+    })
     .await
     .map_err(|e| format!("Failed to create action: {}", e))?;
 
@@ -816,6 +1311,7 @@ pub async fn create_meeting_action(
         due_date: action.due_date.map(|t| t.to_rfc3339()),
         status: action.status,
         priority: action.priority,
+        occurrence_start: action.occurrence_start.map(|t| t.to_rfc3339()),
         created_at: action.created_at.to_rfc3339(),
         updated_at: action.updated_at.to_rfc3339(),
     })
@@ -842,6 +1338,7 @@ pub async fn get_meeting_actions(
         due_date: action.due_date.map(|t| t.to_rfc3339()),
         status: action.status,
         priority: action.priority,
+        occurrence_start: action.occurrence_start.map(|t| t.to_rfc3339()),
         created_at: action.created_at.to_rfc3339(),
         updated_at: action.updated_at.to_rfc3339(),
     }).collect();
@@ -862,3 +1359,2276 @@ pub async fn delete_meeting(
 
     Ok(())
 }
+
+#[tauri::command]
+pub async fn link_meeting_note(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    entry_item_id: String,
+    occurrence_start: Option<String>,
+) -> Result<(), String> {
+    let db = state.lock().await;
+    let occurrence_start = parse_optional_timestamp(occurrence_start.as_deref(), "occurrence start")?;
+    
+    db.link_meeting_note(&meeting_id, &entry_item_id, occurrence_start)
+        .await
+        .map_err(|e| format!("Failed to link meeting note: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn unlink_meeting_note(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    entry_item_id: String,
+) -> Result<(), String> {
+    let db = state.lock().await;
+    
+    db.unlink_meeting_note(&meeting_id, &entry_item_id)
+        .await
+        .map_err(|e| format!("Failed to unlink meeting note: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_meeting_recurrence(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    recurrence_rule: Option<String>,
+    exceptions: Vec<String>,
+) -> Result<MeetingResponse, String> {
+    let db = state.lock().await;
+    
+    let meeting = db.get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| "Meeting not found".to_string())?;
+
+    let recurrence_rule = recurrence_rule.filter(|rule| !rule.trim().is_empty());
+    if let Some(rule) = &recurrence_rule {
+        RecurrenceRule::parse(rule).map_err(|e| format!("Invalid recurrence rule: {}", e))?;
+        if meeting.start_time.is_none() {
+            return Err("Recurring meetings need a start time".to_string());
+        }
+    }
+
+    let mut parsed_exceptions = Vec::new();
+    for exception in &exceptions {
+        parsed_exceptions.push(parse_timestamp(exception, "exception")?);
+    }
+    parsed_exceptions.sort();
+    parsed_exceptions.dedup();
+
+    db.update_meeting_recurrence(&meeting_id, recurrence_rule.as_deref(), &parsed_exceptions)
+        .await
+        .map_err(|e| format!("Failed to update meeting recurrence: {}", e))?;
+
+    let meeting = db.get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| "Meeting not found".to_string())?;
+
+    Ok(MeetingResponse::from(meeting))
+}
+
+#[tauri::command]
+pub async fn cancel_meeting_occurrence(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    occurrence_start: String,
+) -> Result<MeetingResponse, String> {
+    let db = state.lock().await;
+    let occurrence_start = parse_timestamp(&occurrence_start, "occurrence start")?;
+    
+    let meeting = db.get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| "Meeting not found".to_string())?;
+    if meeting.recurrence_rule.is_none() {
+        return Err("Only occurrences of recurring meetings can be cancelled".to_string());
+    }
+
+    let mut exceptions = meeting.recurrence_exceptions;
+    if !exceptions.contains(&occurrence_start) {
+        exceptions.push(occurrence_start);
+        exceptions.sort();
+    }
+
+    db.update_meeting_recurrence(&meeting_id, meeting.recurrence_rule.as_deref(), &exceptions)
+        .await
+        .map_err(|e| format!("Failed to cancel meeting occurrence: {}", e))?;
+
+    let meeting = db.get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| "Meeting not found".to_string())?;
+
+    Ok(MeetingResponse::from(meeting))
+}
+
+/// Expand every meeting into the occurrences that start inside the range.
+/// One-off meetings yield at most one occurrence; attendees are not repeated
+/// here because occurrences share the series' attendee list.
+#[tauri::command]
+pub async fn get_meeting_occurrences(
+    state: State<'_, AppState>,
+    range_start: String,
+    range_end: String,
+) -> Result<Vec<MeetingOccurrenceResponse>, String> {
+    let db = state.lock().await;
+    let range_start = parse_timestamp(&range_start, "range start")?;
+    let range_end = parse_timestamp(&range_end, "range end")?;
+
+    let meetings = db.get_all_meetings()
+        .await
+        .map_err(|e| format!("Failed to get meetings: {}", e))?;
+
+    expand_occurrences(meetings, range_start, range_end)
+}
+
+/// Occurrences of `meetings` starting inside the range, in start order.
+pub(crate) fn expand_occurrences(
+    meetings: Vec<Meeting>,
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+) -> Result<Vec<MeetingOccurrenceResponse>, String> {
+    let mut occurrences = Vec::new();
+    for meeting in meetings {
+        let Some(start_time) = meeting.start_time else {
+            continue;
+        };
+        let duration = meeting.end_time.map(|end| end - start_time);
+
+        let starts = match &meeting.recurrence_rule {
+            Some(rule) => {
+                let rule = RecurrenceRule::parse(rule)
+                    .map_err(|e| format!("Invalid recurrence rule on '{}': {}", meeting.title, e))?;
+                rule.occurrences_between(
+                    &start_time.with_timezone(&Local),
+                    range_start,
+                    range_end,
+                    &meeting.recurrence_exceptions,
+                )
+            }
+            None if start_time >= range_start && start_time <= range_end => vec![start_time],
+            None => Vec::new(),
+        };
+
+        for start in starts {
+            occurrences.push(MeetingOccurrenceResponse {
+                meeting_id: meeting.id.clone(),
+                title: meeting.title.clone(),
+                occurrence_start: start.to_rfc3339(),
+                occurrence_end: duration.map(|d| (start + d).to_rfc3339()),
+                location: meeting.location.clone(),
+                meeting_type: meeting.meeting_type.clone(),
+                status: meeting.status.clone(),
+                is_recurring: meeting.recurrence_rule.is_some(),
+            });
+        }
+    }
+
+    occurrences.sort_by(|a, b| a.occurrence_start.cmp(&b.occurrence_start));
+    Ok(occurrences)
+}
+
+fn parse_timestamp(value: &str, what: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid {}: {}", what, e))
+}
+
+/// Due dates come from date pickers as `YYYY-MM-DD` (local midnight) or as
+/// full RFC 3339 timestamps.
+fn parse_due_date(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(day) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(timesheet::start_of_day(&Local, day));
+    }
+    parse_timestamp(value, "due date")
+}
+
+fn parse_optional_timestamp(value: Option<&str>, what: &str) -> Result<Option<DateTime<Utc>>, String> {
+    value.map(|v| parse_timestamp(v, what)).transpose()
+}
+
+// Meeting minutes
+struct MeetingMinutes {
+    meeting: Meeting,
+    attendees: Vec<MeetingAttendee>,
+    actions: Vec<MeetingAction>,
+    notes: Vec<EntryItemWithMetadata>,
+}
+
+#[tauri::command]
+pub async fn generate_meeting_minutes(
+    state: State<'_, AppState>,
+    meeting_id: String,
+    format: String,
+    occurrence_start: Option<String>,
+) -> Result<String, String> {
+    let db = state.lock().await;
+    let occurrence_start = parse_optional_timestamp(occurrence_start.as_deref(), "occurrence start")?;
+    
+    let mut meeting = db.get_meeting(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting: {}", e))?
+        .ok_or_else(|| "Meeting not found".to_string())?;
+    let attendees = db.get_meeting_attendees(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get attendees: {}", e))?;
+    let mut actions = db.get_meeting_actions(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get actions: {}", e))?;
+    let notes = db.get_meeting_notes(&meeting_id, occurrence_start)
+        .await
+        .map_err(|e| format!("Failed to get meeting notes: {}", e))?;
+
+    // Minutes for a single occurrence show that occurrence's time and only
+    // the actions raised in it; attendees come from the series.
+    if let Some(occurrence) = occurrence_start {
+        if let (Some(start), Some(end)) = (meeting.start_time, meeting.end_time) {
+            meeting.end_time = Some(occurrence + (end - start));
+        }
+        meeting.start_time = Some(occurrence);
+        actions.retain(|a| a.occurrence_start == Some(occurrence));
+    }
+
+    // Minutes read top-down, so list actions in the order they were raised
+    actions.reverse();
+
+    let minutes = MeetingMinutes { meeting, attendees, actions, notes };
+
+    match format.as_str() {
+        "markdown" | "md" => Ok(render_minutes_markdown(&minutes)),
+        "html" => Ok(render_minutes_html(&minutes)),
+        other => Err(format!("Unsupported minutes format: {}", other)),
+    }
+}
+
+fn item_type_emoji(item_type: &str) -> &'static str {
+    match item_type {
+        "Action" => "🔴",
+        "Decision" => "🔵",
+        "Note" => "🟢",
+        "Meeting" => "🟣",
+        _ => "📝",
+    }
+}
+
+fn rsvp_label(status: &str) -> &'static str {
+    match status {
+        "accepted" => "✅ Accepted",
+        "declined" => "❌ Declined",
+        "tentative" => "❔ Tentative",
+        "attended" => "✅ Attended",
+        "absent" => "❌ Absent",
+        _ => "✉️ Invited",
+    }
+}
+
+fn meeting_when(meeting: &Meeting) -> Option<String> {
+    let start = meeting.start_time?;
+    let mut when = start.format("%Y-%m-%d %H:%M").to_string();
+    if let Some(end) = meeting.end_time {
+        if end.date_naive() == start.date_naive() {
+            when.push_str(&format!(" – {}", end.format("%H:%M")));
+        } else {
+            when.push_str(&format!(" – {}", end.format("%Y-%m-%d %H:%M")));
+        }
+    }
+    Some(when)
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn render_minutes_markdown(minutes: &MeetingMinutes) -> String {
+    let meeting = &minutes.meeting;
+    let mut markdown = format!("# 🟣 Meeting Minutes: {}\n\n", meeting.title);
+
+    if let Some(when) = meeting_when(meeting) {
+        markdown.push_str(&format!("**Date:** 📅 {}\n\n", when));
+    }
+    if let Some(location) = &meeting.location {
+        if !location.is_empty() {
+            markdown.push_str(&format!("**Location:** 📍 {}\n\n", location));
+        }
+    }
+    markdown.push_str(&format!("**Type:** {} · **Status:** {}\n\n", meeting.meeting_type, meeting.status));
+    markdown.push_str("---\n\n");
+
+    markdown.push_str("## 👥 Attendance\n\n");
+    if minutes.attendees.is_empty() {
+        markdown.push_str("_No attendees recorded._\n\n");
+    } else {
+        for attendee in &minutes.attendees {
+            markdown.push_str(&format!("- **{}** ({}) — {}\n", attendee.name, attendee.role, rsvp_label(&attendee.status)));
+        }
+        markdown.push('\n');
+    }
+
+    markdown.push_str("## 📋 Agenda\n\n");
+    match meeting.description.as_deref().map(str::trim) {
+        Some(agenda) if !agenda.is_empty() => markdown.push_str(&format!("{}\n\n", agenda)),
+        _ => markdown.push_str("_No agenda recorded._\n\n"),
+    }
+
+    let (decisions, notes): (Vec<_>, Vec<_>) = minutes.notes
+        .iter()
+        .partition(|n| n.item.item_type == "Decision");
+
+    markdown.push_str(&format!("## {} Decisions\n\n", item_type_emoji("Decision")));
+    if decisions.is_empty() {
+        markdown.push_str("_No decisions recorded._\n\n");
+    } else {
+        for decision in &decisions {
+            markdown.push_str(&format!("- {}\n", decision.item.content));
+        }
+        markdown.push('\n');
+    }
+
+    if !notes.is_empty() {
+        markdown.push_str("## 📝 Notes\n\n");
+        for note in &notes {
+            markdown.push_str(&format!("### {} {}\n", item_type_emoji(&note.item.item_type), note.item.item_type));
+            markdown.push_str(&format!("{}\n\n", note.item.content));
+            if let Some(project) = &note.item.project {
+                if !project.is_empty() {
+                    markdown.push_str(&format!("**Project:** 📂 {}\n\n", project));
+                }
+            }
+            if !note.tags.is_empty() {
+                let tags = note.tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(", ");
+                markdown.push_str(&format!("**Tags:** 🏷 {}\n\n", tags));
+            }
+        }
+    }
+
+    markdown.push_str(&format!("## {} Actions\n\n", item_type_emoji("Action")));
+    if minutes.actions.is_empty() {
+        markdown.push_str("_No actions recorded._\n\n");
+    } else {
+        markdown.push_str("| Action | Owner | Due | Priority | Status |\n");
+        markdown.push_str("|---|---|---|---|---|\n");
+        for action in &minutes.actions {
+            markdown.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                action.title.replace('|', "\\|"),
+                action.assignee.as_deref().unwrap_or("Unassigned").replace('|', "\\|"),
+                action.due_date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "—".to_string()),
+                action.priority,
+                action.status,
+            ));
+        }
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+fn render_minutes_html(minutes: &MeetingMinutes) -> String {
+    let meeting = &minutes.meeting;
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>Meeting Minutes: {}</title>\n", escape_html(&meeting.title)));
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&format!("<h1>🟣 Meeting Minutes: {}</h1>\n", escape_html(&meeting.title)));
+
+    if let Some(when) = meeting_when(meeting) {
+        html.push_str(&format!("<p><strong>Date:</strong> 📅 {}</p>\n", escape_html(&when)));
+    }
+    if let Some(location) = &meeting.location {
+        if !location.is_empty() {
+            html.push_str(&format!("<p><strong>Location:</strong> 📍 {}</p>\n", escape_html(location)));
+        }
+    }
+    html.push_str(&format!(
+        "<p><strong>Type:</strong> {} · <strong>Status:</strong> {}</p>\n<hr>\n",
+        escape_html(&meeting.meeting_type),
+        escape_html(&meeting.status)
+    ));
+
+    html.push_str("<h2>👥 Attendance</h2>\n");
+    if minutes.attendees.is_empty() {
+        html.push_str("<p><em>No attendees recorded.</em></p>\n");
+    } else {
+        html.push_str("<ul>\n");
+        for attendee in &minutes.attendees {
+            html.push_str(&format!(
+                "<li><strong>{}</strong> ({}) — {}</li>\n",
+                escape_html(&attendee.name),
+                escape_html(&attendee.role),
+                rsvp_label(&attendee.status)
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    html.push_str("<h2>📋 Agenda</h2>\n");
+    match meeting.description.as_deref().map(str::trim) {
+        Some(agenda) if !agenda.is_empty() => {
+            html.push_str(&format!("<p>{}</p>\n", escape_html(agenda).replace('\n', "<br>\n")));
+        }
+        _ => html.push_str("<p><em>No agenda recorded.</em></p>\n"),
+    }
+
+    let (decisions, notes): (Vec<_>, Vec<_>) = minutes.notes
+        .iter()
+        .partition(|n| n.item.item_type == "Decision");
+
+    html.push_str(&format!("<h2>{} Decisions</h2>\n", item_type_emoji("Decision")));
+    if decisions.is_empty() {
+        html.push_str("<p><em>No decisions recorded.</em></p>\n");
+    } else {
+        html.push_str("<ul>\n");
+        for decision in &decisions {
+            html.push_str(&format!("<li>{}</li>\n", escape_html(&decision.item.content)));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    if !notes.is_empty() {
+        html.push_str("<h2>📝 Notes</h2>\n");
+        for note in &notes {
+            html.push_str(&format!(
+                "<h3>{} {}</h3>\n<p>{}</p>\n",
+                item_type_emoji(&note.item.item_type),
+                escape_html(&note.item.item_type),
+                escape_html(&note.item.content)
+            ));
+            if let Some(project) = &note.item.project {
+                if !project.is_empty() {
+                    html.push_str(&format!("<p><strong>Project:</strong> 📂 {}</p>\n", escape_html(project)));
+                }
+            }
+            if !note.tags.is_empty() {
+                let tags = note.tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(", ");
+                html.push_str(&format!("<p><strong>Tags:</strong> 🏷 {}</p>\n", escape_html(&tags)));
+            }
+        }
+    }
+
+    html.push_str(&format!("<h2>{} Actions</h2>\n", item_type_emoji("Action")));
+    if minutes.actions.is_empty() {
+        html.push_str("<p><em>No actions recorded.</em></p>\n");
+    } else {
+        html.push_str("<table>\n<tr><th>Action</th><th>Owner</th><th>Due</th><th>Priority</th><th>Status</th></tr>\n");
+        for action in &minutes.actions {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&action.title),
+                escape_html(action.assignee.as_deref().unwrap_or("Unassigned")),
+                action.due_date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "—".to_string()),
+                escape_html(&action.priority),
+                escape_html(&action.status),
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+// Time tracking structs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StartTimerRequest {
+    pub project: Option<String>,
+    pub entry_item_id: Option<String>,
+    pub jira_key: Option<String>,
+    pub notes: Option<String>,
+    pub person: Option<String>,
+    pub billable: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateTimeEntryRequest {
+    pub id: String,
+    pub project: Option<String>,
+    pub entry_item_id: Option<String>,
+    pub jira_key: Option<String>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub notes: Option<String>,
+    pub person: Option<String>,
+    pub billable: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimeEntryResponse {
+    pub id: String,
+    pub project: Option<String>,
+    pub entry_item_id: Option<String>,
+    pub jira_key: Option<String>,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub duration_seconds: i64,
+    pub notes: Option<String>,
+    pub person: Option<String>,
+    pub billable: bool,
+    pub invoice_id: Option<String>,
+    pub is_running: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<TimeEntry> for TimeEntryResponse {
+    fn from(entry: TimeEntry) -> Self {
+        TimeEntryResponse {
+            duration_seconds: entry.duration_at(Utc::now()).num_seconds(),
+            is_running: entry.end_time.is_none(),
+            id: entry.id,
+            project: entry.project,
+            entry_item_id: entry.entry_item_id,
+            jira_key: entry.jira_key,
+            start_time: entry.start_time.to_rfc3339(),
+            end_time: entry.end_time.map(|t| t.to_rfc3339()),
+            notes: entry.notes,
+            person: entry.person,
+            billable: entry.billable,
+            invoice_id: entry.invoice_id,
+            created_at: entry.created_at.to_rfc3339(),
+            updated_at: entry.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Treat empty strings from the frontend's form fields as "no value".
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+// Time tracking commands
+#[tauri::command]
+pub async fn start_timer(
+    state: State<'_, AppState>,
+    request: StartTimerRequest,
+) -> Result<TimeEntryResponse, String> {
+    let db = state.lock().await;
+
+    if let Some(active) = db.get_active_timer()
+        .await
+        .map_err(|e| format!("Failed to get active timer: {}", e))?
+    {
+        return Err(format!(
+            "A timer is already running since {}; stop it first",
+            active.start_time.with_timezone(&Local).format("%H:%M")
+        ));
+    }
+
+    let project = non_empty(request.project);
+    let entry_item_id = non_empty(request.entry_item_id);
+    let jira_key = non_empty(request.jira_key);
+    let notes = non_empty(request.notes);
+    let person = non_empty(request.person);
+
+    let entry = db.start_timer(
+        project.as_deref(),
+        entry_item_id.as_deref(),
+        jira_key.as_deref(),
+        notes.as_deref(),
+        person.as_deref(),
+        request.billable.unwrap_or(true),
+        Utc::now(),
+    )
+    .await
+    .map_err(|e| format!("Failed to start timer: {}", e))?;
+
+    Ok(TimeEntryResponse::from(entry))
+}
+
+#[tauri::command]
+pub async fn stop_timer(state: State<'_, AppState>) -> Result<TimeEntryResponse, String> {
+    let db = state.lock().await;
+    
+    let entry = db.stop_timer(Utc::now())
+        .await
+        .map_err(|e| format!("Failed to stop timer: {}", e))?
+        .ok_or_else(|| "No active timer to stop".to_string())?;
+
+    Ok(TimeEntryResponse::from(entry))
+}
+
+#[tauri::command]
+pub async fn get_active_timer(state: State<'_, AppState>) -> Result<Option<TimeEntryResponse>, String> {
+    let db = state.lock().await;
+    
+    let entry = db.get_active_timer()
+        .await
+        .map_err(|e| format!("Failed to get active timer: {}", e))?;
+
+    Ok(entry.map(TimeEntryResponse::from))
+}
+
+#[tauri::command]
+pub async fn list_time_entries(
+    state: State<'_, AppState>,
+    range_start: Option<String>,
+    range_end: Option<String>,
+    project: Option<String>,
+) -> Result<Vec<TimeEntryResponse>, String> {
+    let db = state.lock().await;
+    let range_start = parse_optional_timestamp(range_start.as_deref(), "range start")?;
+    let range_end = parse_optional_timestamp(range_end.as_deref(), "range end")?;
+    let project = non_empty(project);
+    
+    let entries = db.get_time_entries(range_start, range_end, project.as_deref())
+        .await
+        .map_err(|e| format!("Failed to get time entries: {}", e))?;
+
+    Ok(entries.into_iter().map(TimeEntryResponse::from).collect())
+}
+
+/// Edit a time entry. Omitted fields are left unchanged and empty strings
+/// clear optional fields. Setting an end time on the running timer stops it.
+#[tauri::command]
+pub async fn edit_time_entry(
+    state: State<'_, AppState>,
+    request: UpdateTimeEntryRequest,
+) -> Result<TimeEntryResponse, String> {
+    let db = state.lock().await;
+    
+    let mut entry = db.get_time_entry(&request.id)
+        .await
+        .map_err(|e| format!("Failed to get time entry: {}", e))?
+        .ok_or_else(|| "Time entry not found".to_string())?;
+
+    if entry.invoice_id.is_some() {
+        return Err("Time entry has already been invoiced and can no longer be edited".to_string());
+    }
+
+    if let Some(project) = request.project {
+        entry.project = non_empty(Some(project));
+    }
+    if let Some(entry_item_id) = request.entry_item_id {
+        entry.entry_item_id = non_empty(Some(entry_item_id));
+    }
+    if let Some(jira_key) = request.jira_key {
+        entry.jira_key = non_empty(Some(jira_key));
+    }
+    if let Some(notes) = request.notes {
+        entry.notes = non_empty(Some(notes));
+    }
+    if let Some(person) = request.person {
+        entry.person = non_empty(Some(person));
+    }
+    if let Some(billable) = request.billable {
+        entry.billable = billable;
+    }
+    if let Some(start_time) = request.start_time {
+        entry.start_time = parse_timestamp(&start_time, "start time")?;
+    }
+    if let Some(end_time) = request.end_time {
+        entry.end_time = Some(parse_timestamp(&end_time, "end time")?);
+    }
+
+    if entry.end_time.is_some_and(|end| end < entry.start_time) {
+        return Err("End time must be after start time".to_string());
+    }
+    if entry.end_time.is_none() && entry.start_time > Utc::now() {
+        return Err("A running timer cannot start in the future".to_string());
+    }
+
+    let entry = db.update_time_entry(&entry)
+        .await
+        .map_err(|e| format!("Failed to update time entry: {}", e))?;
+
+    Ok(TimeEntryResponse::from(entry))
+}
+
+#[tauri::command]
+pub async fn delete_time_entry(
+    state: State<'_, AppState>,
+    time_entry_id: String,
+) -> Result<(), String> {
+    let db = state.lock().await;
+
+    let entry = db.get_time_entry(&time_entry_id)
+        .await
+        .map_err(|e| format!("Failed to get time entry: {}", e))?;
+    if entry.is_some_and(|e| e.invoice_id.is_some()) {
+        return Err("Time entry has already been invoiced and can no longer be deleted".to_string());
+    }
+    
+    db.delete_time_entry(&time_entry_id)
+        .await
+        .map_err(|e| format!("Failed to delete time entry: {}", e))?;
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimerRecoveryResponse {
+    pub timer: TimeEntryResponse,
+    pub last_heartbeat: String,
+    pub detected_at: String,
+    pub gap_seconds: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolveTimerRecoveryResponse {
+    pub stopped: Option<TimeEntryResponse>,
+    pub running: Option<TimeEntryResponse>,
+}
+
+/// Report an idle gap on the running timer, typically called once on launch.
+/// Returns `None` when there is no running timer or it has been kept alive.
+#[tauri::command]
+pub async fn get_timer_recovery(state: State<'_, AppState>) -> Result<Option<TimerRecoveryResponse>, String> {
+    let db = state.lock().await;
+    
+    let Some(timer) = db.get_active_timer()
+        .await
+        .map_err(|e| format!("Failed to get active timer: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let Some(gap) = timer_recovery::detect_idle_gap(&timer, Utc::now(), timer_recovery::idle_threshold()) else {
+        return Ok(None);
+    };
+
+    Ok(Some(TimerRecoveryResponse {
+        last_heartbeat: gap.last_heartbeat.to_rfc3339(),
+        detected_at: gap.detected_at.to_rfc3339(),
+        gap_seconds: gap.duration().num_seconds(),
+        timer: TimeEntryResponse::from(timer),
+    }))
+}
+
+/// Apply the user's choice for an idle gap: `keep` counts it, `trim` stops
+/// the timer at the last heartbeat, `split` does the same and resumes
+/// tracking from now.
+#[tauri::command]
+pub async fn resolve_timer_recovery(
+    state: State<'_, AppState>,
+    decision: GapDecision,
+) -> Result<ResolveTimerRecoveryResponse, String> {
+    let db = state.lock().await;
+    
+    let timer = db.get_active_timer()
+        .await
+        .map_err(|e| format!("Failed to get active timer: {}", e))?
+        .ok_or_else(|| "No active timer to recover".to_string())?;
+    let gap = timer_recovery::detect_idle_gap(&timer, Utc::now(), timer_recovery::idle_threshold())
+        .ok_or_else(|| "The active timer has no idle gap".to_string())?;
+
+    match timer_recovery::plan_recovery(&gap, decision) {
+        RecoveryPlan::Continue { heartbeat } => {
+            db.record_timer_heartbeat(&timer.id, heartbeat)
+                .await
+                .map_err(|e| format!("Failed to record timer heartbeat: {}", e))?;
+            let running = db.get_active_timer()
+                .await
+                .map_err(|e| format!("Failed to get active timer: {}", e))?;
+
+            Ok(ResolveTimerRecoveryResponse {
+                stopped: None,
+                running: running.map(TimeEntryResponse::from),
+            })
+        }
+        RecoveryPlan::Stop { end } => {
+            let stopped = db.stop_timer(end)
+                .await
+                .map_err(|e| format!("Failed to stop timer: {}", e))?;
+
+            Ok(ResolveTimerRecoveryResponse {
+                stopped: stopped.map(TimeEntryResponse::from),
+                running: None,
+            })
+        }
+        RecoveryPlan::StopAndRestart { end, restart_at } => {
+            let stopped = db.stop_timer(end)
+                .await
+                .map_err(|e| format!("Failed to stop timer: {}", e))?;
+            let running = db.start_timer(
+                timer.project.as_deref(),
+                timer.entry_item_id.as_deref(),
+                timer.jira_key.as_deref(),
+                timer.notes.as_deref(),
+                timer.person.as_deref(),
+                timer.billable,
+                restart_at,
+            )
+            .await
+            .map_err(|e| format!("Failed to restart timer: {}", e))?;
+
+            Ok(ResolveTimerRecoveryResponse {
+                stopped: stopped.map(TimeEntryResponse::from),
+                running: Some(TimeEntryResponse::from(running)),
+            })
+        }
+    }
+}
+
+// Timesheet commands
+fn parse_week_start(week_start: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(week_start, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid week start: {}", e))
+}
+
+async fn load_week_time_entries(db: &Database, week_start: NaiveDate) -> Result<Vec<TimeEntry>, String> {
+    let boundaries = timesheet::day_boundaries(&Local, week_start);
+    db.get_time_entries(boundaries.first().copied(), boundaries.last().copied(), None)
+        .await
+        .map_err(|e| format!("Failed to get time entries: {}", e))
+}
+
+/// Aggregate tracked time for the week starting at `week_start`
+/// (`YYYY-MM-DD`, local time). `rounding_minutes` rounds each entry's time
+/// per day to the nearest multiple, e.g. 6 or 15.
+#[tauri::command]
+pub async fn get_timesheet(
+    state: State<'_, AppState>,
+    week_start: String,
+    group_by: TimesheetGrouping,
+    rounding_minutes: Option<u32>,
+) -> Result<Timesheet, String> {
+    let db = state.lock().await;
+    let week_start = parse_week_start(&week_start)?;
+
+    let entries = load_week_time_entries(&db, week_start).await?;
+
+    Ok(timesheet::build_timesheet(&entries, week_start, group_by, rounding_minutes, Utc::now()))
+}
+
+/// Export a week of tracked time as `csv` (grid), `markdown` (grid) or
+/// `toggl` (one row per entry and day, importable by Toggl and Clockify).
+#[tauri::command]
+pub async fn export_timesheet(
+    state: State<'_, AppState>,
+    week_start: String,
+    group_by: TimesheetGrouping,
+    rounding_minutes: Option<u32>,
+    format: String,
+    email: Option<String>,
+) -> Result<String, String> {
+    let db = state.lock().await;
+    let week_start = parse_week_start(&week_start)?;
+    let now = Utc::now();
+
+    let entries = load_week_time_entries(&db, week_start).await?;
+
+    match format.as_str() {
+        "csv" => timesheet::render_csv(&timesheet::build_timesheet(&entries, week_start, group_by, rounding_minutes, now)),
+        "markdown" | "md" => Ok(timesheet::render_markdown(&timesheet::build_timesheet(&entries, week_start, group_by, rounding_minutes, now))),
+        "toggl" | "clockify" => timesheet::render_toggl_csv(&entries, week_start, rounding_minutes, email.as_deref(), now),
+        other => Err(format!("Unsupported timesheet format: {}", other)),
+    }
+}
+
+// Billing structs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectPersonRateResponse {
+    pub project_id: String,
+    pub person: String,
+    pub hourly_rate: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InvoiceLineResponse {
+    pub time_entry_id: Option<String>,
+    pub date: String,
+    pub description: String,
+    pub person: Option<String>,
+    pub hours: f64,
+    pub rate: f64,
+    pub amount: f64,
+}
+
+impl From<InvoiceLine> for InvoiceLineResponse {
+    fn from(line: InvoiceLine) -> Self {
+        InvoiceLineResponse {
+            time_entry_id: line.time_entry_id,
+            date: line.date.to_rfc3339(),
+            description: line.description,
+            person: line.person,
+            hours: line.hours,
+            rate: line.rate,
+            amount: line.amount,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InvoiceResponse {
+    pub id: String,
+    pub number: i64,
+    pub invoice_number: String,
+    pub project_id: String,
+    pub project_name: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub currency: String,
+    pub total: f64,
+    pub created_at: String,
+    pub lines: Vec<InvoiceLineResponse>,
+}
+
+impl InvoiceResponse {
+    fn new(invoice: Invoice, lines: Vec<InvoiceLine>) -> Self {
+        InvoiceResponse {
+            invoice_number: invoice::format_invoice_number(invoice.number),
+            id: invoice.id,
+            number: invoice.number,
+            project_id: invoice.project_id,
+            project_name: invoice.project_name,
+            period_start: invoice.period_start.to_rfc3339(),
+            period_end: invoice.period_end.to_rfc3339(),
+            currency: invoice.currency,
+            total: invoice.total,
+            created_at: invoice.created_at.to_rfc3339(),
+            lines: lines.into_iter().map(InvoiceLineResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GeneratedInvoiceResponse {
+    pub invoice: InvoiceResponse,
+    pub markdown: String,
+    pub html: String,
+}
+
+async fn load_project(db: &Database, project_id: &str) -> Result<Project, String> {
+    db.get_project(project_id)
+        .await
+        .map_err(|e| format!("Failed to get project: {}", e))?
+        .ok_or_else(|| "Project not found".to_string())
+}
+
+// Billing commands
+/// Set a person's hourly rate on a project. A missing rate removes the
+/// override so the project rate applies again.
+#[tauri::command]
+pub async fn set_project_person_rate(
+    state: State<'_, AppState>,
+    project_id: String,
+    person_name: String,
+    hourly_rate: Option<f64>,
+) -> Result<(), String> {
+    let db = state.lock().await;
+
+    if hourly_rate.is_some_and(|rate| rate < 0.0) {
+        return Err("Hourly rate cannot be negative".to_string());
+    }
+
+    let project = load_project(&db, &project_id).await?;
+    let person = db.get_or_create_person(&person_name)
+        .await
+        .map_err(|e| format!("Failed to create person: {}", e))?;
+
+    db.set_project_person_rate(&project.id, &person.id, hourly_rate)
+        .await
+        .map_err(|e| format!("Failed to set person rate: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_project_person_rates(
+    state: State<'_, AppState>,
+    project_id: String,
+) -> Result<Vec<ProjectPersonRateResponse>, String> {
+    let db = state.lock().await;
+
+    let rates = db.get_project_person_rates(&project_id)
+        .await
+        .map_err(|e| format!("Failed to get person rates: {}", e))?;
+
+    Ok(rates.into_iter().map(|rate| ProjectPersonRateResponse {
+        project_id: rate.project_id,
+        person: rate.person,
+        hourly_rate: rate.hourly_rate,
+    }).collect())
+}
+
+/// Invoice all finished, billable, not yet invoiced time on a project that
+/// started within the period. The time entries are locked to the invoice so
+/// they cannot be billed twice or edited afterwards.
+#[tauri::command]
+pub async fn generate_invoice(
+    state: State<'_, AppState>,
+    project_id: String,
+    period_start: String,
+    period_end: String,
+) -> Result<GeneratedInvoiceResponse, String> {
+    let db = state.lock().await;
+    let period_start = parse_timestamp(&period_start, "period start")?;
+    let period_end = parse_timestamp(&period_end, "period end")?;
+    if period_end <= period_start {
+        return Err("Period end must be after period start".to_string());
+    }
+
+    let project = load_project(&db, &project_id).await?;
+    if !project.billable {
+        return Err(format!("Project {} is not billable", project.name));
+    }
+
+    let entries = db.get_uninvoiced_time_entries(&project.name, period_start, period_end)
+        .await
+        .map_err(|e| format!("Failed to get time entries: {}", e))?;
+    if entries.is_empty() {
+        return Err(format!("No uninvoiced billable time on {} in this period", project.name));
+    }
+
+    let person_rates = db.get_project_person_rates(&project.id)
+        .await
+        .map_err(|e| format!("Failed to get person rates: {}", e))?;
+    let lines = invoice::build_lines(&project, &person_rates, &entries)?;
+
+    let created = db.create_invoice(&project, period_start, period_end, &lines)
+        .await
+        .map_err(|e| format!("Failed to create invoice: {}", e))?;
+
+    Ok(GeneratedInvoiceResponse {
+        markdown: invoice::render_markdown(&created, &lines),
+        html: invoice::render_html(&created, &lines),
+        invoice: InvoiceResponse::new(created, lines),
+    })
+}
+
+#[tauri::command]
+pub async fn list_invoices(
+    state: State<'_, AppState>,
+    project_id: Option<String>,
+) -> Result<Vec<InvoiceResponse>, String> {
+    let db = state.lock().await;
+    let project_id = non_empty(project_id);
+
+    let invoices = db.get_invoices(project_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to get invoices: {}", e))?;
+
+    let mut response = Vec::new();
+    for invoice in invoices {
+        let lines = db.get_invoice_lines(&invoice.id)
+            .await
+            .map_err(|e| format!("Failed to get invoice lines: {}", e))?;
+        response.push(InvoiceResponse::new(invoice, lines));
+    }
+
+    Ok(response)
+}
+
+/// Render a stored invoice again as `markdown` or `html`.
+#[tauri::command]
+pub async fn export_invoice(
+    state: State<'_, AppState>,
+    invoice_id: String,
+    format: String,
+) -> Result<String, String> {
+    let db = state.lock().await;
+
+    let stored = db.get_invoice(&invoice_id)
+        .await
+        .map_err(|e| format!("Failed to get invoice: {}", e))?
+        .ok_or_else(|| "Invoice not found".to_string())?;
+    let lines = db.get_invoice_lines(&stored.id)
+        .await
+        .map_err(|e| format!("Failed to get invoice lines: {}", e))?;
+
+    match format.as_str() {
+        "markdown" | "md" => Ok(invoice::render_markdown(&stored, &lines)),
+        "html" => Ok(invoice::render_html(&stored, &lines)),
+        other => Err(format!("Unsupported invoice format: {}", other)),
+    }
+}
+
+// Focus session structs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FocusSessionResponse {
+    pub id: String,
+    pub project: Option<String>,
+    pub time_entry_id: Option<String>,
+    pub cycle: i64,
+    pub start_time: String,
+    pub end_time: String,
+    pub duration_seconds: i64,
+}
+
+impl From<FocusSessionRecord> for FocusSessionResponse {
+    fn from(record: FocusSessionRecord) -> Self {
+        FocusSessionResponse {
+            duration_seconds: (record.end_time - record.start_time).num_seconds(),
+            id: record.id,
+            project: record.project,
+            time_entry_id: record.time_entry_id,
+            cycle: record.cycle,
+            start_time: record.start_time.to_rfc3339(),
+            end_time: record.end_time.to_rfc3339(),
+        }
+    }
+}
+
+// Focus session commands
+#[tauri::command]
+pub async fn get_focus_settings(state: State<'_, AppState>) -> Result<FocusSettings, String> {
+    let db = state.lock().await;
+    focus::load_settings(&db).await
+}
+
+/// Save the focus settings. They apply from the next session on.
+#[tauri::command]
+pub async fn update_focus_settings(
+    state: State<'_, AppState>,
+    settings: FocusSettings,
+) -> Result<FocusSettings, String> {
+    let db = state.lock().await;
+    settings.validate()?;
+
+    let json = serde_json::to_string(&settings)
+        .map_err(|e| format!("Failed to save focus settings: {}", e))?;
+    db.set_setting(focus::FOCUS_SETTINGS_KEY, &json)
+        .await
+        .map_err(|e| format!("Failed to save focus settings: {}", e))?;
+
+    Ok(settings)
+}
+
+/// Start a focus session with the saved settings. A running timer is adopted
+/// together with its project; otherwise a timer is started on `project`.
+#[tauri::command]
+pub async fn start_focus_session(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    focus_state: State<'_, FocusState>,
+    project: Option<String>,
+) -> Result<FocusStatus, String> {
+    let mut runtime = focus_state.lock().await;
+    if runtime.session.is_some() {
+        return Err("A focus session is already running".to_string());
+    }
+
+    let db = state.lock().await;
+    let settings = focus::load_settings(&db).await?;
+    settings.validate()?;
+    let project = non_empty(project);
+    let now = Utc::now();
+
+    let active = db.get_active_timer()
+        .await
+        .map_err(|e| format!("Failed to get active timer: {}", e))?;
+    let (project, time_entry_id) = match active {
+        Some(timer) if project.is_some() && timer.project != project => {
+            return Err(format!(
+                "A timer is already running on {}; stop it first",
+                timer.project.as_deref().unwrap_or("no project")
+            ));
+        }
+        Some(timer) => (timer.project, Some(timer.id)),
+        None => {
+            let time_entry_id = focus::start_work_timer(&db, project.as_deref(), now).await?;
+            (project, time_entry_id)
+        }
+    };
+
+    let session = FocusSession {
+        project,
+        phase: FocusPhase::Work,
+        cycle: 1,
+        phase_started_at: now,
+        phase_ends_at: now + focus::phase_length(&settings, FocusPhase::Work),
+        time_entry_id,
+        settings,
+    };
+    focus::announce(&app, Some(&session));
+    let status = focus::status(Some(&session), now);
+
+    runtime.session = Some(session);
+    runtime.replace_task(Some(focus::spawn_session_task(app, state.inner().clone(), focus_state.inner().clone())));
+
+    Ok(status)
+}
+
+/// Stop the focus session. Time tracked in the current work block is kept,
+/// but the unfinished block is not counted as a completed session.
+#[tauri::command]
+pub async fn stop_focus_session(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    focus_state: State<'_, FocusState>,
+) -> Result<FocusStatus, String> {
+    let mut runtime = focus_state.lock().await;
+    let session = runtime.session.take()
+        .ok_or_else(|| "No focus session is running".to_string())?;
+    runtime.replace_task(None);
+
+    let db = state.lock().await;
+    focus::stop_work_timer(&db, &session, Utc::now()).await?;
+    focus::announce(&app, None);
+
+    Ok(focus::status(None, Utc::now()))
+}
+
+/// End the current phase now, e.g. to cut a break short.
+#[tauri::command]
+pub async fn skip_focus_phase(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    focus_state: State<'_, FocusState>,
+) -> Result<FocusStatus, String> {
+    let mut runtime = focus_state.lock().await;
+    if runtime.session.is_none() {
+        return Err("No focus session is running".to_string());
+    }
+    runtime.replace_task(None);
+
+    let db = state.lock().await;
+    let now = Utc::now();
+    let mut session = runtime.session.take().unwrap();
+    if focus::advance(&app, &db, &mut session, now).await? {
+        runtime.session = Some(session);
+        runtime.replace_task(Some(focus::spawn_session_task(app, state.inner().clone(), focus_state.inner().clone())));
+    } else {
+        focus::announce(&app, None);
+    }
+
+    Ok(focus::status(runtime.session.as_ref(), now))
+}
+
+#[tauri::command]
+pub async fn get_focus_status(focus_state: State<'_, FocusState>) -> Result<FocusStatus, String> {
+    let runtime = focus_state.lock().await;
+    Ok(focus::status(runtime.session.as_ref(), Utc::now()))
+}
+
+#[tauri::command]
+pub async fn list_focus_sessions(
+    state: State<'_, AppState>,
+    range_start: Option<String>,
+    range_end: Option<String>,
+    project: Option<String>,
+) -> Result<Vec<FocusSessionResponse>, String> {
+    let db = state.lock().await;
+    let range_start = parse_optional_timestamp(range_start.as_deref(), "range start")?;
+    let range_end = parse_optional_timestamp(range_end.as_deref(), "range end")?;
+    let project = non_empty(project);
+
+    let sessions = db.get_focus_sessions(range_start, range_end, project.as_deref())
+        .await
+        .map_err(|e| format!("Failed to get focus sessions: {}", e))?;
+
+    Ok(sessions.into_iter().map(FocusSessionResponse::from).collect())
+}
+
+// Analytics commands
+/// Dashboard analytics for `[range_start, range_end)`, either bound optional.
+/// Streaks always look at the whole log and skip days off.
+#[tauri::command]
+pub async fn get_analytics(
+    state: State<'_, AppState>,
+    range_start: Option<String>,
+    range_end: Option<String>,
+    granularity: Granularity,
+) -> Result<Analytics, String> {
+    let db = state.lock().await;
+    let from = parse_optional_timestamp(range_start.as_deref(), "range start")?;
+    let to = parse_optional_timestamp(range_end.as_deref(), "range end")?;
+    let failed = |e: sqlx::Error| format!("Failed to compute analytics: {}", e);
+
+    let (total_entries, total_items) = db.count_entries_and_items(from, to).await.map_err(failed)?;
+    let heatmap = db.count_items_by_weekday_hour(from, to).await.map_err(failed)?;
+    let active_days = db.get_active_days().await.map_err(failed)?;
+    let calendar = goals::load_calendar(&db).await?;
+
+    Ok(Analytics {
+        range_start: from.map(|t| t.to_rfc3339()),
+        range_end: to.map(|t| t.to_rfc3339()),
+        granularity,
+        total_entries,
+        total_items,
+        items_per_period: db.count_items_by_period(from, to, granularity).await.map_err(failed)?,
+        items_per_type: db.count_items_by_type(from, to).await.map_err(failed)?,
+        top_tags: db.top_tags(from, to, analytics::TOP_LIMIT).await.map_err(failed)?,
+        top_people: db.top_people(from, to, analytics::TOP_LIMIT).await.map_err(failed)?,
+        top_projects: db.top_projects(from, to, analytics::TOP_LIMIT).await.map_err(failed)?,
+        heatmap: analytics::heatmap(&heatmap),
+        streaks: analytics::streaks(&active_days, &calendar, Local::now().date_naive()),
+    })
+}
+
+// Goal structs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateGoalRequest {
+    pub name: String,
+    pub item_type: Option<String>,
+    pub target_count: i64,
+    pub period: String,
+    pub sprint_days: Option<i64>,
+    pub sprint_start: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateGoalRequest {
+    pub id: String,
+    pub name: Option<String>,
+    pub item_type: Option<String>,
+    pub target_count: Option<i64>,
+    pub period: Option<String>,
+    pub sprint_days: Option<i64>,
+    pub sprint_start: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GoalResponse {
+    pub id: String,
+    pub name: String,
+    pub item_type: Option<String>,
+    pub target_count: i64,
+    pub period: String,
+    pub sprint_days: Option<i64>,
+    pub sprint_start: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<Goal> for GoalResponse {
+    fn from(goal: Goal) -> Self {
+        GoalResponse {
+            id: goal.id,
+            name: goal.name,
+            item_type: goal.item_type,
+            target_count: goal.target_count,
+            period: goal.period,
+            sprint_days: goal.sprint_days,
+            sprint_start: goal.sprint_start.map(|d| d.to_string()),
+            created_at: goal.created_at.to_rfc3339(),
+            updated_at: goal.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+fn parse_date(value: &str, what: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid {} (expected YYYY-MM-DD): {}", what, e))
+}
+
+fn validate_goal(goal: &Goal) -> Result<(), String> {
+    if goal.name.trim().is_empty() {
+        return Err("Goal name is required".to_string());
+    }
+    if goal.target_count < 1 {
+        return Err("Goal target must be at least 1".to_string());
+    }
+    goals::GoalPeriod::from_goal(goal).map(|_| ())
+}
+
+// Goal commands
+#[tauri::command]
+pub async fn get_work_calendar(state: State<'_, AppState>) -> Result<WorkCalendar, String> {
+    let db = state.lock().await;
+    goals::load_calendar(&db).await
+}
+
+#[tauri::command]
+pub async fn update_work_calendar(
+    state: State<'_, AppState>,
+    calendar: WorkCalendar,
+) -> Result<WorkCalendar, String> {
+    let db = state.lock().await;
+    calendar.validate()?;
+
+    let mut calendar = calendar;
+    calendar.working_days.sort_unstable();
+    calendar.working_days.dedup();
+    calendar.holidays.sort_unstable();
+    calendar.holidays.dedup();
+
+    let json = serde_json::to_string(&calendar)
+        .map_err(|e| format!("Failed to save working-days calendar: {}", e))?;
+    db.set_setting(goals::CALENDAR_SETTINGS_KEY, &json)
+        .await
+        .map_err(|e| format!("Failed to save working-days calendar: {}", e))?;
+
+    Ok(calendar)
+}
+
+#[tauri::command]
+pub async fn create_goal(
+    state: State<'_, AppState>,
+    request: CreateGoalRequest,
+) -> Result<GoalResponse, String> {
+    let db = state.lock().await;
+    let sprint_start = request.sprint_start.as_deref().map(|d| parse_date(d, "sprint start")).transpose()?;
+    let item_type = non_empty(request.item_type);
+
+    let draft = Goal {
+        id: String::new(),
+        name: request.name.trim().to_string(),
+        item_type: item_type.clone(),
+        target_count: request.target_count,
+        period: request.period.clone(),
+        sprint_days: request.sprint_days,
+        sprint_start,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    validate_goal(&draft)?;
+
+    let goal = db.create_goal(
+        &draft.name,
+        item_type.as_deref(),
+        request.target_count,
+        &request.period,
+        request.sprint_days,
+        sprint_start,
+    )
+    .await
+    .map_err(|e| format!("Failed to create goal: {}", e))?;
+
+    Ok(GoalResponse::from(goal))
+}
+
+#[tauri::command]
+pub async fn get_all_goals(state: State<'_, AppState>) -> Result<Vec<GoalResponse>, String> {
+    let db = state.lock().await;
+
+    let goals = db.get_goals()
+        .await
+        .map_err(|e| format!("Failed to get goals: {}", e))?;
+
+    Ok(goals.into_iter().map(GoalResponse::from).collect())
+}
+
+/// Update a goal. Omitted fields are left unchanged; an empty item type
+/// makes the goal count every item.
+#[tauri::command]
+pub async fn update_goal(
+    state: State<'_, AppState>,
+    request: UpdateGoalRequest,
+) -> Result<GoalResponse, String> {
+    let db = state.lock().await;
+
+    let mut goal = db.get_goal(&request.id)
+        .await
+        .map_err(|e| format!("Failed to get goal: {}", e))?
+        .ok_or_else(|| "Goal not found".to_string())?;
+
+    if let Some(name) = request.name {
+        goal.name = name.trim().to_string();
+    }
+    if let Some(item_type) = request.item_type {
+        goal.item_type = non_empty(Some(item_type));
+    }
+    if let Some(target_count) = request.target_count {
+        goal.target_count = target_count;
+    }
+    if let Some(period) = request.period {
+        goal.period = period;
+    }
+    if let Some(sprint_days) = request.sprint_days {
+        goal.sprint_days = Some(sprint_days);
+    }
+    if let Some(sprint_start) = request.sprint_start {
+        goal.sprint_start = Some(parse_date(&sprint_start, "sprint start")?);
+    }
+    validate_goal(&goal)?;
+
+    let goal = db.update_goal(&goal)
+        .await
+        .map_err(|e| format!("Failed to update goal: {}", e))?;
+
+    Ok(GoalResponse::from(goal))
+}
+
+#[tauri::command]
+pub async fn delete_goal(
+    state: State<'_, AppState>,
+    goal_id: String,
+) -> Result<(), String> {
+    let db = state.lock().await;
+
+    db.delete_goal(&goal_id)
+        .await
+        .map_err(|e| format!("Failed to delete goal: {}", e))?;
+
+    Ok(())
+}
+
+/// Progress on every goal in its current period, with streaks.
+#[tauri::command]
+pub async fn get_goal_progress(state: State<'_, AppState>) -> Result<Vec<GoalProgress>, String> {
+    let db = state.lock().await;
+    let calendar = goals::load_calendar(&db).await?;
+
+    goals::goal_progress(&db, &calendar, Local::now().date_naive()).await
+}
+
+// Nudge commands
+#[tauri::command]
+pub async fn get_nudge_settings(state: State<'_, AppState>) -> Result<NudgeSettings, String> {
+    let db = state.lock().await;
+    nudges::load_settings(&db).await
+}
+
+/// Save the nudge settings; the scheduler reads them on its next check.
+#[tauri::command]
+pub async fn update_nudge_settings(
+    state: State<'_, AppState>,
+    settings: NudgeSettings,
+) -> Result<NudgeSettings, String> {
+    let db = state.lock().await;
+    settings.validate()?;
+
+    let json = serde_json::to_string(&settings)
+        .map_err(|e| format!("Failed to save nudge settings: {}", e))?;
+    db.set_setting(nudges::NUDGE_SETTINGS_KEY, &json)
+        .await
+        .map_err(|e| format!("Failed to save nudge settings: {}", e))?;
+
+    Ok(settings)
+}
+
+/// The nudge that led to the quick-entry window, if any. Taking it clears it
+/// and resets the tray tooltip.
+#[tauri::command]
+pub async fn take_pending_nudge(
+    app: AppHandle,
+    nudge_state: State<'_, NudgeState>,
+) -> Result<Option<SmartPrompt>, String> {
+    let prompt = nudge_state.lock().await.take();
+    if prompt.is_some() {
+        notifications::set_tooltip(&app, notifications::DEFAULT_TOOLTIP);
+    }
+    Ok(prompt)
+}
+
+#[tauri::command]
+pub async fn open_quick_entry(app: AppHandle) -> Result<(), String> {
+    tray::open_quick_entry(&app)
+}
+
+// Reminder commands
+#[tauri::command]
+pub async fn get_reminder_settings(state: State<'_, AppState>) -> Result<ReminderSettings, String> {
+    let db = state.lock().await;
+    reminders::load_settings(&db).await
+}
+
+#[tauri::command]
+pub async fn update_reminder_settings(
+    state: State<'_, AppState>,
+    settings: ReminderSettings,
+) -> Result<ReminderSettings, String> {
+    let db = state.lock().await;
+    settings.validate()?;
+
+    let json = serde_json::to_string(&settings)
+        .map_err(|e| format!("Failed to save reminder settings: {}", e))?;
+    db.set_setting(reminders::REMINDER_SETTINGS_KEY, &json)
+        .await
+        .map_err(|e| format!("Failed to save reminder settings: {}", e))?;
+
+    Ok(settings)
+}
+
+/// Open actions with a due date and where their reminders stand, soonest
+/// due first.
+#[tauri::command]
+pub async fn list_reminders(state: State<'_, AppState>) -> Result<Vec<Reminder>, String> {
+    let db = state.lock().await;
+    let reminders = reminders::collect(&db, Utc::now()).await?;
+
+    Ok(reminders.into_iter().map(|(reminder, _)| reminder).collect())
+}
+
+/// Remind again at `until`, even about offsets already sent.
+#[tauri::command]
+pub async fn snooze_reminder(
+    state: State<'_, AppState>,
+    target_kind: String,
+    target_id: String,
+    until: String,
+) -> Result<Reminder, String> {
+    let db = state.lock().await;
+    let until = parse_timestamp(&until, "snooze time")?;
+    if until <= Utc::now() {
+        return Err("Snooze until a time in the future".to_string());
+    }
+
+    let (mut reminder, mut reminder_state) = find_reminder(&db, &target_kind, &target_id).await?;
+    reminder_state.snoozed_until = Some(until);
+    reminder_state.dismissed = false;
+    db.save_reminder_state(&reminder_state)
+        .await
+        .map_err(|e| format!("Failed to snooze reminder: {}", e))?;
+
+    reminder.snoozed_until = Some(until.to_rfc3339());
+    reminder.dismissed = false;
+    Ok(reminder)
+}
+
+/// Stop reminding about the current due date.
+#[tauri::command]
+pub async fn dismiss_reminder(
+    state: State<'_, AppState>,
+    target_kind: String,
+    target_id: String,
+) -> Result<(), String> {
+    let db = state.lock().await;
+
+    let (_, mut reminder_state) = find_reminder(&db, &target_kind, &target_id).await?;
+    reminder_state.dismissed = true;
+    reminder_state.snoozed_until = None;
+    db.save_reminder_state(&reminder_state)
+        .await
+        .map_err(|e| format!("Failed to dismiss reminder: {}", e))?;
+
+    Ok(())
+}
+
+async fn find_reminder(db: &Database, target_kind: &str, target_id: &str) -> Result<(Reminder, ReminderState), String> {
+    let kind = ReminderTarget::parse(target_kind)?;
+    reminders::collect(db, Utc::now())
+        .await?
+        .into_iter()
+        .find(|(reminder, _)| reminder.target_kind == kind && reminder.target_id == target_id)
+        .ok_or_else(|| "Nothing open with a due date to remind about".to_string())
+}
+
+/// Mark a meeting action `open` or `done`. Closing an action ends its
+/// reminders and lists it under "Actions closed" in digests.
+#[tauri::command]
+pub async fn update_meeting_action_status(
+    state: State<'_, AppState>,
+    action_id: String,
+    status: String,
+) -> Result<(), String> {
+    let db = state.lock().await;
+    if !matches!(status.as_str(), "open" | "done") {
+        return Err(format!("Unknown action status: {}", status));
+    }
+
+    let updated = db.update_meeting_action_status(&action_id, &status)
+        .await
+        .map_err(|e| format!("Failed to update action: {}", e))?;
+    if !updated {
+        return Err("Action not found".to_string());
+    }
+
+    Ok(())
+}
+
+// Digest structs
+#[derive(Debug, Serialize)]
+pub struct DigestResponse {
+    pub template: String,
+    pub content: String,
+    pub digest: Digest,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DigestTemplatesResponse {
+    /// Where the templates live, for editing them.
+    pub directory: String,
+    pub templates: Vec<String>,
+}
+
+/// The digest template directory, with the default templates in place.
+fn digest_template_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let data_dir = tauri::api::path::app_data_dir(&app.config())
+        .ok_or_else(|| "No app data directory available for templates".to_string())?;
+    let dir = digest::template_dir(&data_dir);
+    digest::ensure_default_templates(&dir)?;
+    Ok(dir)
+}
+
+// Digest commands
+/// Summarise `[range_start, range_end)` by project and render it with a
+/// template from the data directory, `digest.md` unless another is named.
+#[tauri::command]
+pub async fn generate_digest(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    range_start: String,
+    range_end: String,
+    template: Option<String>,
+) -> Result<DigestResponse, String> {
+    let db = state.lock().await;
+    let range_start = parse_timestamp(&range_start, "range start")?;
+    let range_end = parse_timestamp(&range_end, "range end")?;
+    if range_end <= range_start {
+        return Err("The range must end after it starts".to_string());
+    }
+    let template = template.unwrap_or_else(|| "digest.md".to_string());
+    let now = Utc::now();
+
+    let items = db.get_items_between(Some(range_start), Some(range_end))
+        .await
+        .map_err(|e| format!("Failed to get items: {}", e))?;
+    let actions = db.get_meeting_actions_touched_between(range_start, range_end)
+        .await
+        .map_err(|e| format!("Failed to get actions: {}", e))?;
+    let meetings = db.get_all_meetings()
+        .await
+        .map_err(|e| format!("Failed to get meetings: {}", e))?;
+    let occurrences = expand_occurrences(meetings, range_start, range_end)?;
+    let time_entries = db.get_time_entries(Some(range_start), Some(range_end), None)
+        .await
+        .map_err(|e| format!("Failed to get time entries: {}", e))?;
+    drop(db);
+
+    let digest = digest::build_digest(range_start, range_end, &items, &actions, &occurrences, &time_entries, now);
+    let content = digest::render(&digest_template_dir(&app)?, &template, &digest)?;
+
+    Ok(DigestResponse { template, content, digest })
+}
+
+/// The digest templates available, writing the defaults on first use.
+#[tauri::command]
+pub async fn list_digest_templates(app: AppHandle) -> Result<DigestTemplatesResponse, String> {
+    let dir = digest_template_dir(&app)?;
+
+    Ok(DigestTemplatesResponse {
+        templates: digest::list_templates(&dir)?,
+        directory: dir.display().to_string(),
+    })
+}
+
+// Standup structs
+#[derive(Debug, Serialize)]
+pub struct StandupResponse {
+    /// Paste-ready text.
+    pub text: String,
+    pub standup: Standup,
+}
+
+/// Yesterday / Today / Blockers notes for the local day `date` (YYYY-MM-DD),
+/// today if omitted. "Yesterday" reaches back to the previous working day.
+#[tauri::command]
+pub async fn generate_standup(state: State<'_, AppState>, date: Option<String>) -> Result<StandupResponse, String> {
+    let db = state.lock().await;
+    let date = match date {
+        Some(date) => parse_date(&date, "date")?,
+        None => Local::now().date_naive(),
+    };
+    let calendar = goals::load_calendar(&db).await?;
+    let since = standup::previous_working_day(&calendar, date);
+
+    let from = timesheet::start_of_day(&Local, since);
+    let to = timesheet::start_of_day(&Local, date + Duration::days(1));
+    let items = db.get_items_between(Some(from), Some(to))
+        .await
+        .map_err(|e| format!("Failed to get items: {}", e))?;
+    let actions = db.get_open_actions_with_due_dates()
+        .await
+        .map_err(|e| format!("Failed to get actions: {}", e))?;
+    drop(db);
+
+    let standup = standup::build_standup(date, since, &items, &actions);
+    let text = standup::render_text(&standup);
+
+    Ok(StandupResponse { text, standup })
+}
+
+// Decision register structs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateDecisionRequest {
+    pub item_id: String,
+    /// Unchanged if omitted; an empty string clears it.
+    pub context: Option<String>,
+    /// `proposed`, `accepted` or `superseded`; unchanged if omitted.
+    pub status: Option<String>,
+    /// Unchanged if omitted; an empty string clears it.
+    pub supersedes_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdrExportResponse {
+    pub directory: String,
+    pub files: Vec<String>,
+}
+
+async fn load_decision_register(db: &Database) -> Result<Vec<Decision>, String> {
+    let items = db.get_items_of_type(decisions::DECISION_TYPE)
+        .await
+        .map_err(|e| format!("Failed to get decisions: {}", e))?;
+    let records = db.get_decision_records()
+        .await
+        .map_err(|e| format!("Failed to get decision records: {}", e))?;
+    Ok(decisions::build_register(&items, records))
+}
+
+/// Every decision, numbered in the order logged, optionally narrowed to a
+/// status or project.
+#[tauri::command]
+pub async fn list_decisions(
+    state: State<'_, AppState>,
+    status: Option<String>,
+    project: Option<String>,
+) -> Result<Vec<Decision>, String> {
+    let db = state.lock().await;
+    let status = status.as_deref().map(DecisionStatus::parse).transpose()?;
+    let register = load_decision_register(&db).await?;
+
+    Ok(register
+        .into_iter()
+        .filter(|d| status.is_none_or(|s| d.status == s))
+        .filter(|d| project.is_none() || d.project == project)
+        .collect())
+}
+
+/// Set the context, status and superseded decision of a Decision item.
+/// Superseding a decision marks that one as superseded; one that is no
+/// longer superseded by anything goes back to accepted.
+#[tauri::command]
+pub async fn update_decision(
+    state: State<'_, AppState>,
+    request: UpdateDecisionRequest,
+) -> Result<Decision, String> {
+    let db = state.lock().await;
+    let item_type = db.get_item_type(&request.item_id)
+        .await
+        .map_err(|e| format!("Failed to get item: {}", e))?
+        .ok_or_else(|| format!("Item not found: {}", request.item_id))?;
+    if item_type != decisions::DECISION_TYPE {
+        return Err(format!("Only Decision items are in the register, not {} items", item_type));
+    }
+
+    let records: HashMap<String, DecisionRecord> = db.get_decision_records()
+        .await
+        .map_err(|e| format!("Failed to get decision records: {}", e))?
+        .into_iter()
+        .map(|record| (record.entry_item_id.clone(), record))
+        .collect();
+    let existing = records.get(&request.item_id);
+    let status = match request.status.as_deref() {
+        Some(status) => DecisionStatus::parse(status)?,
+        None => existing
+            .and_then(|r| DecisionStatus::parse(&r.status).ok())
+            .unwrap_or(DecisionStatus::Proposed),
+    };
+    let supersedes_id = match request.supersedes_id {
+        Some(id) => Some(id).filter(|id| !id.trim().is_empty()),
+        None => existing.and_then(|r| r.supersedes_id.clone()),
+    };
+    let context = match request.context {
+        Some(context) => Some(context).filter(|c| !c.trim().is_empty()),
+        None => existing.and_then(|r| r.context.clone()),
+    };
+
+    if let Some(earlier) = &supersedes_id {
+        let earlier_type = db.get_item_type(earlier)
+            .await
+            .map_err(|e| format!("Failed to get item: {}", e))?;
+        if earlier_type.as_deref() != Some(decisions::DECISION_TYPE) {
+            return Err(format!("Superseded decision not found: {}", earlier));
+        }
+        // Following the chain from the earlier decision must not lead back here.
+        let mut next = Some(earlier.clone());
+        while let Some(id) = next {
+            if id == request.item_id {
+                return Err("A decision cannot supersede itself or a decision that supersedes it".to_string());
+            }
+            next = records.get(&id).and_then(|r| r.supersedes_id.clone());
+        }
+    }
+
+    let record = DecisionRecord {
+        entry_item_id: request.item_id.clone(),
+        context,
+        status: status.as_str().to_string(),
+        supersedes_id,
+        updated_at: Utc::now(),
+    };
+    let changed = decisions::supersession_changes(&records, record);
+    db.save_decision_records(&changed)
+        .await
+        .map_err(|e| format!("Failed to save decision: {}", e))?;
+
+    load_decision_register(&db)
+        .await?
+        .into_iter()
+        .find(|d| d.item_id == request.item_id)
+        .ok_or_else(|| format!("Decision not found: {}", request.item_id))
+}
+
+/// Write every decision as a numbered ADR Markdown file into `directory`.
+#[tauri::command]
+pub async fn export_decisions_adr(state: State<'_, AppState>, directory: String) -> Result<AdrExportResponse, String> {
+    if directory.trim().is_empty() {
+        return Err("Choose a directory to export the decisions to".to_string());
+    }
+    let db = state.lock().await;
+    let register = load_decision_register(&db).await?;
+    drop(db);
+
+    let files = decisions::write_adrs(&PathBuf::from(&directory), &register)?
+        .into_iter()
+        .map(|path| path.display().to_string())
+        .collect();
+
+    Ok(AdrExportResponse { directory, files })
+}
+
+// 1:1 agenda structs
+#[derive(Debug, Serialize)]
+pub struct OneOnOneAgendaResponse {
+    pub markdown: String,
+    pub agenda: OneOnOneAgenda,
+    /// The 1:1 created for the agenda, if one was asked for.
+    pub meeting: Option<MeetingResponse>,
+}
+
+/// Agenda for a 1:1 with a person, covering everything since `since` or,
+/// by default, since the last 1:1 with them. Given `schedule_at`, a 1:1 is
+/// created at that time with the agenda as its description.
+#[tauri::command]
+pub async fn build_one_on_one_agenda(
+    state: State<'_, AppState>,
+    person_id: String,
+    since: Option<String>,
+    schedule_at: Option<String>,
+) -> Result<OneOnOneAgendaResponse, String> {
+    let db = state.lock().await;
+    let now = Utc::now();
+    let person = db.get_person(&person_id)
+        .await
+        .map_err(|e| format!("Failed to get person: {}", e))?
+        .ok_or_else(|| format!("Person not found: {}", person_id))?;
+    let since = parse_optional_timestamp(since.as_deref(), "since")?;
+    let schedule_at = parse_optional_timestamp(schedule_at.as_deref(), "meeting start")?;
+
+    let one_on_ones = db.get_meetings_with_attendee(&person.name, one_on_one::ONE_ON_ONE_TYPE)
+        .await
+        .map_err(|e| format!("Failed to get meetings: {}", e))?;
+    let one_on_one_ids: HashSet<String> = one_on_ones.iter().map(|m| m.id.clone()).collect();
+    // Cancelled occurrences are already left out as exceptions; cancelled
+    // meetings never happened either.
+    let last_meeting = expand_occurrences(one_on_ones, now - Duration::days(366), now)?
+        .iter()
+        .rfind(|o| o.status != "cancelled")
+        .map(|o| parse_timestamp(&o.occurrence_start, "occurrence start"))
+        .transpose()?;
+    let since = since
+        .or(last_meeting)
+        .unwrap_or(now - Duration::days(one_on_one::DEFAULT_LOOKBACK_DAYS));
+
+    let open_actions = db.get_open_meeting_actions()
+        .await
+        .map_err(|e| format!("Failed to get actions: {}", e))?;
+    let items = db.get_items_for_person(&person.id, Some(since))
+        .await
+        .map_err(|e| format!("Failed to get items: {}", e))?;
+
+    let agenda = one_on_one::build_agenda(&person.name, since, last_meeting, &open_actions, &one_on_one_ids, &items, now);
+    let markdown = one_on_one::render_markdown(&agenda);
+
+    let meeting = match schedule_at {
+        Some(start) => {
+            let end = start + Duration::minutes(one_on_one::DEFAULT_DURATION_MINUTES);
+            let title = format!("1:1 with {}", person.name);
+            let meeting = db.create_meeting(NewMeeting {
+                title: &title,
+                description: Some(&markdown),
+                start_time: Some(start),
+                end_time: Some(end),
+                meeting_type: Some(one_on_one::ONE_ON_ONE_TYPE),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| format!("Failed to create meeting: {}", e))?;
+            db.add_meeting_attendee(&meeting.id, &person.name, None, None)
+                .await
+                .map_err(|e| format!("Failed to add attendee: {}", e))?;
+            Some(MeetingResponse::from(meeting))
+        }
+        None => None,
+    };
+
+    Ok(OneOnOneAgendaResponse { markdown, agenda, meeting })
+}
+
+/// Mark an item as waiting on `person`, optionally expected by a date
+/// (YYYY-MM-DD or RFC3339). The person is linked to the item as well.
+#[tauri::command]
+pub async fn set_waiting_for(
+    state: State<'_, AppState>,
+    item_id: String,
+    person: String,
+    expected_by: Option<String>,
+) -> Result<WaitingFor, String> {
+    let db = state.lock().await;
+    let person = person.trim();
+    if person.is_empty() {
+        return Err("Choose who the item is waiting on".to_string());
+    }
+    let expected_by = expected_by.as_deref()
+        .filter(|d| !d.trim().is_empty())
+        .map(parse_due_date)
+        .transpose()?;
+    db.get_item_type(&item_id)
+        .await
+        .map_err(|e| format!("Failed to get item: {}", e))?
+        .ok_or_else(|| format!("Item not found: {}", item_id))?;
+
+    let person = db.get_or_create_person(person)
+        .await
+        .map_err(|e| format!("Failed to create person: {}", e))?;
+    db.link_item_person(&item_id, &person.id)
+        .await
+        .map_err(|e| format!("Failed to link person: {}", e))?;
+    db.set_waiting_for(&item_id, &person.id, expected_by)
+        .await
+        .map_err(|e| format!("Failed to mark item as waiting: {}", e))?;
+
+    db.get_waiting_for(true)
+        .await
+        .map_err(|e| format!("Failed to get waiting-for items: {}", e))?
+        .into_iter()
+        .map(|(waiting, _, _)| waiting)
+        .find(|waiting| waiting.entry_item_id == item_id)
+        .ok_or_else(|| format!("Item not found: {}", item_id))
+}
+
+/// The wait is over; the item stays listed only with `include_resolved`.
+#[tauri::command]
+pub async fn resolve_waiting_for(state: State<'_, AppState>, item_id: String) -> Result<(), String> {
+    let db = state.lock().await;
+    let updated = db.resolve_waiting_for(&item_id, Some(Utc::now()))
+        .await
+        .map_err(|e| format!("Failed to resolve waiting-for item: {}", e))?;
+    if !updated {
+        return Err(format!("Item is not waiting on anyone: {}", item_id));
+    }
+    Ok(())
+}
+
+/// Stop tracking an item as waiting on someone.
+#[tauri::command]
+pub async fn clear_waiting_for(state: State<'_, AppState>, item_id: String) -> Result<(), String> {
+    let db = state.lock().await;
+    db.delete_waiting_for(&item_id)
+        .await
+        .map_err(|e| format!("Failed to clear waiting-for item: {}", e))?;
+    Ok(())
+}
+
+/// What we are waiting on, grouped by person with the most overdue first.
+#[tauri::command]
+pub async fn list_waiting_for(
+    state: State<'_, AppState>,
+    include_resolved: Option<bool>,
+) -> Result<Vec<WaitingForGroup>, String> {
+    let db = state.lock().await;
+    let rows = db.get_waiting_for(include_resolved.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to get waiting-for items: {}", e))?;
+
+    Ok(waiting::group_by_person(rows, Utc::now()))
+}
+
+/// Append a step to an item's checklist.
+#[tauri::command]
+pub async fn add_checklist_row(
+    state: State<'_, AppState>,
+    item_id: String,
+    text: String,
+    assignee: Option<String>,
+) -> Result<ChecklistRow, String> {
+    let db = state.lock().await;
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("A checklist row needs some text".to_string());
+    }
+    db.get_item_type(&item_id)
+        .await
+        .map_err(|e| format!("Failed to get item: {}", e))?
+        .ok_or_else(|| format!("Item not found: {}", item_id))?;
+    let assignee = assignee.as_deref().map(str::trim).filter(|a| !a.is_empty());
+
+    db.add_checklist_row(&item_id, text, assignee)
+        .await
+        .map_err(|e| format!("Failed to add checklist row: {}", e))
+}
+
+/// Flip a checklist row between done and not done.
+#[tauri::command]
+pub async fn toggle_checklist_row(state: State<'_, AppState>, row_id: String) -> Result<ChecklistRow, String> {
+    let db = state.lock().await;
+    let row = db.get_checklist_row(&row_id)
+        .await
+        .map_err(|e| format!("Failed to get checklist row: {}", e))?
+        .ok_or_else(|| format!("Checklist row not found: {}", row_id))?;
+
+    db.set_checklist_row_done(&row_id, !row.done)
+        .await
+        .map_err(|e| format!("Failed to update checklist row: {}", e))?;
+
+    db.get_checklist_row(&row_id)
+        .await
+        .map_err(|e| format!("Failed to get checklist row: {}", e))?
+        .ok_or_else(|| format!("Checklist row not found: {}", row_id))
+}
+
+/// Put an item's checklist rows in the given order; every row of the item
+/// must be listed exactly once.
+#[tauri::command]
+pub async fn reorder_checklist_rows(
+    state: State<'_, AppState>,
+    item_id: String,
+    ordered_ids: Vec<String>,
+) -> Result<Vec<ChecklistRow>, String> {
+    let db = state.lock().await;
+    let rows = db.get_item_checklist(&item_id)
+        .await
+        .map_err(|e| format!("Failed to get checklist: {}", e))?;
+
+    let mut current: Vec<&str> = rows.iter().map(|row| row.id.as_str()).collect();
+    let mut requested: Vec<&str> = ordered_ids.iter().map(String::as_str).collect();
+    current.sort_unstable();
+    requested.sort_unstable();
+    if current != requested {
+        return Err("The new order must list every row of the checklist exactly once".to_string());
+    }
+
+    db.reorder_checklist(&item_id, &ordered_ids)
+        .await
+        .map_err(|e| format!("Failed to reorder checklist: {}", e))?;
+
+    db.get_item_checklist(&item_id)
+        .await
+        .map_err(|e| format!("Failed to get checklist: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_checklist_row(state: State<'_, AppState>, row_id: String) -> Result<(), String> {
+    let db = state.lock().await;
+    let deleted = db.delete_checklist_row(&row_id)
+        .await
+        .map_err(|e| format!("Failed to delete checklist row: {}", e))?;
+    if !deleted {
+        return Err(format!("Checklist row not found: {}", row_id));
+    }
+    Ok(())
+}
+
+// Custom field structs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateCustomFieldRequest {
+    pub name: String,
+    /// `text`, `number`, `date` or `enum`.
+    pub field_type: String,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateCustomFieldRequest {
+    pub name: Option<String>,
+    pub options: Option<Vec<String>>,
+}
+
+#[tauri::command]
+pub async fn create_custom_field(
+    state: State<'_, AppState>,
+    request: CreateCustomFieldRequest,
+) -> Result<CustomField, String> {
+    let db = state.lock().await;
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err("A custom field needs a name".to_string());
+    }
+    let field_type = FieldType::parse(&request.field_type)?;
+    let options = custom_fields::clean_options(field_type, &request.options)?;
+    check_field_name_free(&db, name, None).await?;
+
+    db.create_custom_field(name, field_type.as_str(), &options)
+        .await
+        .map_err(|e| format!("Failed to create custom field: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_custom_fields(state: State<'_, AppState>) -> Result<Vec<CustomField>, String> {
+    let db = state.lock().await;
+    db.get_custom_fields()
+        .await
+        .map_err(|e| format!("Failed to get custom fields: {}", e))
+}
+
+/// Field names are matched ignoring case, so they must be unique that way.
+async fn check_field_name_free(db: &Database, name: &str, except_id: Option<&str>) -> Result<(), String> {
+    let fields = db.get_custom_fields()
+        .await
+        .map_err(|e| format!("Failed to get custom fields: {}", e))?;
+    match fields.iter().find(|f| f.name.eq_ignore_ascii_case(name) && Some(f.id.as_str()) != except_id) {
+        Some(existing) => Err(format!("A custom field named '{}' already exists", existing.name)),
+        None => Ok(()),
+    }
+}
+
+/// Rename a field or change its enum options. The type is fixed once
+/// created, and options still in use cannot be removed.
+#[tauri::command]
+pub async fn update_custom_field(
+    state: State<'_, AppState>,
+    id: String,
+    request: UpdateCustomFieldRequest,
+) -> Result<CustomField, String> {
+    let db = state.lock().await;
+    let mut field = db.get_custom_field(&id)
+        .await
+        .map_err(|e| format!("Failed to get custom field: {}", e))?
+        .ok_or_else(|| format!("Custom field not found: {}", id))?;
+
+    if let Some(name) = request.name {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("A custom field needs a name".to_string());
+        }
+        check_field_name_free(&db, name, Some(&id)).await?;
+        field.name = name.to_string();
+    }
+    if let Some(options) = request.options {
+        let options = custom_fields::clean_options(FieldType::parse(&field.field_type)?, &options)?;
+        let in_use = db.get_custom_field_values_in_use(&id)
+            .await
+            .map_err(|e| format!("Failed to get custom field values: {}", e))?;
+        // Values match options ignoring case, so recasing an option in use is fine
+        if let Some(value) = in_use.iter().find(|value| !options.iter().any(|o| o.eq_ignore_ascii_case(value))) {
+            return Err(format!("'{}' is still used by items and cannot be removed", value));
+        }
+        field.options = options;
+    }
+
+    db.update_custom_field(&field)
+        .await
+        .map_err(|e| format!("Failed to update custom field: {}", e))
+}
+
+/// Delete a field along with every item's value for it.
+#[tauri::command]
+pub async fn delete_custom_field(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    let db = state.lock().await;
+    db.delete_custom_field(&id)
+        .await
+        .map_err(|e| format!("Failed to delete custom field: {}", e))
+}
+
+/// Suggest tags, people, projects or Jira keys (`kind`) matching what has
+/// been typed, best match and most used lately first.
+#[tauri::command]
+pub async fn autocomplete(
+    state: State<'_, AppState>,
+    kind: String,
+    prefix: String,
+    limit: Option<usize>,
+) -> Result<Vec<Suggestion>, String> {
+    let kind = SuggestionKind::parse(&kind)?;
+    let limit = limit.unwrap_or(autocomplete::DEFAULT_LIMIT).clamp(1, autocomplete::MAX_LIMIT);
+
+    let db = state.lock().await;
+    let now = Utc::now();
+    let usage = db.get_usage_by_day(kind, autocomplete::recent_since(now))
+        .await
+        .map_err(|e| format!("Failed to get suggestions: {}", e))?;
+
+    Ok(autocomplete::rank(usage, &prefix, limit, now.date_naive()))
+}
@@ -0,0 +1,334 @@
+// CSV export of entry items, one row per item. Rows are written with the
+// `csv` crate as RFC 4180 asks: CRLF line endings, and fields quoted when
+// they contain commas, quotes or line breaks. Columns are the standard
+// ones followed by custom fields, or any selection of them by name. An
+// optional byte order mark makes Excel read the file as UTF-8.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::database::{CustomField, EntryItemWithMetadata};
+
+/// Columns every export can have, in their default order.
+pub const STANDARD_COLUMNS: [&str; 8] = ["Date", "Time", "Type", "Content", "Project", "Tags", "Jira", "People"];
+
+/// Separator between tags, Jira keys and people in one cell.
+const LIST_SEPARATOR: &str = ";";
+
+const BOM: &str = "\u{feff}";
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsvExportOptions {
+    /// Column names in the order wanted; all columns when left out.
+    pub columns: Option<Vec<String>>,
+    /// Start with a UTF-8 byte order mark, for Excel.
+    pub bom: bool,
+    /// First and last day to include, as YYYY-MM-DD in the Date column's time.
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub project: Option<String>,
+    pub tag: Option<String>,
+    pub item_type: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Column {
+    Date,
+    Time,
+    Type,
+    Content,
+    Project,
+    Tags,
+    Jira,
+    People,
+    Field(CustomField),
+}
+
+impl Column {
+    fn header(&self) -> &str {
+        match self {
+            Column::Date => "Date",
+            Column::Time => "Time",
+            Column::Type => "Type",
+            Column::Content => "Content",
+            Column::Project => "Project",
+            Column::Tags => "Tags",
+            Column::Jira => "Jira",
+            Column::People => "People",
+            Column::Field(field) => &field.name,
+        }
+    }
+
+    fn standard(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "date" => Some(Column::Date),
+            "time" => Some(Column::Time),
+            "type" => Some(Column::Type),
+            "content" => Some(Column::Content),
+            "project" => Some(Column::Project),
+            "tags" => Some(Column::Tags),
+            "jira" => Some(Column::Jira),
+            "people" => Some(Column::People),
+            _ => None,
+        }
+    }
+
+    fn cell(&self, timestamp: DateTime<Utc>, item: &EntryItemWithMetadata) -> String {
+        match self {
+            Column::Date => timestamp.format("%Y-%m-%d").to_string(),
+            Column::Time => timestamp.format("%H:%M:%S").to_string(),
+            Column::Type => item.item.item_type.clone(),
+            Column::Content => item.item.content.clone(),
+            Column::Project => item.item.project.clone().unwrap_or_default(),
+            Column::Tags => item.tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join(LIST_SEPARATOR),
+            Column::Jira => item.jira_refs.iter().map(|j| j.jira_key.as_str()).collect::<Vec<_>>().join(LIST_SEPARATOR),
+            Column::People => item.people.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(LIST_SEPARATOR),
+            Column::Field(field) => item.fields.iter()
+                .find(|v| v.field_id == field.id)
+                .map(|v| v.value.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The columns to write. Standard columns win over custom fields of the
+/// same name; names are matched case-insensitively and repeats dropped.
+pub fn resolve_columns(requested: Option<&[String]>, fields: &[CustomField]) -> Result<Vec<Column>, String> {
+    let Some(requested) = requested else {
+        let standard = STANDARD_COLUMNS.iter().filter_map(|name| Column::standard(name));
+        return Ok(standard.chain(fields.iter().cloned().map(Column::Field)).collect());
+    };
+
+    let mut columns: Vec<Column> = Vec::new();
+    for name in requested.iter().map(|n| n.trim()).filter(|n| !n.is_empty()) {
+        let column = match Column::standard(name) {
+            Some(column) => column,
+            None => fields.iter()
+                .find(|field| field.name.eq_ignore_ascii_case(name))
+                .cloned()
+                .map(Column::Field)
+                .ok_or_else(|| format!("Unknown column: {}", name))?,
+        };
+        if !columns.iter().any(|c| c.header().eq_ignore_ascii_case(column.header())) {
+            columns.push(column);
+        }
+    }
+    if columns.is_empty() {
+        return Err("Choose at least one column to export".to_string());
+    }
+    Ok(columns)
+}
+
+/// Conditions on items besides their date, each compared case-insensitively.
+#[derive(Debug, Default, Clone)]
+pub struct ItemFilter {
+    pub project: Option<String>,
+    pub tag: Option<String>,
+    pub item_type: Option<String>,
+}
+
+impl ItemFilter {
+    pub fn from_options(options: &CsvExportOptions) -> Self {
+        let clean = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+        ItemFilter {
+            project: clean(&options.project),
+            tag: clean(&options.tag),
+            item_type: clean(&options.item_type),
+        }
+    }
+
+    pub fn matches(&self, item: &EntryItemWithMetadata) -> bool {
+        let project = self.project.as_deref().is_none_or(|wanted| {
+            item.item.project.as_deref().is_some_and(|p| p.eq_ignore_ascii_case(wanted))
+        });
+        let tag = self.tag.as_deref().is_none_or(|wanted| item.tags.iter().any(|t| t.name.eq_ignore_ascii_case(wanted)));
+        let item_type = self.item_type.as_deref().is_none_or(|wanted| item.item.item_type.eq_ignore_ascii_case(wanted));
+        project && tag && item_type
+    }
+}
+
+/// `[from, to)` in UTC for the inclusive days `from` to `to`.
+pub fn day_range(from: Option<NaiveDate>, to: Option<NaiveDate>) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    let start = |day: NaiveDate| day.and_hms_opt(0, 0, 0).map(|t| t.and_utc());
+    (
+        from.and_then(start),
+        to.and_then(|day| day.succ_opt()).and_then(start),
+    )
+}
+
+/// The CSV text for `rows`, with a header row.
+pub fn write_csv(columns: &[Column], rows: &[(DateTime<Utc>, EntryItemWithMetadata)], bom: bool) -> Result<String, String> {
+    let mut writer = csv::WriterBuilder::new()
+        .terminator(csv::Terminator::CRLF)
+        .from_writer(Vec::new());
+
+    writer.write_record(columns.iter().map(Column::header))
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    for (timestamp, item) in rows {
+        writer.write_record(columns.iter().map(|column| column.cell(*timestamp, item)))
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| format!("Failed to write CSV: {}", e))?;
+    let text = String::from_utf8(bytes).map_err(|e| format!("Failed to write CSV: {}", e))?;
+    Ok(if bom { format!("{}{}", BOM, text) } else { text })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    use crate::csv_import::{self, CsvMapping};
+    use crate::database::{EntryItem, ItemFieldValue, JiraRef, Person, Tag};
+
+    const AWKWARD: &str = "a, \"quoted\" line\r\nand another\nwith ✨ emoji 🎉";
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 5, hour, 30, 15).unwrap()
+    }
+
+    fn notes_field() -> CustomField {
+        CustomField {
+            id: "field-notes".to_string(),
+            name: "Notes, \"long\"".to_string(),
+            field_type: "text".to_string(),
+            options: vec![],
+            created_at: at(0),
+            updated_at: at(0),
+        }
+    }
+
+    fn tag(name: &str) -> Tag {
+        Tag {
+            id: format!("tag-{}", name),
+            name: name.to_string(),
+            description: None,
+            color: "#000000".to_string(),
+            category: None,
+            created_at: at(0),
+            updated_at: at(0),
+        }
+    }
+
+    fn person(name: &str) -> Person {
+        Person { id: format!("person-{}", name), name: name.to_string(), created_at: at(0) }
+    }
+
+    fn item(id: &str, content: &str, project: Option<&str>) -> EntryItemWithMetadata {
+        EntryItemWithMetadata {
+            item: EntryItem {
+                id: id.to_string(),
+                entry_id: "entry".to_string(),
+                item_type: "Note".to_string(),
+                content: content.to_string(),
+                project: project.map(str::to_string),
+                position: 0,
+                created_at: at(0),
+                updated_at: at(0),
+            },
+            tags: vec![tag("a,b"), tag("say \"hi\""), tag("line\r\nbreak 🚀")],
+            people: vec![person("O'Brien, \"Pat\""), person("Zoë\nÅsa 👩‍💻")],
+            jira_refs: vec![JiraRef {
+                id: "jira".to_string(),
+                entry_item_id: id.to_string(),
+                jira_key: "ABC-1".to_string(),
+                created_at: at(0),
+            }],
+            checklist: vec![],
+            fields: vec![ItemFieldValue {
+                field_id: notes_field().id,
+                name: notes_field().name,
+                field_type: "text".to_string(),
+                value: AWKWARD.to_string(),
+            }],
+        }
+    }
+
+    fn rows() -> Vec<(DateTime<Utc>, EntryItemWithMetadata)> {
+        vec![
+            (at(9), item("one", AWKWARD, Some("Proj, \"X\"\nnext 🎯"))),
+            (at(17), item("two", "plain", None)),
+        ]
+    }
+
+    #[test]
+    fn starts_with_bom_only_when_asked() {
+        let columns = resolve_columns(None, &[]).unwrap();
+        assert!(write_csv(&columns, &rows(), true).unwrap().starts_with(BOM));
+        assert!(!write_csv(&columns, &rows(), false).unwrap().starts_with(BOM));
+    }
+
+    #[test]
+    fn every_cell_reads_back_with_csv_reader() {
+        let fields = vec![notes_field()];
+        let columns = resolve_columns(None, &fields).unwrap();
+        let rows = rows();
+        let text = write_csv(&columns, &rows, true).unwrap();
+
+        let body = text.strip_prefix(BOM).unwrap();
+        let mut reader = csv::Reader::from_reader(body.as_bytes());
+        let headers: Vec<String> = reader.headers().unwrap().iter().map(str::to_string).collect();
+        let expected_headers: Vec<String> = columns.iter().map(|c| c.header().to_string()).collect();
+        assert_eq!(headers, expected_headers);
+
+        let records: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(records.len(), rows.len());
+        for (record, (timestamp, item)) in records.iter().zip(&rows) {
+            let expected: Vec<String> = columns.iter().map(|c| c.cell(*timestamp, item)).collect();
+            let actual: Vec<&str> = record.iter().collect();
+            assert_eq!(actual, expected);
+        }
+        assert_eq!(&records[0][3], AWKWARD);
+        assert_eq!(&records[0][8], AWKWARD);
+    }
+
+    #[test]
+    fn every_value_reads_back_with_csv_import() {
+        let fields = vec![notes_field()];
+        let columns = resolve_columns(None, &fields).unwrap();
+        let rows = rows();
+        let text = write_csv(&columns, &rows, true).unwrap();
+
+        let (imported, errors) = csv_import::read_rows(&text, &CsvMapping::default(), &fields).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(imported.len(), rows.len());
+        for (row, (timestamp, item)) in imported.iter().zip(&rows) {
+            assert_eq!(row.timestamp, *timestamp);
+            assert_eq!(row.item.item_type, item.item.item_type);
+            assert_eq!(row.item.content, item.item.content);
+            assert_eq!(row.item.project, item.item.project);
+            assert_eq!(row.item.tags, item.tags.iter().map(|t| t.name.clone()).collect::<Vec<_>>());
+            assert_eq!(row.item.people, item.people.iter().map(|p| p.name.clone()).collect::<Vec<_>>());
+            assert_eq!(row.item.jira, vec!["ABC-1".to_string()]);
+            assert_eq!(row.item.fields.get(&notes_field().name).map(String::as_str), Some(AWKWARD));
+        }
+    }
+
+    #[test]
+    fn selected_columns_keep_their_order() {
+        let fields = vec![notes_field()];
+        let requested: Vec<String> = ["people", "NOTES, \"LONG\"", "Content", "date", "content"]
+            .iter().map(|s| s.to_string()).collect();
+        let columns = resolve_columns(Some(&requested), &fields).unwrap();
+        let text = write_csv(&columns, &rows(), false).unwrap();
+
+        let mut reader = csv::Reader::from_reader(text.as_bytes());
+        let headers: Vec<&str> = reader.headers().unwrap().iter().collect::<Vec<_>>();
+        assert_eq!(headers, vec!["People", "Notes, \"long\"", "Content", "Date"]);
+        let first = reader.records().next().unwrap().unwrap();
+        assert_eq!(first.iter().collect::<Vec<_>>(), vec![
+            "O'Brien, \"Pat\";Zoë\nÅsa 👩‍💻",
+            AWKWARD,
+            AWKWARD,
+            "2026-10-05",
+        ]);
+    }
+
+    #[test]
+    fn unknown_columns_are_rejected() {
+        let requested = vec!["Mood".to_string()];
+        assert_eq!(resolve_columns(Some(&requested), &[]).unwrap_err(), "Unknown column: Mood");
+    }
+}
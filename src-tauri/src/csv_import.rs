@@ -0,0 +1,254 @@
+// CSV import of entries, e.g. from old spreadsheets. Columns are found by
+// the headers `export_entries_csv` writes unless a mapping names others, so
+// an export imports back as it was. Rows with the same date and time become
+// one entry, their items in file order. Dates and times are read as UTC,
+// like the export writes them. Every row is checked before anything is
+// written, a file with any bad row is not imported at all, and a good file
+// is written in one transaction.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::custom_fields;
+use crate::database::CustomField;
+
+/// Item type of rows whose type is left empty.
+pub const DEFAULT_ITEM_TYPE: &str = "Note";
+
+/// Entries shown in a preview.
+pub const PREVIEW_ENTRIES: usize = 20;
+
+const DEFAULT_LIST_SEPARATOR: &str = ";";
+
+/// Headers to read each value from. Left out, a value is read from the
+/// column `export_entries_csv` writes for it, if the file has one; custom
+/// fields are also read from columns named after them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsvMapping {
+    pub date: Option<String>,
+    pub time: Option<String>,
+    pub item_type: Option<String>,
+    pub content: Option<String>,
+    pub project: Option<String>,
+    pub tags: Option<String>,
+    pub jira: Option<String>,
+    pub people: Option<String>,
+    /// Headers by custom field name.
+    pub fields: BTreeMap<String, String>,
+    /// Separator between tags, Jira keys and people in one cell.
+    pub list_separator: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedItem {
+    pub item_type: String,
+    pub content: String,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+    pub jira: Vec<String>,
+    pub people: Vec<String>,
+    /// Custom field values by field name.
+    pub fields: BTreeMap<String, String>,
+}
+
+/// An item ready to write, with its custom field values as field id and value.
+#[derive(Debug, Clone)]
+pub struct ResolvedItem {
+    pub item: ImportedItem,
+    pub values: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedRow {
+    pub timestamp: DateTime<Utc>,
+    pub item: ImportedItem,
+}
+
+/// A problem with one row; `line` is where the row starts in the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowError {
+    pub line: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewEntry {
+    pub timestamp: String,
+    pub items: Vec<ImportedItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvImportReport {
+    pub dry_run: bool,
+    pub imported: bool,
+    pub rows: usize,
+    pub entries: usize,
+    pub errors: Vec<RowError>,
+    /// The first entries the file makes, oldest first.
+    pub preview: Vec<PreviewEntry>,
+    /// Entries created, when imported.
+    pub entry_ids: Vec<String>,
+}
+
+struct Columns {
+    date: usize,
+    time: Option<usize>,
+    item_type: Option<usize>,
+    content: usize,
+    project: Option<usize>,
+    tags: Option<usize>,
+    jira: Option<usize>,
+    people: Option<usize>,
+    fields: Vec<(String, usize)>,
+}
+
+fn find_header(headers: &csv::StringRecord, name: &str) -> Option<usize> {
+    headers.iter().position(|header| header.trim().eq_ignore_ascii_case(name.trim()))
+}
+
+/// The column for a mapped header, which must exist, or else for the
+/// export's header, if present.
+fn pick(headers: &csv::StringRecord, mapped: &Option<String>, default: &str) -> Result<Option<usize>, String> {
+    match mapped {
+        Some(name) => find_header(headers, name)
+            .map(Some)
+            .ok_or_else(|| format!("Column not found: {}", name)),
+        None => Ok(find_header(headers, default)),
+    }
+}
+
+fn resolve_columns(headers: &csv::StringRecord, mapping: &CsvMapping, custom_fields: &[CustomField]) -> Result<Columns, String> {
+    let required = |mapped: &Option<String>, default: &str| -> Result<usize, String> {
+        pick(headers, mapped, default)?
+            .ok_or_else(|| format!("Column not found: {}", mapped.as_deref().unwrap_or(default)))
+    };
+
+    let mut fields = Vec::new();
+    for (name, header) in &mapping.fields {
+        let field = custom_fields.iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| format!("Unknown field: {}", name))?;
+        let column = find_header(headers, header).ok_or_else(|| format!("Column not found: {}", header))?;
+        fields.push((field.name.clone(), column));
+    }
+    for field in custom_fields {
+        if fields.iter().any(|(name, _)| name == &field.name) {
+            continue;
+        }
+        if let Some(column) = find_header(headers, &field.name) {
+            fields.push((field.name.clone(), column));
+        }
+    }
+
+    Ok(Columns {
+        date: required(&mapping.date, "Date")?,
+        time: pick(headers, &mapping.time, "Time")?,
+        item_type: pick(headers, &mapping.item_type, "Type")?,
+        content: required(&mapping.content, "Content")?,
+        project: pick(headers, &mapping.project, "Project")?,
+        tags: pick(headers, &mapping.tags, "Tags")?,
+        jira: pick(headers, &mapping.jira, "Jira")?,
+        people: pick(headers, &mapping.people, "People")?,
+        fields,
+    })
+}
+
+fn parse_row(
+    record: &csv::StringRecord,
+    columns: &Columns,
+    separator: &str,
+    custom_fields: &[CustomField],
+) -> Result<(DateTime<Utc>, ImportedItem), String> {
+    let cell = |column: Option<usize>| column.and_then(|c| record.get(c)).unwrap_or_default();
+    let list = |column: Option<usize>| -> Vec<String> {
+        cell(column).split(separator).map(str::trim).filter(|v| !v.is_empty()).map(str::to_string).collect()
+    };
+
+    let date = cell(Some(columns.date)).trim();
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}' (expected YYYY-MM-DD)", date))?;
+    let time = cell(columns.time).trim();
+    let time = if time.is_empty() {
+        NaiveTime::MIN
+    } else {
+        NaiveTime::parse_from_str(time, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+            .map_err(|_| format!("Invalid time '{}' (expected HH:MM or HH:MM:SS)", time))?
+    };
+
+    let content = cell(Some(columns.content));
+    if content.trim().is_empty() {
+        return Err("Content is empty".to_string());
+    }
+    let item_type = match cell(columns.item_type).trim() {
+        "" => DEFAULT_ITEM_TYPE.to_string(),
+        item_type => item_type.to_string(),
+    };
+    let project = Some(cell(columns.project).trim()).filter(|p| !p.is_empty()).map(str::to_string);
+
+    let fields: BTreeMap<String, String> = columns.fields.iter()
+        .map(|(name, column)| (name.clone(), cell(Some(*column)).to_string()))
+        .filter(|(_, value)| !value.trim().is_empty())
+        .collect();
+    custom_fields::resolve_values(custom_fields, &fields)?;
+
+    let item = ImportedItem {
+        item_type,
+        content: content.to_string(),
+        project,
+        tags: list(columns.tags),
+        jira: list(columns.jira),
+        people: list(columns.people),
+        fields,
+    };
+    Ok((date.and_time(time).and_utc(), item))
+}
+
+/// Every row of `text` that can be imported, and what is wrong with the
+/// others. Fails outright only when the header row does not fit `mapping`.
+pub fn read_rows(
+    text: &str,
+    mapping: &CsvMapping,
+    custom_fields: &[CustomField],
+) -> Result<(Vec<ImportedRow>, Vec<RowError>), String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut reader = csv::ReaderBuilder::new().from_reader(text.as_bytes());
+    let headers = reader.headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .clone();
+    let columns = resolve_columns(&headers, mapping, custom_fields)?;
+    let separator = mapping.list_separator.as_deref().filter(|s| !s.is_empty()).unwrap_or(DEFAULT_LIST_SEPARATOR);
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or_default();
+                errors.push(RowError { line, message: e.to_string() });
+                continue;
+            }
+        };
+        match parse_row(&record, &columns, separator, custom_fields) {
+            Ok((timestamp, item)) => rows.push(ImportedRow { timestamp, item }),
+            Err(message) => {
+                let line = record.position().map(|p| p.line()).unwrap_or_default();
+                errors.push(RowError { line, message });
+            }
+        }
+    }
+    Ok((rows, errors))
+}
+
+/// Rows grouped into entries by their date and time, oldest first.
+pub fn group_entries(rows: Vec<ImportedRow>) -> Vec<(DateTime<Utc>, Vec<ImportedItem>)> {
+    let mut entries: BTreeMap<DateTime<Utc>, Vec<ImportedItem>> = BTreeMap::new();
+    for row in rows {
+        entries.entry(row.timestamp).or_default().push(row.item);
+    }
+    entries.into_iter().collect()
+}
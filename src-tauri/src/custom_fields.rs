@@ -0,0 +1,185 @@
+// User-defined fields on entry items, such as customer, environment,
+// severity or story points. Values are kept as text in a canonical form per
+// type: numbers as entered, dates as YYYY-MM-DD and enum values spelled as
+// the option they match. Requests refer to fields by name, like tags.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::database::CustomField;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    Text,
+    Number,
+    Date,
+    Enum,
+}
+
+impl FieldType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FieldType::Text => "text",
+            FieldType::Number => "number",
+            FieldType::Date => "date",
+            FieldType::Enum => "enum",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "text" => Ok(FieldType::Text),
+            "number" => Ok(FieldType::Number),
+            "date" => Ok(FieldType::Date),
+            "enum" => Ok(FieldType::Enum),
+            other => Err(format!("Invalid field type '{}'; use text, number, date or enum", other)),
+        }
+    }
+}
+
+/// Trimmed, de-duplicated enum options; other types take none.
+pub fn clean_options(field_type: FieldType, options: &[String]) -> Result<Vec<String>, String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for option in options.iter().map(|o| o.trim()).filter(|o| !o.is_empty()) {
+        if !cleaned.iter().any(|c| c.eq_ignore_ascii_case(option)) {
+            cleaned.push(option.to_string());
+        }
+    }
+
+    match field_type {
+        FieldType::Enum if cleaned.is_empty() => Err("An enum field needs at least one option".to_string()),
+        FieldType::Enum => Ok(cleaned),
+        _ if !cleaned.is_empty() => Err("Only enum fields take options".to_string()),
+        _ => Ok(cleaned),
+    }
+}
+
+fn parse_number(field: &str, value: &str) -> Result<f64, String> {
+    value.parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .ok_or_else(|| format!("{} must be a number, not '{}'", field, value))
+}
+
+/// The value to store for `raw`, or `None` to clear the field.
+pub fn normalize_value(field: &CustomField, raw: &str) -> Result<Option<String>, String> {
+    let value = raw.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    let normalized = match FieldType::parse(&field.field_type)? {
+        FieldType::Text => value.to_string(),
+        FieldType::Number => {
+            parse_number(&field.name, value)?;
+            value.to_string()
+        }
+        FieldType::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| format!("{} must be a date (YYYY-MM-DD), not '{}'", field.name, value))?
+            .to_string(),
+        FieldType::Enum => field.options
+            .iter()
+            .find(|option| option.eq_ignore_ascii_case(value))
+            .cloned()
+            .ok_or_else(|| format!("{} must be one of {}, not '{}'", field.name, field.options.join(", "), value))?,
+    };
+    Ok(Some(normalized))
+}
+
+fn find_field<'a>(fields: &'a [CustomField], name: &str) -> Result<&'a CustomField, String> {
+    fields.iter()
+        .find(|field| field.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| format!("Unknown field: {}", name))
+}
+
+/// Field ids and stored values for values keyed by field name. Empty values
+/// are left out.
+pub fn resolve_values(fields: &[CustomField], values: &BTreeMap<String, String>) -> Result<Vec<(String, String)>, String> {
+    let mut resolved = Vec::new();
+    for (name, raw) in values {
+        let field = find_field(fields, name)?;
+        if let Some(value) = normalize_value(field, raw)? {
+            resolved.push((field.id.clone(), value));
+        }
+    }
+    Ok(resolved)
+}
+
+/// A condition on one field for listing items. `equals` compares text and
+/// enum values case-insensitively; `min` and `max` are inclusive bounds for
+/// numbers and dates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldFilter {
+    pub field: String,
+    pub equals: Option<String>,
+    pub min: Option<String>,
+    pub max: Option<String>,
+}
+
+/// A filter with its field looked up and its values normalized.
+pub struct ResolvedFilter {
+    field: CustomField,
+    field_type: FieldType,
+    equals: Option<String>,
+    min: Option<String>,
+    max: Option<String>,
+}
+
+pub fn resolve_filters(fields: &[CustomField], filters: &[FieldFilter]) -> Result<Vec<ResolvedFilter>, String> {
+    filters.iter()
+        .map(|filter| {
+            let field = find_field(fields, &filter.field)?.clone();
+            let field_type = FieldType::parse(&field.field_type)?;
+            if (filter.min.is_some() || filter.max.is_some()) && !matches!(field_type, FieldType::Number | FieldType::Date) {
+                return Err(format!("{} can only be filtered by value", field.name));
+            }
+            let normalize = |raw: &Option<String>| -> Result<Option<String>, String> {
+                match raw {
+                    Some(raw) => normalize_value(&field, raw),
+                    None => Ok(None),
+                }
+            };
+            Ok(ResolvedFilter {
+                equals: normalize(&filter.equals)?,
+                min: normalize(&filter.min)?,
+                max: normalize(&filter.max)?,
+                field_type,
+                field,
+            })
+        })
+        .collect()
+}
+
+impl ResolvedFilter {
+    /// The filter as an SQL condition on the entry item aliased `i`, with its
+    /// parameters in order. Items without the field never pass.
+    pub fn sql_condition(&self) -> (String, Vec<String>) {
+        let (value, param) = match self.field_type {
+            FieldType::Number => ("CAST(v.value AS REAL)", "CAST(? AS REAL)"),
+            _ => ("v.value", "?"),
+        };
+        let mut sql = String::from("EXISTS (SELECT 1 FROM item_field_values v WHERE v.entry_item_id = i.id AND v.field_id = ?");
+        let mut params = vec![self.field.id.clone()];
+
+        if let Some(equals) = &self.equals {
+            match self.field_type {
+                FieldType::Number => sql.push_str(&format!(" AND {} = {}", value, param)),
+                _ => sql.push_str(" AND v.value = ? COLLATE NOCASE"),
+            }
+            params.push(equals.clone());
+        }
+        if let Some(min) = &self.min {
+            sql.push_str(&format!(" AND {} >= {}", value, param));
+            params.push(min.clone());
+        }
+        if let Some(max) = &self.max {
+            sql.push_str(&format!(" AND {} <= {}", value, param));
+            params.push(max.clone());
+        }
+        sql.push(')');
+        (sql, params)
+    }
+}
@@ -1,8 +1,17 @@
-use sqlx::{sqlite::SqlitePool, Row};
+use sqlx::{sqlite::{SqliteArguments, SqliteConnectOptions, SqliteConnection, SqlitePool, SqliteRow}, Column, Row, TypeInfo, ValueRef};
+use std::collections::HashMap;
+use std::path::Path;
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use uuid::Uuid;
 
+use crate::analytics::{CountPoint, Granularity};
+use crate::autocomplete::{SuggestionKind, UsageDay};
+use crate::backup::{self, ImportMode, TableImport};
+use crate::bulk::{BulkItemResult, ResolvedOps};
+use crate::csv_import::ResolvedItem;
+use crate::custom_fields::ResolvedFilter;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Entry {
     pub id: String,
@@ -18,6 +27,8 @@ pub struct EntryItem {
     pub item_type: String,
     pub content: String,
     pub project: Option<String>,
+    /// Place within the entry, from 0.
+    pub position: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -66,6 +77,9 @@ pub struct Project {
     pub name: String,
     pub description: Option<String>,
     pub color: String,
+    pub hourly_rate: Option<f64>,
+    pub currency: String,
+    pub billable: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -80,10 +94,24 @@ pub struct Meeting {
     pub location: Option<String>,
     pub meeting_type: String,
     pub status: String,
+    pub recurrence_rule: Option<String>,
+    pub recurrence_exceptions: Vec<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Fields of a meeting to create; `meeting_type` defaults to "meeting".
+#[derive(Debug, Clone, Default)]
+pub struct NewMeeting<'a> {
+    pub title: &'a str,
+    pub description: Option<&'a str>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub location: Option<&'a str>,
+    pub meeting_type: Option<&'a str>,
+    pub recurrence_rule: Option<&'a str>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MeetingAttendee {
     pub id: String,
@@ -106,10 +134,146 @@ pub struct MeetingAction {
     pub due_date: Option<DateTime<Utc>>,
     pub status: String,
     pub priority: String,
+    pub occurrence_start: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Fields of a meeting action to create; `priority` defaults to "medium".
+#[derive(Debug, Clone, Default)]
+pub struct NewMeetingAction<'a> {
+    pub meeting_id: &'a str,
+    pub title: &'a str,
+    pub description: Option<&'a str>,
+    pub assignee: Option<&'a str>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub priority: Option<&'a str>,
+    pub occurrence_start: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TimeEntry {
+    pub id: String,
+    pub project: Option<String>,
+    pub entry_item_id: Option<String>,
+    pub jira_key: Option<String>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    pub last_heartbeat: Option<DateTime<Utc>>,
+    pub person: Option<String>,
+    pub billable: bool,
+    pub invoice_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TimeEntry {
+    /// Tracked duration; a running timer counts up to `now`.
+    pub fn duration_at(&self, now: DateTime<Utc>) -> chrono::Duration {
+        self.end_time.unwrap_or(now) - self.start_time
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectPersonRate {
+    pub project_id: String,
+    pub person: String,
+    pub hourly_rate: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Invoice {
+    pub id: String,
+    pub number: i64,
+    pub project_id: String,
+    pub project_name: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub currency: String,
+    pub total: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InvoiceLine {
+    pub id: String,
+    pub invoice_id: String,
+    pub time_entry_id: Option<String>,
+    pub date: DateTime<Utc>,
+    pub description: String,
+    pub person: Option<String>,
+    pub hours: f64,
+    pub rate: f64,
+    pub amount: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FocusSessionRecord {
+    pub id: String,
+    pub project: Option<String>,
+    pub time_entry_id: Option<String>,
+    pub cycle: i64,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Goal {
+    pub id: String,
+    pub name: String,
+    /// Only items of this type count; `None` counts every item.
+    pub item_type: Option<String>,
+    pub target_count: i64,
+    /// `workday`, `week`, `sprint` or `month`.
+    pub period: String,
+    pub sprint_days: Option<i64>,
+    pub sprint_start: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What has been reminded about for one thing with a due date. The state
+/// belongs to `due_date`; moving the due date starts over.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReminderState {
+    /// `meeting_action`.
+    pub target_kind: String,
+    pub target_id: String,
+    pub due_date: DateTime<Utc>,
+    /// Offsets already notified, e.g. `day_before`.
+    pub sent_offsets: Vec<String>,
+    pub snoozed_until: Option<DateTime<Utc>>,
+    pub dismissed: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An item that is waiting on someone else, e.g. "waiting on Priya for
+/// the API spec".
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WaitingFor {
+    pub entry_item_id: String,
+    pub person_id: String,
+    pub person_name: String,
+    pub expected_by: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Register details of a Decision item.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DecisionRecord {
+    pub entry_item_id: String,
+    pub context: Option<String>,
+    /// `proposed`, `accepted` or `superseded`.
+    pub status: String,
+    /// The earlier decision this one replaces.
+    pub supersedes_id: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EntryWithItems {
     pub entry: Entry,
@@ -122,19 +286,99 @@ pub struct EntryItemWithMetadata {
     pub tags: Vec<Tag>,
     pub people: Vec<Person>,
     pub jira_refs: Vec<JiraRef>,
+    pub checklist: Vec<ChecklistRow>,
+    pub fields: Vec<ItemFieldValue>,
+}
+
+/// A user-defined field items can carry, e.g. "Severity".
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomField {
+    pub id: String,
+    pub name: String,
+    /// `text`, `number`, `date` or `enum`.
+    pub field_type: String,
+    /// Allowed values of an `enum` field.
+    pub options: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An item's value for one custom field, stored as text.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ItemFieldValue {
+    pub field_id: String,
+    pub name: String,
+    pub field_type: String,
+    pub value: String,
+}
+
+/// One step of an item's checklist.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChecklistRow {
+    pub id: String,
+    pub entry_item_id: String,
+    pub position: i64,
+    pub text: String,
+    pub done: bool,
+    pub assignee: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Column values of one row, enough to insert it again.
+pub type RowValues = Vec<(String, serde_json::Value)>;
+
+/// An item as it was before a bulk change: its entry, its own row, the rows
+/// deleted along with it and the links deleting it would clear.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ItemSnapshot {
+    pub item_id: String,
+    pub entry: RowValues,
+    pub item: RowValues,
+    /// Rows of `ITEM_DEPENDENT_TABLES`, by table.
+    pub dependents: Vec<(String, Vec<RowValues>)>,
+    /// Meeting actions linked to the item.
+    pub action_ids: Vec<String>,
+    /// Decisions recorded as superseding the item.
+    pub superseded_by: Vec<String>,
+    /// Time entries tracked against the item.
+    #[serde(default)]
+    pub time_entry_ids: Vec<String>,
 }
 
+/// Tables whose rows belong to an entry item through `entry_item_id` and are
+/// deleted with it.
+const ITEM_DEPENDENT_TABLES: [&str; 8] = [
+    "item_tags",
+    "item_people",
+    "jira_refs",
+    "checklist_rows",
+    "item_field_values",
+    "decision_records",
+    "waiting_for",
+    "meeting_notes",
+];
+
 pub struct Database {
     pool: SqlitePool,
 }
 
 impl Database {
-    pub async fn new() -> Result<Self, sqlx::Error> {
-        // Use in-memory database for now to avoid file permission issues
-        let database_url = "sqlite::memory:";
-        println!("Database URL: {}", database_url);
-        
-        let pool = SqlitePool::connect(database_url).await?;
+    /// Open the logbook database inside `data_dir`, creating it if needed.
+    /// Without a data directory an in-memory database is used, which loses
+    /// everything (including running timers) when the app quits.
+    pub async fn new(data_dir: Option<&Path>) -> Result<Self, sqlx::Error> {
+        let pool = match data_dir {
+            Some(dir) => {
+                std::fs::create_dir_all(dir)?;
+                let path = dir.join("scobro-logbook.db");
+                let options = SqliteConnectOptions::new()
+                    .filename(path)
+                    .create_if_missing(true);
+                SqlitePool::connect_with(options).await?
+            }
+            None => SqlitePool::connect("sqlite::memory:").await?,
+        };
         
         let db = Database { pool };
         db.init().await?;
@@ -164,6 +408,7 @@ impl Database {
                 item_type TEXT NOT NULL,
                 content TEXT NOT NULL,
                 project TEXT,
+                position INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
                 FOREIGN KEY (entry_id) REFERENCES entries (id) ON DELETE CASCADE
@@ -250,6 +495,9 @@ impl Database {
                 name TEXT UNIQUE NOT NULL,
                 description TEXT,
                 color TEXT DEFAULT '#0275d8',
+                hourly_rate REAL,
+                currency TEXT NOT NULL DEFAULT 'USD',
+                billable INTEGER NOT NULL DEFAULT 1,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )
@@ -269,6 +517,8 @@ impl Database {
                 location TEXT,
                 meeting_type TEXT DEFAULT 'meeting',
                 status TEXT DEFAULT 'scheduled',
+                recurrence_rule TEXT,
+                recurrence_exceptions TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )
@@ -306,6 +556,7 @@ impl Database {
                 due_date TEXT,
                 status TEXT DEFAULT 'open',
                 priority TEXT DEFAULT 'medium',
+                occurrence_start TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
                 FOREIGN KEY (meeting_id) REFERENCES meetings (id) ON DELETE CASCADE,
@@ -316,939 +567,3705 @@ impl Database {
         .execute(&self.pool)
         .await?;
 
-        Ok(())
-    }
-
-    pub async fn create_entry(&self, timestamp: DateTime<Utc>) -> Result<Entry, sqlx::Error> {
-        let id = Uuid::new_v4().to_string();
-        let now = Utc::now();
-        
         sqlx::query(
-            "INSERT INTO entries (id, timestamp, created_at, updated_at) VALUES (?, ?, ?, ?)"
+            r#"
+            CREATE TABLE IF NOT EXISTS meeting_notes (
+                meeting_id TEXT NOT NULL,
+                entry_item_id TEXT NOT NULL,
+                occurrence_start TEXT,
+                PRIMARY KEY (meeting_id, entry_item_id),
+                FOREIGN KEY (meeting_id) REFERENCES meetings (id) ON DELETE CASCADE,
+                FOREIGN KEY (entry_item_id) REFERENCES entry_items (id) ON DELETE CASCADE
+            )
+            "#,
         )
-        .bind(&id)
-        .bind(timestamp.to_rfc3339())
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(Entry {
-            id,
-            timestamp,
-            created_at: now,
-            updated_at: now,
-        })
-    }
-
-    pub async fn create_entry_item(
-        &self,
-        entry_id: &str,
-        item_type: &str,
-        content: &str,
-        project: Option<&str>,
-    ) -> Result<EntryItem, sqlx::Error> {
-        let id = Uuid::new_v4().to_string();
-        let now = Utc::now();
-        
         sqlx::query(
-            "INSERT INTO entry_items (id, entry_id, item_type, content, project, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            r#"
+            CREATE TABLE IF NOT EXISTS time_entries (
+                id TEXT PRIMARY KEY,
+                project TEXT,
+                entry_item_id TEXT,
+                jira_key TEXT,
+                start_time TEXT NOT NULL,
+                end_time TEXT,
+                notes TEXT,
+                last_heartbeat TEXT,
+                person TEXT,
+                billable INTEGER NOT NULL DEFAULT 1,
+                invoice_id TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (entry_item_id) REFERENCES entry_items (id) ON DELETE SET NULL,
+                FOREIGN KEY (invoice_id) REFERENCES invoices (id) ON DELETE SET NULL
+            )
+            "#,
         )
-        .bind(&id)
-        .bind(entry_id)
-        .bind(item_type)
-        .bind(content)
-        .bind(project)
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(EntryItem {
-            id,
-            entry_id: entry_id.to_string(),
-            item_type: item_type.to_string(),
-            content: content.to_string(),
-            project: project.map(|s| s.to_string()),
-            created_at: now,
-            updated_at: now,
-        })
-    }
-
-    pub async fn get_or_create_tag(&self, name: &str) -> Result<Tag, sqlx::Error> {
-        // Try to get existing tag
-        let result = sqlx::query("SELECT id, name, description, color, category, created_at, updated_at FROM tags WHERE name = ?")
-            .bind(name)
-            .fetch_optional(&self.pool)
+        // A running timer is a row without an end time; this index guarantees
+        // there is never more than one of them.
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_time_entries_running ON time_entries ((end_time IS NULL)) WHERE end_time IS NULL")
+            .execute(&self.pool)
             .await?;
 
-        if let Some(row) = result {
-            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
-            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS project_person_rates (
+                project_id TEXT NOT NULL,
+                person_id TEXT NOT NULL,
+                hourly_rate REAL NOT NULL,
+                PRIMARY KEY (project_id, person_id),
+                FOREIGN KEY (project_id) REFERENCES projects (id) ON DELETE CASCADE,
+                FOREIGN KEY (person_id) REFERENCES people (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-            return Ok(Tag {
-                id: row.get("id"),
-                name: row.get("name"),
-                description: row.get("description"),
-                color: row.get("color"),
-                category: row.get("category"),
-                created_at,
-                updated_at,
-            });
-        }
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS invoices (
+                id TEXT PRIMARY KEY,
+                number INTEGER UNIQUE NOT NULL,
+                project_id TEXT NOT NULL,
+                project_name TEXT NOT NULL,
+                period_start TEXT NOT NULL,
+                period_end TEXT NOT NULL,
+                currency TEXT NOT NULL,
+                total REAL NOT NULL,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-        // Create new tag with default values
-        let id = Uuid::new_v4().to_string();
-        let now = Utc::now();
-        
-        sqlx::query("INSERT INTO tags (id, name, description, color, category, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)")
-            .bind(&id)
-            .bind(name)
-            .bind(None::<String>)
-            .bind("#6c757d")
-            .bind(None::<String>)
-            .bind(now.to_rfc3339())
-            .bind(now.to_rfc3339())
-            .execute(&self.pool)
-            .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS invoice_lines (
+                id TEXT PRIMARY KEY,
+                invoice_id TEXT NOT NULL,
+                time_entry_id TEXT,
+                date TEXT NOT NULL,
+                description TEXT NOT NULL,
+                person TEXT,
+                hours REAL NOT NULL,
+                rate REAL NOT NULL,
+                amount REAL NOT NULL,
+                FOREIGN KEY (invoice_id) REFERENCES invoices (id) ON DELETE CASCADE,
+                FOREIGN KEY (time_entry_id) REFERENCES time_entries (id) ON DELETE SET NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-        Ok(Tag {
-            id,
-            name: name.to_string(),
-            description: None,
-            color: "#6c757d".to_string(),
-            category: None,
-            created_at: now,
-            updated_at: now,
-        })
-    }
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-    pub async fn get_or_create_person(&self, name: &str) -> Result<Person, sqlx::Error> {
-        // Try to get existing person
-        let result = sqlx::query("SELECT id, name, created_at FROM people WHERE name = ?")
-            .bind(name)
-            .fetch_optional(&self.pool)
-            .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS focus_sessions (
+                id TEXT PRIMARY KEY,
+                project TEXT,
+                time_entry_id TEXT,
+                cycle INTEGER NOT NULL,
+                start_time TEXT NOT NULL,
+                end_time TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (time_entry_id) REFERENCES time_entries (id) ON DELETE SET NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
 
-        if let Some(row) = result {
-            return Ok(Person {
-                id: row.get("id"),
-                name: row.get("name"),
-                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                    .unwrap()
-                    .with_timezone(&Utc),
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS goals (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                item_type TEXT,
+                target_count INTEGER NOT NULL,
+                period TEXT NOT NULL,
+                sprint_days INTEGER,
+                sprint_start TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS reminder_states (
+                target_kind TEXT NOT NULL,
+                target_id TEXT NOT NULL,
+                due_date TEXT NOT NULL,
+                sent_offsets TEXT NOT NULL DEFAULT '[]',
+                snoozed_until TEXT,
+                dismissed INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (target_kind, target_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS decision_records (
+                entry_item_id TEXT PRIMARY KEY,
+                context TEXT,
+                status TEXT NOT NULL DEFAULT 'proposed',
+                supersedes_id TEXT,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (entry_item_id) REFERENCES entry_items (id) ON DELETE CASCADE,
+                FOREIGN KEY (supersedes_id) REFERENCES entry_items (id) ON DELETE SET NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS waiting_for (
+                entry_item_id TEXT PRIMARY KEY,
+                person_id TEXT NOT NULL,
+                expected_by TEXT,
+                resolved_at TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (entry_item_id) REFERENCES entry_items (id) ON DELETE CASCADE,
+                FOREIGN KEY (person_id) REFERENCES people (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS checklist_rows (
+                id TEXT PRIMARY KEY,
+                entry_item_id TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                done INTEGER NOT NULL DEFAULT 0,
+                assignee TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (entry_item_id) REFERENCES entry_items (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS custom_fields (
+                id TEXT PRIMARY KEY,
+                name TEXT UNIQUE NOT NULL,
+                field_type TEXT NOT NULL,
+                options TEXT NOT NULL DEFAULT '[]',
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS item_field_values (
+                entry_item_id TEXT NOT NULL,
+                field_id TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (entry_item_id, field_id),
+                FOREIGN KEY (entry_item_id) REFERENCES entry_items (id) ON DELETE CASCADE,
+                FOREIGN KEY (field_id) REFERENCES custom_fields (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS bulk_batches (
+                id TEXT PRIMARY KEY,
+                snapshot TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                undone_at TEXT
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Indexes for date-range queries and analytics
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_entries_timestamp ON entries (timestamp)")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_entry_items_entry_id ON entry_items (entry_id)")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_checklist_rows_item ON checklist_rows (entry_item_id, position)")
+            .execute(&self.pool)
+            .await?;
+
+        // Indexes for counting how values are used
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_item_tags_tag ON item_tags (tag_id)")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_item_people_person ON item_people (person_id)")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_jira_refs_item ON jira_refs (entry_item_id)")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_jira_refs_key ON jira_refs (jira_key)")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_entry_items_project ON entry_items (project)")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn create_entry(&self, timestamp: DateTime<Utc>) -> Result<Entry, sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::create_entry_in(&mut conn, timestamp).await
+    }
+
+    async fn create_entry_in(conn: &mut SqliteConnection, timestamp: DateTime<Utc>) -> Result<Entry, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        
+        sqlx::query(
+            "INSERT INTO entries (id, timestamp, created_at, updated_at) VALUES (?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(timestamp.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(Entry {
+            id,
+            timestamp,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn create_entry_item(
+        &self,
+        entry_id: &str,
+        item_type: &str,
+        content: &str,
+        project: Option<&str>,
+    ) -> Result<EntryItem, sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::create_entry_item_in(&mut conn, entry_id, item_type, content, project).await
+    }
+
+    async fn create_entry_item_in(
+        conn: &mut SqliteConnection,
+        entry_id: &str,
+        item_type: &str,
+        content: &str,
+        project: Option<&str>,
+    ) -> Result<EntryItem, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let position = Self::next_item_position(&mut *conn, entry_id).await?;
+        
+        sqlx::query(
+            "INSERT INTO entry_items (id, entry_id, item_type, content, project, position, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(entry_id)
+        .bind(item_type)
+        .bind(content)
+        .bind(project)
+        .bind(position)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(EntryItem {
+            id,
+            entry_id: entry_id.to_string(),
+            item_type: item_type.to_string(),
+            content: content.to_string(),
+            project: project.map(|s| s.to_string()),
+            position,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// The position after the last item of an entry.
+    async fn next_item_position<'e, E>(executor: E, entry_id: &str) -> Result<i64, sqlx::Error>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        sqlx::query_scalar("SELECT COALESCE(MAX(position), -1) + 1 FROM entry_items WHERE entry_id = ?")
+            .bind(entry_id)
+            .fetch_one(executor)
+            .await
+    }
+
+    /// Number an entry's items in the order given, in one transaction.
+    pub async fn reorder_items(&self, entry_id: &str, ordered_ids: &[String]) -> Result<(), sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        for (position, id) in ordered_ids.iter().enumerate() {
+            sqlx::query("UPDATE entry_items SET position = ?, updated_at = ? WHERE id = ? AND entry_id = ?")
+                .bind(position as i64)
+                .bind(&now)
+                .bind(id)
+                .bind(entry_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query("UPDATE entries SET updated_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(entry_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn get_or_create_tag(&self, name: &str) -> Result<Tag, sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::get_or_create_tag_in(&mut conn, name).await
+    }
+
+    async fn get_or_create_tag_in(conn: &mut SqliteConnection, name: &str) -> Result<Tag, sqlx::Error> {
+        // Try to get existing tag
+        let result = sqlx::query("SELECT id, name, description, color, category, created_at, updated_at FROM tags WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        if let Some(row) = result {
+            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+
+            return Ok(Tag {
+                id: row.get("id"),
+                name: row.get("name"),
+                description: row.get("description"),
+                color: row.get("color"),
+                category: row.get("category"),
+                created_at,
+                updated_at,
+            });
+        }
+
+        // Create new tag with default values
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        
+        sqlx::query("INSERT INTO tags (id, name, description, color, category, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)")
+            .bind(&id)
+            .bind(name)
+            .bind(None::<String>)
+            .bind("#6c757d")
+            .bind(None::<String>)
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(Tag {
+            id,
+            name: name.to_string(),
+            description: None,
+            color: "#6c757d".to_string(),
+            category: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_or_create_person(&self, name: &str) -> Result<Person, sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::get_or_create_person_in(&mut conn, name).await
+    }
+
+    async fn get_or_create_person_in(conn: &mut SqliteConnection, name: &str) -> Result<Person, sqlx::Error> {
+        // Try to get existing person
+        let result = sqlx::query("SELECT id, name, created_at FROM people WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        if let Some(row) = result {
+            return Ok(Person {
+                id: row.get("id"),
+                name: row.get("name"),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+            });
+        }
+
+        // Create new person
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        
+        sqlx::query("INSERT INTO people (id, name, created_at) VALUES (?, ?, ?)")
+            .bind(&id)
+            .bind(name)
+            .bind(now.to_rfc3339())
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(Person {
+            id,
+            name: name.to_string(),
+            created_at: now,
+        })
+    }
+
+    pub async fn get_person(&self, id: &str) -> Result<Option<Person>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, name, created_at FROM people WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| {
+            Ok(Person {
+                id: row.get("id"),
+                name: row.get("name"),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                    .with_timezone(&Utc),
+            })
+        })
+        .transpose()
+    }
+
+    pub async fn create_jira_ref(&self, entry_item_id: &str, jira_key: &str) -> Result<JiraRef, sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::create_jira_ref_in(&mut conn, entry_item_id, jira_key).await
+    }
+
+    async fn create_jira_ref_in(conn: &mut SqliteConnection, entry_item_id: &str, jira_key: &str) -> Result<JiraRef, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        
+        sqlx::query("INSERT INTO jira_refs (id, entry_item_id, jira_key, created_at) VALUES (?, ?, ?, ?)")
+            .bind(&id)
+            .bind(entry_item_id)
+            .bind(jira_key)
+            .bind(now.to_rfc3339())
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(JiraRef {
+            id,
+            entry_item_id: entry_item_id.to_string(),
+            jira_key: jira_key.to_string(),
+            created_at: now,
+        })
+    }
+
+    pub async fn link_item_tag(&self, entry_item_id: &str, tag_id: &str) -> Result<(), sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::link_item_tag_in(&mut conn, entry_item_id, tag_id).await
+    }
+
+    async fn link_item_tag_in(conn: &mut SqliteConnection, entry_item_id: &str, tag_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT OR IGNORE INTO item_tags (entry_item_id, tag_id) VALUES (?, ?)")
+            .bind(entry_item_id)
+            .bind(tag_id)
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn link_item_person(&self, entry_item_id: &str, person_id: &str) -> Result<(), sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::link_item_person_in(&mut conn, entry_item_id, person_id).await
+    }
+
+    async fn link_item_person_in(conn: &mut SqliteConnection, entry_item_id: &str, person_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT OR IGNORE INTO item_people (entry_item_id, person_id) VALUES (?, ?)")
+            .bind(entry_item_id)
+            .bind(person_id)
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_all_entries_with_items(&self) -> Result<Vec<EntryWithItems>, sqlx::Error> {
+        let entries = sqlx::query("SELECT id, timestamp, created_at, updated_at FROM entries ORDER BY timestamp DESC")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut result = Vec::new();
+        
+        for row in entries {
+            let entry = Entry {
+                id: row.get("id"),
+                timestamp: DateTime::parse_from_rfc3339(&row.get::<String, _>("timestamp"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+            };
+
+            let items = self.get_entry_items_with_metadata(&entry.id).await?;
+            result.push(EntryWithItems { entry, items });
+        }
+
+        Ok(result)
+    }
+
+    /// Entries with only their items that pass every filter, newest first.
+    /// Entries without such items are left out.
+    pub async fn get_entries_with_matching_items(&self, filters: &[ResolvedFilter]) -> Result<Vec<EntryWithItems>, sqlx::Error> {
+        let conditions: Vec<(String, Vec<String>)> = filters.iter().map(ResolvedFilter::sql_condition).collect();
+        let mut sql = String::from(
+            "SELECT i.id, i.entry_id, i.item_type, i.content, i.project, i.position, i.created_at, i.updated_at,
+                    e.timestamp, e.created_at AS entry_created_at, e.updated_at AS entry_updated_at
+             FROM entry_items i JOIN entries e ON e.id = i.entry_id
+             WHERE 1 = 1"
+        );
+        for (condition, _) in &conditions {
+            sql.push_str(" AND ");
+            sql.push_str(condition);
+        }
+        sql.push_str(" ORDER BY e.timestamp DESC, e.id, i.position, i.created_at");
+
+        let mut query = sqlx::query(&sql);
+        for param in conditions.iter().flat_map(|(_, params)| params) {
+            query = query.bind(param);
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
+        let entries = rows.iter()
+            .map(|row| {
+                Ok(Entry {
+                    id: row.get("entry_id"),
+                    timestamp: parse(row.get("timestamp"))?,
+                    created_at: parse(row.get("entry_created_at"))?,
+                    updated_at: parse(row.get("entry_updated_at"))?,
+                })
+            })
+            .collect::<Result<Vec<_>, sqlx::Error>>()?;
+        let items = self.attach_item_metadata(rows).await?;
+
+        let mut result: Vec<EntryWithItems> = Vec::new();
+        for (entry, item) in entries.into_iter().zip(items) {
+            match result.last_mut() {
+                Some(last) if last.entry.id == entry.id => last.items.push(item),
+                _ => result.push(EntryWithItems { entry, items: vec![item] }),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Items of the entries logged in `[from, to)` with their entry's
+    /// timestamp, oldest first.
+    pub async fn get_items_between(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<(DateTime<Utc>, EntryItemWithMetadata)>, sqlx::Error> {
+        let from = from.map(|t| t.to_rfc3339());
+        let to = to.map(|t| t.to_rfc3339());
+        let rows = sqlx::query(
+            "SELECT i.id, i.entry_id, i.item_type, i.content, i.project, i.position, i.created_at, i.updated_at, e.timestamp
+             FROM entry_items i JOIN entries e ON e.id = i.entry_id
+             WHERE (? IS NULL OR e.timestamp >= ?) AND (? IS NULL OR e.timestamp < ?)
+             ORDER BY e.timestamp, i.position, i.created_at"
+        )
+        .bind(&from)
+        .bind(&from)
+        .bind(&to)
+        .bind(&to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let timestamps = rows.iter()
+            .map(|row| {
+                DateTime::parse_from_rfc3339(&row.get::<String, _>("timestamp"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let items = self.attach_item_metadata(rows).await?;
+
+        Ok(timestamps.into_iter().zip(items).collect())
+    }
+
+    /// Items of one type with their entry's timestamp, oldest first.
+    pub async fn get_items_of_type(&self, item_type: &str) -> Result<Vec<(DateTime<Utc>, EntryItemWithMetadata)>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT i.id, i.entry_id, i.item_type, i.content, i.project, i.position, i.created_at, i.updated_at, e.timestamp
+             FROM entry_items i JOIN entries e ON e.id = i.entry_id
+             WHERE i.item_type = ?
+             ORDER BY e.timestamp, i.position, i.created_at"
+        )
+        .bind(item_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let timestamps = rows.iter()
+            .map(|row| {
+                DateTime::parse_from_rfc3339(&row.get::<String, _>("timestamp"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let items = self.attach_item_metadata(rows).await?;
+
+        Ok(timestamps.into_iter().zip(items).collect())
+    }
+
+    /// Items mentioning a person with their entry's timestamp, oldest first.
+    pub async fn get_items_for_person(
+        &self,
+        person_id: &str,
+        from: Option<DateTime<Utc>>,
+    ) -> Result<Vec<(DateTime<Utc>, EntryItemWithMetadata)>, sqlx::Error> {
+        let from = from.map(|t| t.to_rfc3339());
+        let rows = sqlx::query(
+            "SELECT i.id, i.entry_id, i.item_type, i.content, i.project, i.position, i.created_at, i.updated_at, e.timestamp
+             FROM entry_items i
+             JOIN entries e ON e.id = i.entry_id
+             JOIN item_people ip ON ip.entry_item_id = i.id
+             WHERE ip.person_id = ? AND (? IS NULL OR e.timestamp >= ?)
+             ORDER BY e.timestamp, i.position, i.created_at"
+        )
+        .bind(person_id)
+        .bind(&from)
+        .bind(&from)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let timestamps = rows.iter()
+            .map(|row| {
+                DateTime::parse_from_rfc3339(&row.get::<String, _>("timestamp"))
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let items = self.attach_item_metadata(rows).await?;
+
+        Ok(timestamps.into_iter().zip(items).collect())
+    }
+
+    pub async fn get_item_type(&self, entry_item_id: &str) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT item_type FROM entry_items WHERE id = ?")
+            .bind(entry_item_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn get_entry_items_with_metadata(&self, entry_id: &str) -> Result<Vec<EntryItemWithMetadata>, sqlx::Error> {
+        let items = sqlx::query("SELECT id, entry_id, item_type, content, project, position, created_at, updated_at FROM entry_items WHERE entry_id = ? ORDER BY position, created_at")
+            .bind(entry_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        self.attach_item_metadata(items).await
+    }
+
+    async fn attach_item_metadata(&self, rows: Vec<SqliteRow>) -> Result<Vec<EntryItemWithMetadata>, sqlx::Error> {
+        let mut result = Vec::new();
+        
+        for row in rows {
+            let item = EntryItem {
+                id: row.get("id"),
+                entry_id: row.get("entry_id"),
+                item_type: row.get("item_type"),
+                content: row.get("content"),
+                project: row.get("project"),
+                position: row.get("position"),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+            };
+
+            let tags = self.get_item_tags(&item.id).await?;
+            let people = self.get_item_people(&item.id).await?;
+            let jira_refs = self.get_item_jira_refs(&item.id).await?;
+            let checklist = self.get_item_checklist(&item.id).await?;
+            let fields = self.get_item_field_values(&item.id).await?;
+
+            result.push(EntryItemWithMetadata {
+                item,
+                tags,
+                people,
+                jira_refs,
+                checklist,
+                fields,
+            });
+        }
+
+        Ok(result)
+    }
+
+    async fn get_item_tags(&self, entry_item_id: &str) -> Result<Vec<Tag>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT t.id, t.name, t.description, t.color, t.category, t.created_at, t.updated_at FROM tags t 
+             JOIN item_tags it ON t.id = it.tag_id 
+             WHERE it.entry_item_id = ?"
+        )
+        .bind(entry_item_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tags = Vec::new();
+        for row in rows {
+            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+
+            tags.push(Tag {
+                id: row.get("id"),
+                name: row.get("name"),
+                description: row.get("description"),
+                color: row.get("color"),
+                category: row.get("category"),
+                created_at,
+                updated_at,
+            });
+        }
+        Ok(tags)
+    }
+
+    async fn get_item_people(&self, entry_item_id: &str) -> Result<Vec<Person>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT p.id, p.name, p.created_at FROM people p 
+             JOIN item_people ip ON p.id = ip.person_id 
+             WHERE ip.entry_item_id = ?"
+        )
+        .bind(entry_item_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut people = Vec::new();
+        for row in rows {
+            people.push(Person {
+                id: row.get("id"),
+                name: row.get("name"),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+            });
+        }
+        Ok(people)
+    }
+
+    async fn get_item_jira_refs(&self, entry_item_id: &str) -> Result<Vec<JiraRef>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, entry_item_id, jira_key, created_at FROM jira_refs WHERE entry_item_id = ?")
+            .bind(entry_item_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut jira_refs = Vec::new();
+        for row in rows {
+            jira_refs.push(JiraRef {
+                id: row.get("id"),
+                entry_item_id: row.get("entry_item_id"),
+                jira_key: row.get("jira_key"),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+            });
+        }
+        Ok(jira_refs)
+    }
+
+    pub async fn update_entry_item_content(&self, entry_item_id: &str, content: &str) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query("UPDATE entry_items SET content = ?, updated_at = ? WHERE id = ?")
+            .bind(content)
+            .bind(now.to_rfc3339())
+            .bind(entry_item_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update_entry_item_project(&self, entry_item_id: &str, project: Option<&str>) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query("UPDATE entry_items SET project = ?, updated_at = ? WHERE id = ?")
+            .bind(project)
+            .bind(now.to_rfc3339())
+            .bind(entry_item_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn remove_item_tags(&self, entry_item_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM item_tags WHERE entry_item_id = ?")
+            .bind(entry_item_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn remove_item_people(&self, entry_item_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM item_people WHERE entry_item_id = ?")
+            .bind(entry_item_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn remove_item_jira_refs(&self, entry_item_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM jira_refs WHERE entry_item_id = ?")
+            .bind(entry_item_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_entry_with_items(&self, entry_item_id: &str) -> Result<EntryWithItems, sqlx::Error> {
+        // First get the entry_id from the entry_item
+        let entry_id: String = sqlx::query_scalar("SELECT entry_id FROM entry_items WHERE id = ?")
+            .bind(entry_item_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        // Get the entry
+        let entry_row = sqlx::query("SELECT id, timestamp, created_at, updated_at FROM entries WHERE id = ?")
+            .bind(&entry_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let timestamp: String = entry_row.get("timestamp");
+        let created_at: String = entry_row.get("created_at");
+        let updated_at: String = entry_row.get("updated_at");
+
+        let timestamp = DateTime::parse_from_rfc3339(&timestamp)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+        let created_at = DateTime::parse_from_rfc3339(&created_at)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+        let updated_at = DateTime::parse_from_rfc3339(&updated_at)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+
+        let entry = Entry {
+            id: entry_row.get("id"),
+            timestamp,
+            created_at,
+            updated_at,
+        };
+
+        // Get items with metadata
+        let items = self.get_entry_items_with_metadata(&entry_id).await?;
+
+        Ok(EntryWithItems { entry, items })
+    }
+
+    pub async fn delete_entry_item(&self, entry_item_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM entry_items WHERE id = ?")
+            .bind(entry_item_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_entry(&self, entry_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM entries WHERE id = ?")
+            .bind(entry_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_entry(&self, entry_id: &str) -> Result<Option<EntryWithItems>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, timestamp, created_at, updated_at FROM entries WHERE id = ?")
+            .bind(entry_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
+        let entry = Entry {
+            id: row.get("id"),
+            timestamp: parse(row.get("timestamp"))?,
+            created_at: parse(row.get("created_at"))?,
+            updated_at: parse(row.get("updated_at"))?,
+        };
+        let items = self.get_entry_items_with_metadata(&entry.id).await?;
+
+        Ok(Some(EntryWithItems { entry, items }))
+    }
+
+    /// The entry each of `item_ids` belongs to; unknown items are left out.
+    pub async fn get_item_entry_ids(&self, item_ids: &[String]) -> Result<Vec<(String, String)>, sqlx::Error> {
+        let mut entry_ids = Vec::new();
+        for item_id in item_ids {
+            let entry_id: Option<String> = sqlx::query_scalar("SELECT entry_id FROM entry_items WHERE id = ?")
+                .bind(item_id)
+                .fetch_optional(&self.pool)
+                .await?;
+            if let Some(entry_id) = entry_id {
+                entry_ids.push((item_id.clone(), entry_id));
+            }
+        }
+        Ok(entry_ids)
+    }
+
+    pub async fn update_entry_timestamp(&self, entry_id: &str, timestamp: DateTime<Utc>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE entries SET timestamp = ?, updated_at = ? WHERE id = ?")
+            .bind(timestamp.to_rfc3339())
+            .bind(Utc::now().to_rfc3339())
+            .bind(entry_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Move items to the end of another entry, in the order given, touching
+    /// every entry involved and deleting entries left without items.
+    pub async fn move_items(&self, item_ids: &[String], target_entry_id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        Self::move_items_in(&mut tx, item_ids, target_entry_id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Move every item of the source entries, in order, to the end of the
+    /// target and delete the sources, in one transaction.
+    pub async fn merge_entries(&self, source_entry_ids: &[String], target_entry_id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut item_ids = Vec::new();
+        for source_id in source_entry_ids {
+            let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM entry_items WHERE entry_id = ? ORDER BY position, created_at")
+                .bind(source_id)
+                .fetch_all(&mut *tx)
+                .await?;
+            item_ids.extend(ids);
+        }
+        Self::move_items_in(&mut tx, &item_ids, target_entry_id).await?;
+        // Moving removes emptied entries; sources that had no items remain
+        for source_id in source_entry_ids {
+            sqlx::query("DELETE FROM entries WHERE id = ?")
+                .bind(source_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Create entries with their items, tags, people, Jira refs and field
+    /// values, in one transaction. Returns the new entry ids in order.
+    pub async fn import_entries(&self, entries: &[(DateTime<Utc>, Vec<ResolvedItem>)]) -> Result<Vec<String>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut entry_ids = Vec::new();
+        for (timestamp, items) in entries {
+            let entry = Self::create_entry_in(&mut tx, *timestamp).await?;
+            for ResolvedItem { item, values } in items {
+                let entry_item = Self::create_entry_item_in(&mut tx, &entry.id, &item.item_type, &item.content, item.project.as_deref()).await?;
+                for name in &item.tags {
+                    let tag = Self::get_or_create_tag_in(&mut tx, name).await?;
+                    Self::link_item_tag_in(&mut tx, &entry_item.id, &tag.id).await?;
+                }
+                for name in &item.people {
+                    let person = Self::get_or_create_person_in(&mut tx, name).await?;
+                    Self::link_item_person_in(&mut tx, &entry_item.id, &person.id).await?;
+                }
+                for jira_key in &item.jira {
+                    Self::create_jira_ref_in(&mut tx, &entry_item.id, jira_key).await?;
+                }
+                if !values.is_empty() {
+                    Self::set_item_field_values_in(&mut tx, &entry_item.id, values).await?;
+                }
+            }
+            entry_ids.push(entry.id);
+        }
+        tx.commit().await?;
+        Ok(entry_ids)
+    }
+
+    /// Move items into a new entry at `timestamp`, in one transaction.
+    pub async fn split_entry(&self, item_ids: &[String], timestamp: DateTime<Utc>) -> Result<Entry, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("INSERT INTO entries (id, timestamp, created_at, updated_at) VALUES (?, ?, ?, ?)")
+            .bind(&id)
+            .bind(timestamp.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
+        Self::move_items_in(&mut tx, item_ids, &id).await?;
+        tx.commit().await?;
+
+        Ok(Entry {
+            id,
+            timestamp,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    async fn move_items_in(conn: &mut SqliteConnection, item_ids: &[String], target_entry_id: &str) -> Result<(), sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let mut sources = Vec::new();
+
+        for item_id in item_ids {
+            let source: String = sqlx::query_scalar("SELECT entry_id FROM entry_items WHERE id = ?")
+                .bind(item_id)
+                .fetch_one(&mut *conn)
+                .await?;
+            if source == target_entry_id {
+                continue;
+            }
+            let position = Self::next_item_position(&mut *conn, target_entry_id).await?;
+            sqlx::query("UPDATE entry_items SET entry_id = ?, position = ?, updated_at = ? WHERE id = ?")
+                .bind(target_entry_id)
+                .bind(position)
+                .bind(&now)
+                .bind(item_id)
+                .execute(&mut *conn)
+                .await?;
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+
+        sqlx::query("UPDATE entries SET updated_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(target_entry_id)
+            .execute(&mut *conn)
+            .await?;
+        for source in sources {
+            sqlx::query("UPDATE entries SET updated_at = ? WHERE id = ?")
+                .bind(&now)
+                .bind(&source)
+                .execute(&mut *conn)
+                .await?;
+            sqlx::query("DELETE FROM entries WHERE id = ? AND NOT EXISTS (SELECT 1 FROM entry_items WHERE entry_id = ?)")
+                .bind(&source)
+                .bind(&source)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Ok(())
+    }
+
+    // Project management methods
+    pub async fn create_project(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        color: Option<&str>,
+        hourly_rate: Option<f64>,
+        currency: Option<&str>,
+        billable: Option<bool>,
+    ) -> Result<Project, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let color = color.unwrap_or("#0275d8");
+        let currency = currency.unwrap_or("USD");
+        let billable = billable.unwrap_or(true);
+        
+        sqlx::query(
+            "INSERT INTO projects (id, name, description, color, hourly_rate, currency, billable, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(description)
+        .bind(color)
+        .bind(hourly_rate)
+        .bind(currency)
+        .bind(billable)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(Project {
+            id,
+            name: name.to_string(),
+            description: description.map(|s| s.to_string()),
+            color: color.to_string(),
+            hourly_rate,
+            currency: currency.to_string(),
+            billable,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_all_projects(&self) -> Result<Vec<Project>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, name, description, color, hourly_rate, currency, billable, created_at, updated_at FROM projects ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut projects = Vec::new();
+        for row in rows {
+            projects.push(Self::project_from_row(&row)?);
+        }
+
+        Ok(projects)
+    }
+
+    pub async fn get_project(&self, id: &str) -> Result<Option<Project>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, name, description, color, hourly_rate, currency, billable, created_at, updated_at FROM projects WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| Self::project_from_row(&row)).transpose()
+    }
+
+    fn project_from_row(row: &SqliteRow) -> Result<Project, sqlx::Error> {
+        let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+        let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+
+        Ok(Project {
+            id: row.get("id"),
+            name: row.get("name"),
+            description: row.get("description"),
+            color: row.get("color"),
+            hourly_rate: row.get("hourly_rate"),
+            currency: row.get("currency"),
+            billable: row.get("billable"),
+            created_at,
+            updated_at,
+        })
+    }
+
+    pub async fn get_project_by_name(&self, name: &str) -> Result<Option<Project>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, name, description, color, hourly_rate, currency, billable, created_at, updated_at FROM projects WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| Self::project_from_row(&row)).transpose()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_project(
+        &self,
+        id: &str,
+        name: Option<&str>,
+        description: Option<&str>,
+        color: Option<&str>,
+        hourly_rate: Option<f64>,
+        currency: Option<&str>,
+        billable: Option<bool>,
+    ) -> Result<Project, sqlx::Error> {
+        let now = Utc::now();
+        
+        // Build dynamic update query
+        let mut query_parts = vec!["updated_at = ?".to_string()];
+        
+        if name.is_some() {
+            query_parts.push("name = ?".to_string());
+        }
+        if description.is_some() {
+            query_parts.push("description = ?".to_string());
+        }
+        if color.is_some() {
+            query_parts.push("color = ?".to_string());
+        }
+        if hourly_rate.is_some() {
+            query_parts.push("hourly_rate = ?".to_string());
+        }
+        if currency.is_some() {
+            query_parts.push("currency = ?".to_string());
+        }
+        if billable.is_some() {
+            query_parts.push("billable = ?".to_string());
+        }
+        
+        let query_str = format!("UPDATE projects SET {} WHERE id = ?", query_parts.join(", "));
+        let mut query = sqlx::query(&query_str);
+        
+        query = query.bind(now.to_rfc3339());
+        
+        if let Some(name) = name {
+            query = query.bind(name);
+        }
+        if let Some(description) = description {
+            query = query.bind(description);
+        }
+        if let Some(color) = color {
+            query = query.bind(color);
+        }
+        if let Some(hourly_rate) = hourly_rate {
+            query = query.bind(hourly_rate);
+        }
+        if let Some(currency) = currency {
+            query = query.bind(currency);
+        }
+        if let Some(billable) = billable {
+            query = query.bind(billable);
+        }
+        
+        query = query.bind(id);
+        query.execute(&self.pool).await?;
+
+        // Return updated project
+        self.get_project(id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete_project(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM projects WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // Tag management methods
+    pub async fn create_tag(&self, name: &str, description: Option<&str>, color: Option<&str>, category: Option<&str>) -> Result<Tag, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let color = color.unwrap_or("#6c757d");
+        
+        sqlx::query(
+            "INSERT INTO tags (id, name, description, color, category, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(description)
+        .bind(color)
+        .bind(category)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(Tag {
+            id,
+            name: name.to_string(),
+            description: description.map(|s| s.to_string()),
+            color: color.to_string(),
+            category: category.map(|s| s.to_string()),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_all_tags(&self) -> Result<Vec<Tag>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, name, description, color, category, created_at, updated_at FROM tags ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut tags = Vec::new();
+        for row in rows {
+            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+
+            tags.push(Tag {
+                id: row.get("id"),
+                name: row.get("name"),
+                description: row.get("description"),
+                color: row.get("color"),
+                category: row.get("category"),
+                created_at,
+                updated_at,
+            });
+        }
+
+        Ok(tags)
+    }
+
+    pub async fn update_tag(&self, id: &str, name: Option<&str>, description: Option<&str>, color: Option<&str>, category: Option<&str>) -> Result<Tag, sqlx::Error> {
+        let now = Utc::now();
+        
+        // Build dynamic update query
+        let mut query_parts = vec!["updated_at = ?".to_string()];
+        
+        if let Some(_) = name {
+            query_parts.push("name = ?".to_string());
+        }
+        if let Some(_) = description {
+            query_parts.push("description = ?".to_string());
+        }
+        if let Some(_) = color {
+            query_parts.push("color = ?".to_string());
+        }
+        if let Some(_) = category {
+            query_parts.push("category = ?".to_string());
+        }
+        
+        let query_str = format!("UPDATE tags SET {} WHERE id = ?", query_parts.join(", "));
+        let mut query = sqlx::query(&query_str);
+        
+        query = query.bind(now.to_rfc3339());
+        
+        if let Some(name) = name {
+            query = query.bind(name);
+        }
+        if let Some(description) = description {
+            query = query.bind(description);
+        }
+        if let Some(color) = color {
+            query = query.bind(color);
+        }
+        if let Some(category) = category {
+            query = query.bind(category);
+        }
+        
+        query = query.bind(id);
+        query.execute(&self.pool).await?;
+
+        // Return updated tag
+        let row = sqlx::query("SELECT id, name, description, color, category, created_at, updated_at FROM tags WHERE id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+        let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+
+        Ok(Tag {
+            id: row.get("id"),
+            name: row.get("name"),
+            description: row.get("description"),
+            color: row.get("color"),
+            category: row.get("category"),
+            created_at,
+            updated_at,
+        })
+    }
+
+    pub async fn delete_tag(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM tags WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // Meeting management methods
+    pub async fn create_meeting(&self, meeting: NewMeeting<'_>) -> Result<Meeting, sqlx::Error> {
+        let NewMeeting { title, description, start_time, end_time, location, meeting_type, recurrence_rule } = meeting;
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let meeting_type = meeting_type.unwrap_or("meeting");
+
+        sqlx::query(
+            "INSERT INTO meetings (id, title, description, start_time, end_time, location, meeting_type, status, recurrence_rule, recurrence_exceptions, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(title)
+        .bind(description)
+        .bind(start_time.map(|t| t.to_rfc3339()))
+        .bind(end_time.map(|t| t.to_rfc3339()))
+        .bind(location)
+        .bind(meeting_type)
+        .bind("scheduled")
+        .bind(recurrence_rule)
+        .bind(None::<String>)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(Meeting {
+            id,
+            title: title.to_string(),
+            description: description.map(|s| s.to_string()),
+            start_time,
+            end_time,
+            location: location.map(|s| s.to_string()),
+            meeting_type: meeting_type.to_string(),
+            status: "scheduled".to_string(),
+            recurrence_rule: recurrence_rule.map(|s| s.to_string()),
+            recurrence_exceptions: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_all_meetings(&self) -> Result<Vec<Meeting>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, title, description, start_time, end_time, location, meeting_type, status, recurrence_rule, recurrence_exceptions, created_at, updated_at FROM meetings ORDER BY start_time DESC")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut meetings = Vec::new();
+        for row in rows {
+            meetings.push(Self::meeting_from_row(&row)?);
+        }
+
+        Ok(meetings)
+    }
+
+    /// Meetings of `meeting_type` that `name` attends, matched case-insensitively.
+    pub async fn get_meetings_with_attendee(&self, name: &str, meeting_type: &str) -> Result<Vec<Meeting>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT DISTINCT m.id, m.title, m.description, m.start_time, m.end_time, m.location, m.meeting_type, m.status, m.recurrence_rule, m.recurrence_exceptions, m.created_at, m.updated_at
+             FROM meetings m JOIN meeting_attendees a ON a.meeting_id = m.id
+             WHERE m.meeting_type = ? AND LOWER(TRIM(a.name)) = LOWER(TRIM(?))
+             ORDER BY m.start_time DESC"
+        )
+        .bind(meeting_type)
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut meetings = Vec::new();
+        for row in rows {
+            meetings.push(Self::meeting_from_row(&row)?);
+        }
+
+        Ok(meetings)
+    }
+
+    pub async fn get_meeting(&self, id: &str) -> Result<Option<Meeting>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, title, description, start_time, end_time, location, meeting_type, status, recurrence_rule, recurrence_exceptions, created_at, updated_at FROM meetings WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| Self::meeting_from_row(&row)).transpose()
+    }
+
+    fn meeting_from_row(row: &SqliteRow) -> Result<Meeting, sqlx::Error> {
+        let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+        let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+
+        let start_time = row.get::<Option<String>, _>("start_time")
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        let end_time = row.get::<Option<String>, _>("end_time")
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        let recurrence_exceptions = match row.get::<Option<String>, _>("recurrence_exceptions") {
+            Some(json) => serde_json::from_str::<Vec<DateTime<Utc>>>(&json)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            None => Vec::new(),
+        };
+
+        Ok(Meeting {
+            id: row.get("id"),
+            title: row.get("title"),
+            description: row.get("description"),
+            start_time,
+            end_time,
+            location: row.get("location"),
+            meeting_type: row.get("meeting_type"),
+            status: row.get("status"),
+            recurrence_rule: row.get("recurrence_rule"),
+            recurrence_exceptions,
+            created_at,
+            updated_at,
+        })
+    }
+
+    pub async fn add_meeting_attendee(
+        &self,
+        meeting_id: &str,
+        name: &str,
+        email: Option<&str>,
+        role: Option<&str>,
+    ) -> Result<MeetingAttendee, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let role = role.unwrap_or("attendee");
+
+        sqlx::query(
+            "INSERT INTO meeting_attendees (id, meeting_id, name, email, role, status, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(name)
+        .bind(email)
+        .bind(role)
+        .bind("invited")
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(MeetingAttendee {
+            id,
+            meeting_id: meeting_id.to_string(),
+            name: name.to_string(),
+            email: email.map(|s| s.to_string()),
+            role: role.to_string(),
+            status: "invited".to_string(),
+            created_at: now,
+        })
+    }
+
+    pub async fn get_meeting_attendees(&self, meeting_id: &str) -> Result<Vec<MeetingAttendee>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, meeting_id, name, email, role, status, created_at FROM meeting_attendees WHERE meeting_id = ? ORDER BY name")
+            .bind(meeting_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut attendees = Vec::new();
+        for row in rows {
+            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
+
+            attendees.push(MeetingAttendee {
+                id: row.get("id"),
+                meeting_id: row.get("meeting_id"),
+                name: row.get("name"),
+                email: row.get("email"),
+                role: row.get("role"),
+                status: row.get("status"),
+                created_at,
             });
         }
 
-        // Create new person
-        let id = Uuid::new_v4().to_string();
-        let now = Utc::now();
-        
-        sqlx::query("INSERT INTO people (id, name, created_at) VALUES (?, ?, ?)")
-            .bind(&id)
-            .bind(name)
-            .bind(now.to_rfc3339())
+        Ok(attendees)
+    }
+
+    pub async fn create_meeting_action(&self, action: NewMeetingAction<'_>) -> Result<MeetingAction, sqlx::Error> {
+        let NewMeetingAction { meeting_id, title, description, assignee, due_date, priority, occurrence_start } = action;
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let priority = priority.unwrap_or("medium");
+
+        sqlx::query(
+            "INSERT INTO meeting_actions (id, meeting_id, title, description, assignee, due_date, status, priority, occurrence_start, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(title)
+        .bind(description)
+        .bind(assignee)
+        .bind(due_date.map(|t| t.to_rfc3339()))
+        .bind("open")
+        .bind(priority)
+        .bind(occurrence_start.map(|t| t.to_rfc3339()))
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(MeetingAction {
+            id,
+            meeting_id: meeting_id.to_string(),
+            entry_item_id: None,
+            title: title.to_string(),
+            description: description.map(|s| s.to_string()),
+            assignee: assignee.map(|s| s.to_string()),
+            due_date,
+            status: "open".to_string(),
+            priority: priority.to_string(),
+            occurrence_start,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_meeting_actions(&self, meeting_id: &str) -> Result<Vec<MeetingAction>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, meeting_id, entry_item_id, title, description, assignee, due_date, status, priority, occurrence_start, created_at, updated_at FROM meeting_actions WHERE meeting_id = ? ORDER BY created_at DESC")
+            .bind(meeting_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::meeting_action_from_row).collect()
+    }
+
+    /// Open actions that have a due date, with the title of their meeting.
+    pub async fn get_open_actions_with_due_dates(&self) -> Result<Vec<(MeetingAction, String)>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT a.id, a.meeting_id, a.entry_item_id, a.title, a.description, a.assignee, a.due_date, a.status, a.priority, a.occurrence_start, a.created_at, a.updated_at, m.title AS meeting_title
+             FROM meeting_actions a JOIN meetings m ON m.id = a.meeting_id
+             WHERE a.status = 'open' AND a.due_date IS NOT NULL
+             ORDER BY a.due_date"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok((Self::meeting_action_from_row(row)?, row.get("meeting_title"))))
+            .collect()
+    }
+
+    /// Open actions from every meeting with the meeting's title, oldest first.
+    pub async fn get_open_meeting_actions(&self) -> Result<Vec<(MeetingAction, String)>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT a.id, a.meeting_id, a.entry_item_id, a.title, a.description, a.assignee, a.due_date, a.status, a.priority, a.occurrence_start, a.created_at, a.updated_at, m.title AS meeting_title
+             FROM meeting_actions a JOIN meetings m ON m.id = a.meeting_id
+             WHERE a.status = 'open'
+             ORDER BY a.created_at"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok((Self::meeting_action_from_row(row)?, row.get("meeting_title"))))
+            .collect()
+    }
+
+    /// Actions raised or updated in `[from, to)`, with the title of their
+    /// meeting and the project of the item they were logged from.
+    pub async fn get_meeting_actions_touched_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<(MeetingAction, String, Option<String>)>, sqlx::Error> {
+        let from = from.to_rfc3339();
+        let to = to.to_rfc3339();
+        let rows = sqlx::query(
+            "SELECT a.id, a.meeting_id, a.entry_item_id, a.title, a.description, a.assignee, a.due_date, a.status, a.priority, a.occurrence_start, a.created_at, a.updated_at, m.title AS meeting_title, i.project AS item_project
+             FROM meeting_actions a JOIN meetings m ON m.id = a.meeting_id
+             LEFT JOIN entry_items i ON i.id = a.entry_item_id
+             WHERE (a.created_at >= ? AND a.created_at < ?) OR (a.updated_at >= ? AND a.updated_at < ?)
+             ORDER BY a.created_at"
+        )
+        .bind(&from)
+        .bind(&to)
+        .bind(&from)
+        .bind(&to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok((Self::meeting_action_from_row(row)?, row.get("meeting_title"), row.get("item_project"))))
+            .collect()
+    }
+
+    pub async fn update_meeting_action_status(&self, id: &str, status: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE meeting_actions SET status = ?, updated_at = ? WHERE id = ?")
+            .bind(status)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
             .execute(&self.pool)
             .await?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        Ok(Person {
-            id,
-            name: name.to_string(),
-            created_at: now,
+    fn meeting_action_from_row(row: &SqliteRow) -> Result<MeetingAction, sqlx::Error> {
+        let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+        let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            .with_timezone(&Utc);
+
+        let due_date = row.get::<Option<String>, _>("due_date")
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        let occurrence_start = row.get::<Option<String>, _>("occurrence_start")
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        Ok(MeetingAction {
+            id: row.get("id"),
+            meeting_id: row.get("meeting_id"),
+            entry_item_id: row.get("entry_item_id"),
+            title: row.get("title"),
+            description: row.get("description"),
+            assignee: row.get("assignee"),
+            due_date,
+            status: row.get("status"),
+            priority: row.get("priority"),
+            occurrence_start,
+            created_at,
+            updated_at,
         })
     }
 
-    pub async fn create_jira_ref(&self, entry_item_id: &str, jira_key: &str) -> Result<JiraRef, sqlx::Error> {
-        let id = Uuid::new_v4().to_string();
+    pub async fn update_meeting_recurrence(
+        &self,
+        id: &str,
+        recurrence_rule: Option<&str>,
+        exceptions: &[DateTime<Utc>],
+    ) -> Result<(), sqlx::Error> {
         let now = Utc::now();
-        
-        sqlx::query("INSERT INTO jira_refs (id, entry_item_id, jira_key, created_at) VALUES (?, ?, ?, ?)")
-            .bind(&id)
-            .bind(entry_item_id)
-            .bind(jira_key)
+        let exceptions = if exceptions.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(exceptions).map_err(|e| sqlx::Error::Protocol(e.to_string()))?)
+        };
+
+        sqlx::query("UPDATE meetings SET recurrence_rule = ?, recurrence_exceptions = ?, updated_at = ? WHERE id = ?")
+            .bind(recurrence_rule)
+            .bind(exceptions)
             .bind(now.to_rfc3339())
+            .bind(id)
             .execute(&self.pool)
             .await?;
-
-        Ok(JiraRef {
-            id,
-            entry_item_id: entry_item_id.to_string(),
-            jira_key: jira_key.to_string(),
-            created_at: now,
-        })
+        Ok(())
     }
 
-    pub async fn link_item_tag(&self, entry_item_id: &str, tag_id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("INSERT OR IGNORE INTO item_tags (entry_item_id, tag_id) VALUES (?, ?)")
+    /// Link an entry item to a meeting. For recurring meetings the link can be
+    /// scoped to a single occurrence by passing its start time.
+    pub async fn link_meeting_note(
+        &self,
+        meeting_id: &str,
+        entry_item_id: &str,
+        occurrence_start: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT OR REPLACE INTO meeting_notes (meeting_id, entry_item_id, occurrence_start) VALUES (?, ?, ?)")
+            .bind(meeting_id)
             .bind(entry_item_id)
-            .bind(tag_id)
+            .bind(occurrence_start.map(|t| t.to_rfc3339()))
             .execute(&self.pool)
             .await?;
         Ok(())
     }
 
-    pub async fn link_item_person(&self, entry_item_id: &str, person_id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("INSERT OR IGNORE INTO item_people (entry_item_id, person_id) VALUES (?, ?)")
+    pub async fn unlink_meeting_note(&self, meeting_id: &str, entry_item_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM meeting_notes WHERE meeting_id = ? AND entry_item_id = ?")
+            .bind(meeting_id)
             .bind(entry_item_id)
-            .bind(person_id)
             .execute(&self.pool)
             .await?;
         Ok(())
     }
 
-    pub async fn get_all_entries_with_items(&self) -> Result<Vec<EntryWithItems>, sqlx::Error> {
-        let entries = sqlx::query("SELECT id, timestamp, created_at, updated_at FROM entries ORDER BY timestamp DESC")
-            .fetch_all(&self.pool)
+    /// Entry items linked to a meeting, either directly as notes or through one
+    /// of the meeting's actions. When `occurrence_start` is given only items
+    /// attached to that occurrence of a recurring meeting are returned.
+    pub async fn get_meeting_notes(
+        &self,
+        meeting_id: &str,
+        occurrence_start: Option<DateTime<Utc>>,
+    ) -> Result<Vec<EntryItemWithMetadata>, sqlx::Error> {
+        let occurrence_start = occurrence_start.map(|t| t.to_rfc3339());
+        let items = sqlx::query(
+            "SELECT ei.id, ei.entry_id, ei.item_type, ei.content, ei.project, ei.position, ei.created_at, ei.updated_at FROM entry_items ei
+             JOIN entries e ON e.id = ei.entry_id
+             WHERE ei.id IN (
+                 SELECT entry_item_id FROM meeting_notes
+                 WHERE meeting_id = ? AND (? IS NULL OR occurrence_start = ?)
+                 UNION
+                 SELECT entry_item_id FROM meeting_actions
+                 WHERE meeting_id = ? AND entry_item_id IS NOT NULL AND (? IS NULL OR occurrence_start = ?)
+             )
+             ORDER BY e.timestamp, ei.position, ei.created_at"
+        )
+        .bind(meeting_id)
+        .bind(&occurrence_start)
+        .bind(&occurrence_start)
+        .bind(meeting_id)
+        .bind(&occurrence_start)
+        .bind(&occurrence_start)
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.attach_item_metadata(items).await
+    }
+
+    pub async fn delete_meeting(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM meetings WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
             .await?;
+        Ok(())
+    }
 
-        let mut result = Vec::new();
-        
-        for row in entries {
-            let entry = Entry {
-                id: row.get("id"),
-                timestamp: DateTime::parse_from_rfc3339(&row.get::<String, _>("timestamp"))
-                    .unwrap()
-                    .with_timezone(&Utc),
-                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                    .unwrap()
-                    .with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                    .unwrap()
-                    .with_timezone(&Utc),
-            };
+    // Time tracking methods
+    #[allow(clippy::too_many_arguments)]
+    pub async fn start_timer(
+        &self,
+        project: Option<&str>,
+        entry_item_id: Option<&str>,
+        jira_key: Option<&str>,
+        notes: Option<&str>,
+        person: Option<&str>,
+        billable: bool,
+        start_time: DateTime<Utc>,
+    ) -> Result<TimeEntry, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
 
-            let items = self.get_entry_items_with_metadata(&entry.id).await?;
-            result.push(EntryWithItems { entry, items });
-        }
+        sqlx::query(
+            "INSERT INTO time_entries (id, project, entry_item_id, jira_key, start_time, end_time, notes, last_heartbeat, person, billable, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(project)
+        .bind(entry_item_id)
+        .bind(jira_key)
+        .bind(start_time.to_rfc3339())
+        .bind(None::<String>)
+        .bind(notes)
+        .bind(start_time.to_rfc3339())
+        .bind(person)
+        .bind(billable)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
 
-        Ok(result)
+        Ok(TimeEntry {
+            id,
+            project: project.map(|s| s.to_string()),
+            entry_item_id: entry_item_id.map(|s| s.to_string()),
+            jira_key: jira_key.map(|s| s.to_string()),
+            start_time,
+            end_time: None,
+            notes: notes.map(|s| s.to_string()),
+            last_heartbeat: Some(start_time),
+            person: person.map(|s| s.to_string()),
+            billable,
+            invoice_id: None,
+            created_at: now,
+            updated_at: now,
+        })
     }
 
-    async fn get_entry_items_with_metadata(&self, entry_id: &str) -> Result<Vec<EntryItemWithMetadata>, sqlx::Error> {
-        let items = sqlx::query("SELECT id, entry_id, item_type, content, project, created_at, updated_at FROM entry_items WHERE entry_id = ? ORDER BY created_at")
-            .bind(entry_id)
-            .fetch_all(&self.pool)
+    /// Stop the running timer, if any, and return it.
+    pub async fn stop_timer(&self, end_time: DateTime<Utc>) -> Result<Option<TimeEntry>, sqlx::Error> {
+        let Some(active) = self.get_active_timer().await? else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        sqlx::query("UPDATE time_entries SET end_time = ?, updated_at = ? WHERE id = ?")
+            .bind(end_time.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .bind(&active.id)
+            .execute(&self.pool)
             .await?;
 
-        let mut result = Vec::new();
-        
-        for row in items {
-            let item = EntryItem {
-                id: row.get("id"),
-                entry_id: row.get("entry_id"),
-                item_type: row.get("item_type"),
-                content: row.get("content"),
-                project: row.get("project"),
-                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                    .unwrap()
-                    .with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                    .unwrap()
-                    .with_timezone(&Utc),
-            };
+        self.get_time_entry(&active.id).await
+    }
 
-            let tags = self.get_item_tags(&item.id).await?;
-            let people = self.get_item_people(&item.id).await?;
-            let jira_refs = self.get_item_jira_refs(&item.id).await?;
+    pub async fn record_timer_heartbeat(&self, id: &str, heartbeat: DateTime<Utc>) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE time_entries SET last_heartbeat = ? WHERE id = ? AND end_time IS NULL")
+            .bind(heartbeat.to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
 
-            result.push(EntryItemWithMetadata {
-                item,
-                tags,
-                people,
-                jira_refs,
-            });
-        }
+    pub async fn get_active_timer(&self) -> Result<Option<TimeEntry>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, project, entry_item_id, jira_key, start_time, end_time, notes, last_heartbeat, person, billable, invoice_id, created_at, updated_at FROM time_entries WHERE end_time IS NULL")
+            .fetch_optional(&self.pool)
+            .await?;
 
-        Ok(result)
+        row.map(|row| Self::time_entry_from_row(&row)).transpose()
     }
 
-    async fn get_item_tags(&self, entry_item_id: &str) -> Result<Vec<Tag>, sqlx::Error> {
+    pub async fn get_time_entry(&self, id: &str) -> Result<Option<TimeEntry>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, project, entry_item_id, jira_key, start_time, end_time, notes, last_heartbeat, person, billable, invoice_id, created_at, updated_at FROM time_entries WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| Self::time_entry_from_row(&row)).transpose()
+    }
+
+    /// Time entries that overlap `[from, to)`, oldest first. Either bound may
+    /// be omitted; running timers are treated as ending now.
+    pub async fn get_time_entries(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        project: Option<&str>,
+    ) -> Result<Vec<TimeEntry>, sqlx::Error> {
         let rows = sqlx::query(
-            "SELECT t.id, t.name, t.description, t.color, t.category, t.created_at, t.updated_at FROM tags t 
-             JOIN item_tags it ON t.id = it.tag_id 
-             WHERE it.entry_item_id = ?"
+            "SELECT id, project, entry_item_id, jira_key, start_time, end_time, notes, last_heartbeat, person, billable, invoice_id, created_at, updated_at FROM time_entries
+             WHERE (? IS NULL OR start_time < ?)
+               AND (? IS NULL OR end_time IS NULL OR end_time > ?)
+               AND (? IS NULL OR project = ?)
+             ORDER BY start_time"
         )
-        .bind(entry_item_id)
+        .bind(to.map(|t| t.to_rfc3339()))
+        .bind(to.map(|t| t.to_rfc3339()))
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(project)
+        .bind(project)
         .fetch_all(&self.pool)
         .await?;
 
-        let mut tags = Vec::new();
+        let mut entries = Vec::new();
         for row in rows {
-            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
-            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
+            entries.push(Self::time_entry_from_row(&row)?);
+        }
 
-            tags.push(Tag {
-                id: row.get("id"),
-                name: row.get("name"),
-                description: row.get("description"),
-                color: row.get("color"),
-                category: row.get("category"),
-                created_at,
-                updated_at,
-            });
+        Ok(entries)
+    }
+
+    pub async fn update_time_entry(&self, entry: &TimeEntry) -> Result<TimeEntry, sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query(
+            "UPDATE time_entries SET project = ?, entry_item_id = ?, jira_key = ?, start_time = ?, end_time = ?, notes = ?, person = ?, billable = ?, updated_at = ? WHERE id = ?"
+        )
+        .bind(&entry.project)
+        .bind(&entry.entry_item_id)
+        .bind(&entry.jira_key)
+        .bind(entry.start_time.to_rfc3339())
+        .bind(entry.end_time.map(|t| t.to_rfc3339()))
+        .bind(&entry.notes)
+        .bind(&entry.person)
+        .bind(entry.billable)
+        .bind(now.to_rfc3339())
+        .bind(&entry.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(TimeEntry {
+            updated_at: now,
+            ..entry.clone()
+        })
+    }
+
+    pub async fn delete_time_entry(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM time_entries WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    fn time_entry_from_row(row: &SqliteRow) -> Result<TimeEntry, sqlx::Error> {
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
+
+        Ok(TimeEntry {
+            id: row.get("id"),
+            project: row.get("project"),
+            entry_item_id: row.get("entry_item_id"),
+            jira_key: row.get("jira_key"),
+            start_time: parse(row.get("start_time"))?,
+            end_time: row.get::<Option<String>, _>("end_time").map(parse).transpose()?,
+            notes: row.get("notes"),
+            last_heartbeat: row.get::<Option<String>, _>("last_heartbeat").map(parse).transpose()?,
+            person: row.get("person"),
+            billable: row.get("billable"),
+            invoice_id: row.get("invoice_id"),
+            created_at: parse(row.get("created_at"))?,
+            updated_at: parse(row.get("updated_at"))?,
+        })
+    }
+
+    // Billing methods
+    /// Set a person's rate on a project, or remove it with `None` so the
+    /// project rate applies again.
+    pub async fn set_project_person_rate(&self, project_id: &str, person_id: &str, hourly_rate: Option<f64>) -> Result<(), sqlx::Error> {
+        match hourly_rate {
+            Some(rate) => {
+                sqlx::query("INSERT OR REPLACE INTO project_person_rates (project_id, person_id, hourly_rate) VALUES (?, ?, ?)")
+                    .bind(project_id)
+                    .bind(person_id)
+                    .bind(rate)
+                    .execute(&self.pool)
+                    .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM project_person_rates WHERE project_id = ? AND person_id = ?")
+                    .bind(project_id)
+                    .bind(person_id)
+                    .execute(&self.pool)
+                    .await?;
+            }
         }
-        Ok(tags)
+        Ok(())
     }
 
-    async fn get_item_people(&self, entry_item_id: &str) -> Result<Vec<Person>, sqlx::Error> {
+    pub async fn get_project_person_rates(&self, project_id: &str) -> Result<Vec<ProjectPersonRate>, sqlx::Error> {
         let rows = sqlx::query(
-            "SELECT p.id, p.name, p.created_at FROM people p 
-             JOIN item_people ip ON p.id = ip.person_id 
-             WHERE ip.entry_item_id = ?"
+            "SELECT r.project_id, p.name, r.hourly_rate FROM project_person_rates r
+             JOIN people p ON p.id = r.person_id
+             WHERE r.project_id = ?
+             ORDER BY p.name"
         )
-        .bind(entry_item_id)
+        .bind(project_id)
         .fetch_all(&self.pool)
         .await?;
 
-        let mut people = Vec::new();
-        for row in rows {
-            people.push(Person {
-                id: row.get("id"),
-                name: row.get("name"),
-                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                    .unwrap()
-                    .with_timezone(&Utc),
-            });
-        }
-        Ok(people)
+        Ok(rows.iter().map(|row| ProjectPersonRate {
+            project_id: row.get("project_id"),
+            person: row.get("name"),
+            hourly_rate: row.get("hourly_rate"),
+        }).collect())
     }
 
-    async fn get_item_jira_refs(&self, entry_item_id: &str) -> Result<Vec<JiraRef>, sqlx::Error> {
-        let rows = sqlx::query("SELECT id, entry_item_id, jira_key, created_at FROM jira_refs WHERE entry_item_id = ?")
-            .bind(entry_item_id)
-            .fetch_all(&self.pool)
-            .await?;
+    /// Finished, billable time on a project that started within
+    /// `[from, to)` and is not on an invoice yet.
+    pub async fn get_uninvoiced_time_entries(
+        &self,
+        project: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<TimeEntry>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, project, entry_item_id, jira_key, start_time, end_time, notes, last_heartbeat, person, billable, invoice_id, created_at, updated_at FROM time_entries
+             WHERE project = ? AND start_time >= ? AND start_time < ?
+               AND end_time IS NOT NULL AND billable = 1 AND invoice_id IS NULL
+             ORDER BY start_time"
+        )
+        .bind(project)
+        .bind(from.to_rfc3339())
+        .bind(to.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
 
-        let mut jira_refs = Vec::new();
+        let mut entries = Vec::new();
         for row in rows {
-            jira_refs.push(JiraRef {
-                id: row.get("id"),
-                entry_item_id: row.get("entry_item_id"),
-                jira_key: row.get("jira_key"),
-                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                    .unwrap()
-                    .with_timezone(&Utc),
-            });
+            entries.push(Self::time_entry_from_row(&row)?);
         }
-        Ok(jira_refs)
-    }
 
-    pub async fn update_entry_item_content(&self, entry_item_id: &str, content: &str) -> Result<(), sqlx::Error> {
-        let now = Utc::now();
-        sqlx::query("UPDATE entry_items SET content = ?, updated_at = ? WHERE id = ?")
-            .bind(content)
-            .bind(now.to_rfc3339())
-            .bind(entry_item_id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+        Ok(entries)
     }
 
-    pub async fn update_entry_item_project(&self, entry_item_id: &str, project: Option<&str>) -> Result<(), sqlx::Error> {
+    /// Store an invoice under the next sequential number and mark its time
+    /// entries as billed. Everything happens in one transaction; if any entry
+    /// was invoiced in the meantime nothing is written.
+    pub async fn create_invoice(
+        &self,
+        project: &Project,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        lines: &[InvoiceLine],
+    ) -> Result<Invoice, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
         let now = Utc::now();
-        sqlx::query("UPDATE entry_items SET project = ?, updated_at = ? WHERE id = ?")
-            .bind(project)
-            .bind(now.to_rfc3339())
-            .bind(entry_item_id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
-    }
+        let total = lines.iter().map(|l| l.amount).sum::<f64>();
 
-    pub async fn remove_item_tags(&self, entry_item_id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM item_tags WHERE entry_item_id = ?")
-            .bind(entry_item_id)
-            .execute(&self.pool)
+        let mut tx = self.pool.begin().await?;
+
+        let number: i64 = sqlx::query("SELECT COALESCE(MAX(number), 0) + 1 AS next FROM invoices")
+            .fetch_one(&mut *tx)
+            .await?
+            .get("next");
+
+        sqlx::query(
+            "INSERT INTO invoices (id, number, project_id, project_name, period_start, period_end, currency, total, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(number)
+        .bind(&project.id)
+        .bind(&project.name)
+        .bind(period_start.to_rfc3339())
+        .bind(period_end.to_rfc3339())
+        .bind(&project.currency)
+        .bind(total)
+        .bind(now.to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+
+        for line in lines {
+            sqlx::query(
+                "INSERT INTO invoice_lines (id, invoice_id, time_entry_id, date, description, person, hours, rate, amount) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&id)
+            .bind(&line.time_entry_id)
+            .bind(line.date.to_rfc3339())
+            .bind(&line.description)
+            .bind(&line.person)
+            .bind(line.hours)
+            .bind(line.rate)
+            .bind(line.amount)
+            .execute(&mut *tx)
             .await?;
-        Ok(())
+
+            if let Some(time_entry_id) = &line.time_entry_id {
+                let result = sqlx::query("UPDATE time_entries SET invoice_id = ?, updated_at = ? WHERE id = ? AND invoice_id IS NULL")
+                    .bind(&id)
+                    .bind(now.to_rfc3339())
+                    .bind(time_entry_id)
+                    .execute(&mut *tx)
+                    .await?;
+                if result.rows_affected() != 1 {
+                    tx.rollback().await?;
+                    return Err(sqlx::Error::RowNotFound);
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(Invoice {
+            id,
+            number,
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            period_start,
+            period_end,
+            currency: project.currency.clone(),
+            total,
+            created_at: now,
+        })
     }
 
-    pub async fn remove_item_people(&self, entry_item_id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM item_people WHERE entry_item_id = ?")
-            .bind(entry_item_id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+    pub async fn get_invoices(&self, project_id: Option<&str>) -> Result<Vec<Invoice>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, number, project_id, project_name, period_start, period_end, currency, total, created_at FROM invoices
+             WHERE (? IS NULL OR project_id = ?)
+             ORDER BY number DESC"
+        )
+        .bind(project_id)
+        .bind(project_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut invoices = Vec::new();
+        for row in rows {
+            invoices.push(Self::invoice_from_row(&row)?);
+        }
+
+        Ok(invoices)
     }
 
-    pub async fn remove_item_jira_refs(&self, entry_item_id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM jira_refs WHERE entry_item_id = ?")
-            .bind(entry_item_id)
-            .execute(&self.pool)
+    pub async fn get_invoice(&self, id: &str) -> Result<Option<Invoice>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, number, project_id, project_name, period_start, period_end, currency, total, created_at FROM invoices WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
             .await?;
-        Ok(())
+
+        row.map(|row| Self::invoice_from_row(&row)).transpose()
     }
 
-    pub async fn get_entry_with_items(&self, entry_item_id: &str) -> Result<EntryWithItems, sqlx::Error> {
-        // First get the entry_id from the entry_item
-        let entry_id: String = sqlx::query_scalar("SELECT entry_id FROM entry_items WHERE id = ?")
-            .bind(entry_item_id)
-            .fetch_one(&self.pool)
-            .await?;
+    pub async fn get_invoice_lines(&self, invoice_id: &str) -> Result<Vec<InvoiceLine>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, invoice_id, time_entry_id, date, description, person, hours, rate, amount FROM invoice_lines
+             WHERE invoice_id = ?
+             ORDER BY date"
+        )
+        .bind(invoice_id)
+        .fetch_all(&self.pool)
+        .await?;
 
-        // Get the entry
-        let entry_row = sqlx::query("SELECT id, timestamp, created_at, updated_at FROM entries WHERE id = ?")
-            .bind(&entry_id)
-            .fetch_one(&self.pool)
-            .await?;
+        let mut lines = Vec::new();
+        for row in rows {
+            let date = DateTime::parse_from_rfc3339(&row.get::<String, _>("date"))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                .with_timezone(&Utc);
 
-        let timestamp: String = entry_row.get("timestamp");
-        let created_at: String = entry_row.get("created_at");
-        let updated_at: String = entry_row.get("updated_at");
+            lines.push(InvoiceLine {
+                id: row.get("id"),
+                invoice_id: row.get("invoice_id"),
+                time_entry_id: row.get("time_entry_id"),
+                date,
+                description: row.get("description"),
+                person: row.get("person"),
+                hours: row.get("hours"),
+                rate: row.get("rate"),
+                amount: row.get("amount"),
+            });
+        }
 
-        let timestamp = DateTime::parse_from_rfc3339(&timestamp)
-            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-            .with_timezone(&Utc);
-        let created_at = DateTime::parse_from_rfc3339(&created_at)
-            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-            .with_timezone(&Utc);
-        let updated_at = DateTime::parse_from_rfc3339(&updated_at)
-            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-            .with_timezone(&Utc);
+        Ok(lines)
+    }
 
-        let entry = Entry {
-            id: entry_row.get("id"),
-            timestamp,
-            created_at,
-            updated_at,
+    fn invoice_from_row(row: &SqliteRow) -> Result<Invoice, sqlx::Error> {
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
         };
 
-        // Get items with metadata
-        let items = self.get_entry_items_with_metadata(&entry_id).await?;
-
-        Ok(EntryWithItems { entry, items })
+        Ok(Invoice {
+            id: row.get("id"),
+            number: row.get("number"),
+            project_id: row.get("project_id"),
+            project_name: row.get("project_name"),
+            period_start: parse(row.get("period_start"))?,
+            period_end: parse(row.get("period_end"))?,
+            currency: row.get("currency"),
+            total: row.get("total"),
+            created_at: parse(row.get("created_at"))?,
+        })
     }
 
-    pub async fn delete_entry_item(&self, entry_item_id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM entry_items WHERE id = ?")
-            .bind(entry_item_id)
-            .execute(&self.pool)
+    // Settings methods
+    /// Settings are stored as JSON strings keyed by feature, e.g. `focus`.
+    pub async fn get_setting(&self, key: &str) -> Result<Option<String>, sqlx::Error> {
+        let row = sqlx::query("SELECT value FROM settings WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
             .await?;
-        Ok(())
+
+        Ok(row.map(|row| row.get("value")))
     }
 
-    pub async fn delete_entry(&self, entry_id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM entries WHERE id = ?")
-            .bind(entry_id)
+    pub async fn set_setting(&self, key: &str, value: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT OR REPLACE INTO settings (key, value, updated_at) VALUES (?, ?, ?)")
+            .bind(key)
+            .bind(value)
+            .bind(Utc::now().to_rfc3339())
             .execute(&self.pool)
             .await?;
         Ok(())
     }
 
-    // Project management methods
-    pub async fn create_project(&self, name: &str, description: Option<&str>, color: Option<&str>) -> Result<Project, sqlx::Error> {
+    // Focus session methods
+    pub async fn record_focus_session(
+        &self,
+        project: Option<&str>,
+        time_entry_id: Option<&str>,
+        cycle: i64,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<FocusSessionRecord, sqlx::Error> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now();
-        let color = color.unwrap_or("#0275d8");
-        
+
         sqlx::query(
-            "INSERT INTO projects (id, name, description, color, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)"
+            "INSERT INTO focus_sessions (id, project, time_entry_id, cycle, start_time, end_time, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&id)
-        .bind(name)
-        .bind(description)
-        .bind(color)
-        .bind(now.to_rfc3339())
+        .bind(project)
+        .bind(time_entry_id)
+        .bind(cycle)
+        .bind(start_time.to_rfc3339())
+        .bind(end_time.to_rfc3339())
         .bind(now.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(Project {
+        Ok(FocusSessionRecord {
             id,
-            name: name.to_string(),
-            description: description.map(|s| s.to_string()),
-            color: color.to_string(),
+            project: project.map(|s| s.to_string()),
+            time_entry_id: time_entry_id.map(|s| s.to_string()),
+            cycle,
+            start_time,
+            end_time,
             created_at: now,
-            updated_at: now,
         })
     }
 
-    pub async fn get_all_projects(&self) -> Result<Vec<Project>, sqlx::Error> {
-        let rows = sqlx::query("SELECT id, name, description, color, created_at, updated_at FROM projects ORDER BY name")
-            .fetch_all(&self.pool)
-            .await?;
+    /// Completed focus blocks that started within `[from, to)`, oldest first.
+    pub async fn get_focus_sessions(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        project: Option<&str>,
+    ) -> Result<Vec<FocusSessionRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, project, time_entry_id, cycle, start_time, end_time, created_at FROM focus_sessions
+             WHERE (? IS NULL OR start_time >= ?)
+               AND (? IS NULL OR start_time < ?)
+               AND (? IS NULL OR project = ?)
+             ORDER BY start_time"
+        )
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(to.map(|t| t.to_rfc3339()))
+        .bind(to.map(|t| t.to_rfc3339()))
+        .bind(project)
+        .bind(project)
+        .fetch_all(&self.pool)
+        .await?;
 
-        let mut projects = Vec::new();
-        for row in rows {
-            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
-            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
 
-            projects.push(Project {
+        let mut sessions = Vec::new();
+        for row in rows {
+            sessions.push(FocusSessionRecord {
                 id: row.get("id"),
-                name: row.get("name"),
-                description: row.get("description"),
-                color: row.get("color"),
-                created_at,
-                updated_at,
+                project: row.get("project"),
+                time_entry_id: row.get("time_entry_id"),
+                cycle: row.get("cycle"),
+                start_time: parse(row.get("start_time"))?,
+                end_time: parse(row.get("end_time"))?,
+                created_at: parse(row.get("created_at"))?,
             });
         }
 
-        Ok(projects)
+        Ok(sessions)
     }
 
-    pub async fn get_project_by_name(&self, name: &str) -> Result<Option<Project>, sqlx::Error> {
-        let row = sqlx::query("SELECT id, name, description, color, created_at, updated_at FROM projects WHERE name = ?")
-            .bind(name)
-            .fetch_optional(&self.pool)
-            .await?;
+    // Analytics methods
+    /// Run a counting query whose first two placeholders pairs are the
+    /// optional `[from, to)` bounds on `entries.timestamp`.
+    async fn count_points(
+        &self,
+        sql: &str,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<i64>,
+    ) -> Result<Vec<CountPoint>, sqlx::Error> {
+        let mut query = sqlx::query(sql)
+            .bind(from.map(|t| t.to_rfc3339()))
+            .bind(from.map(|t| t.to_rfc3339()))
+            .bind(to.map(|t| t.to_rfc3339()))
+            .bind(to.map(|t| t.to_rfc3339()));
+        if let Some(limit) = limit {
+            query = query.bind(limit);
+        }
 
-        if let Some(row) = row {
-            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
-            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
+        let rows = query.fetch_all(&self.pool).await?;
+        Ok(rows.iter().map(|row| CountPoint {
+            key: row.get("key"),
+            count: row.get("count"),
+        }).collect())
+    }
 
-            Ok(Some(Project {
-                id: row.get("id"),
-                name: row.get("name"),
-                description: row.get("description"),
-                color: row.get("color"),
-                created_at,
-                updated_at,
-            }))
-        } else {
-            Ok(None)
-        }
+    pub async fn count_entries_and_items(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<(i64, i64), sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT COUNT(DISTINCT e.id) AS entries, COUNT(i.id) AS items
+             FROM entries e LEFT JOIN entry_items i ON i.entry_id = e.id
+             WHERE (? IS NULL OR e.timestamp >= ?) AND (? IS NULL OR e.timestamp < ?)"
+        )
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(to.map(|t| t.to_rfc3339()))
+        .bind(to.map(|t| t.to_rfc3339()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((row.get("entries"), row.get("items")))
     }
 
-    pub async fn update_project(&self, id: &str, name: Option<&str>, description: Option<&str>, color: Option<&str>) -> Result<Project, sqlx::Error> {
-        let now = Utc::now();
-        
-        // Build dynamic update query
-        let mut query_parts = vec!["updated_at = ?".to_string()];
-        
-        if let Some(_) = name {
-            query_parts.push("name = ?".to_string());
-        }
-        if let Some(_) = description {
-            query_parts.push("description = ?".to_string());
-        }
-        if let Some(_) = color {
-            query_parts.push("color = ?".to_string());
-        }
-        
-        let query_str = format!("UPDATE projects SET {} WHERE id = ?", query_parts.join(", "));
-        let mut query = sqlx::query(&query_str);
-        
-        query = query.bind(now.to_rfc3339());
-        
-        if let Some(name) = name {
-            query = query.bind(name);
-        }
-        if let Some(description) = description {
-            query = query.bind(description);
-        }
-        if let Some(color) = color {
-            query = query.bind(color);
-        }
-        
-        query = query.bind(id);
-        query.execute(&self.pool).await?;
+    /// Items per local day, week or month, keyed by the period's first day.
+    pub async fn count_items_by_period(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        granularity: Granularity,
+    ) -> Result<Vec<CountPoint>, sqlx::Error> {
+        let sql = format!(
+            "SELECT {} AS key, COUNT(*) AS count
+             FROM entry_items i JOIN entries e ON e.id = i.entry_id
+             WHERE (? IS NULL OR e.timestamp >= ?) AND (? IS NULL OR e.timestamp < ?)
+             GROUP BY key ORDER BY key",
+            granularity.bucket_sql("e.timestamp")
+        );
+        self.count_points(&sql, from, to, None).await
+    }
+
+    pub async fn count_items_by_type(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CountPoint>, sqlx::Error> {
+        self.count_points(
+            "SELECT i.item_type AS key, COUNT(*) AS count
+             FROM entry_items i JOIN entries e ON e.id = i.entry_id
+             WHERE (? IS NULL OR e.timestamp >= ?) AND (? IS NULL OR e.timestamp < ?)
+             GROUP BY key ORDER BY count DESC, key",
+            from, to, None,
+        ).await
+    }
+
+    pub async fn top_tags(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>, limit: i64) -> Result<Vec<CountPoint>, sqlx::Error> {
+        self.count_points(
+            "SELECT t.name AS key, COUNT(*) AS count
+             FROM item_tags it
+             JOIN tags t ON t.id = it.tag_id
+             JOIN entry_items i ON i.id = it.entry_item_id
+             JOIN entries e ON e.id = i.entry_id
+             WHERE (? IS NULL OR e.timestamp >= ?) AND (? IS NULL OR e.timestamp < ?)
+             GROUP BY key ORDER BY count DESC, key LIMIT ?",
+            from, to, Some(limit),
+        ).await
+    }
+
+    pub async fn top_people(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>, limit: i64) -> Result<Vec<CountPoint>, sqlx::Error> {
+        self.count_points(
+            "SELECT p.name AS key, COUNT(*) AS count
+             FROM item_people ip
+             JOIN people p ON p.id = ip.person_id
+             JOIN entry_items i ON i.id = ip.entry_item_id
+             JOIN entries e ON e.id = i.entry_id
+             WHERE (? IS NULL OR e.timestamp >= ?) AND (? IS NULL OR e.timestamp < ?)
+             GROUP BY key ORDER BY count DESC, key LIMIT ?",
+            from, to, Some(limit),
+        ).await
+    }
+
+    pub async fn top_projects(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>, limit: i64) -> Result<Vec<CountPoint>, sqlx::Error> {
+        self.count_points(
+            "SELECT i.project AS key, COUNT(*) AS count
+             FROM entry_items i JOIN entries e ON e.id = i.entry_id
+             WHERE (? IS NULL OR e.timestamp >= ?) AND (? IS NULL OR e.timestamp < ?)
+               AND i.project IS NOT NULL AND i.project != ''
+             GROUP BY key ORDER BY count DESC, key LIMIT ?",
+            from, to, Some(limit),
+        ).await
+    }
 
-        // Return updated project
-        let row = sqlx::query("SELECT id, name, description, color, created_at, updated_at FROM projects WHERE id = ?")
-            .bind(id)
-            .fetch_one(&self.pool)
+    /// Item counts as `(weekday, hour, count)` in local time, with SQLite's
+    /// Sunday-first weekday numbering.
+    pub async fn count_items_by_weekday_hour(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<(i64, i64, i64)>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT CAST(strftime('%w', e.timestamp, 'localtime') AS INTEGER) AS weekday,
+                    CAST(strftime('%H', e.timestamp, 'localtime') AS INTEGER) AS hour,
+                    COUNT(*) AS count
+             FROM entry_items i JOIN entries e ON e.id = i.entry_id
+             WHERE (? IS NULL OR e.timestamp >= ?) AND (? IS NULL OR e.timestamp < ?)
+             GROUP BY weekday, hour"
+        )
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(to.map(|t| t.to_rfc3339()))
+        .bind(to.map(|t| t.to_rfc3339()))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(|row| (row.get("weekday"), row.get("hour"), row.get("count"))).collect())
+    }
+
+    /// Every local day with at least one entry, oldest first.
+    pub async fn get_active_days(&self) -> Result<Vec<NaiveDate>, sqlx::Error> {
+        let rows = sqlx::query("SELECT DISTINCT date(timestamp, 'localtime') AS day FROM entries ORDER BY day")
+            .fetch_all(&self.pool)
             .await?;
 
-        let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-            .with_timezone(&Utc);
-        let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-            .with_timezone(&Utc);
+        let mut days = Vec::new();
+        for row in rows {
+            let day = NaiveDate::parse_from_str(&row.get::<String, _>("day"), "%Y-%m-%d")
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+            days.push(day);
+        }
 
-        Ok(Project {
-            id: row.get("id"),
-            name: row.get("name"),
-            description: row.get("description"),
-            color: row.get("color"),
-            created_at,
-            updated_at,
-        })
+        Ok(days)
     }
 
-    pub async fn delete_project(&self, id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM projects WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+    /// Items per local day, optionally only of one type, oldest first.
+    pub async fn count_items_per_day(&self, item_type: Option<&str>) -> Result<Vec<(NaiveDate, i64)>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT date(e.timestamp, 'localtime') AS day, COUNT(*) AS count
+             FROM entry_items i JOIN entries e ON e.id = i.entry_id
+             WHERE (? IS NULL OR i.item_type = ?)
+             GROUP BY day ORDER BY day"
+        )
+        .bind(item_type)
+        .bind(item_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut counts = Vec::new();
+        for row in rows {
+            let day = NaiveDate::parse_from_str(&row.get::<String, _>("day"), "%Y-%m-%d")
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+            counts.push((day, row.get("count")));
+        }
+
+        Ok(counts)
     }
 
-    // Tag management methods
-    pub async fn create_tag(&self, name: &str, description: Option<&str>, color: Option<&str>, category: Option<&str>) -> Result<Tag, sqlx::Error> {
+    // Goal methods
+    pub async fn create_goal(
+        &self,
+        name: &str,
+        item_type: Option<&str>,
+        target_count: i64,
+        period: &str,
+        sprint_days: Option<i64>,
+        sprint_start: Option<NaiveDate>,
+    ) -> Result<Goal, sqlx::Error> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now();
-        let color = color.unwrap_or("#6c757d");
-        
+
         sqlx::query(
-            "INSERT INTO tags (id, name, description, color, category, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO goals (id, name, item_type, target_count, period, sprint_days, sprint_start, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&id)
         .bind(name)
-        .bind(description)
-        .bind(color)
-        .bind(category)
+        .bind(item_type)
+        .bind(target_count)
+        .bind(period)
+        .bind(sprint_days)
+        .bind(sprint_start.map(|d| d.to_string()))
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(Tag {
+        Ok(Goal {
             id,
             name: name.to_string(),
-            description: description.map(|s| s.to_string()),
-            color: color.to_string(),
-            category: category.map(|s| s.to_string()),
+            item_type: item_type.map(|s| s.to_string()),
+            target_count,
+            period: period.to_string(),
+            sprint_days,
+            sprint_start,
             created_at: now,
             updated_at: now,
         })
     }
 
-    pub async fn get_all_tags(&self) -> Result<Vec<Tag>, sqlx::Error> {
-        let rows = sqlx::query("SELECT id, name, description, color, category, created_at, updated_at FROM tags ORDER BY name")
+    pub async fn get_goals(&self) -> Result<Vec<Goal>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, name, item_type, target_count, period, sprint_days, sprint_start, created_at, updated_at FROM goals ORDER BY created_at")
             .fetch_all(&self.pool)
             .await?;
 
-        let mut tags = Vec::new();
+        let mut goals = Vec::new();
         for row in rows {
-            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
-            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
-
-            tags.push(Tag {
-                id: row.get("id"),
-                name: row.get("name"),
-                description: row.get("description"),
-                color: row.get("color"),
-                category: row.get("category"),
-                created_at,
-                updated_at,
-            });
+            goals.push(Self::goal_from_row(&row)?);
         }
 
-        Ok(tags)
+        Ok(goals)
     }
 
-    pub async fn update_tag(&self, id: &str, name: Option<&str>, description: Option<&str>, color: Option<&str>, category: Option<&str>) -> Result<Tag, sqlx::Error> {
+    pub async fn get_goal(&self, id: &str) -> Result<Option<Goal>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, name, item_type, target_count, period, sprint_days, sprint_start, created_at, updated_at FROM goals WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| Self::goal_from_row(&row)).transpose()
+    }
+
+    pub async fn update_goal(&self, goal: &Goal) -> Result<Goal, sqlx::Error> {
         let now = Utc::now();
-        
-        // Build dynamic update query
-        let mut query_parts = vec!["updated_at = ?".to_string()];
-        
-        if let Some(_) = name {
-            query_parts.push("name = ?".to_string());
-        }
-        if let Some(_) = description {
-            query_parts.push("description = ?".to_string());
-        }
-        if let Some(_) = color {
-            query_parts.push("color = ?".to_string());
-        }
-        if let Some(_) = category {
-            query_parts.push("category = ?".to_string());
-        }
-        
-        let query_str = format!("UPDATE tags SET {} WHERE id = ?", query_parts.join(", "));
-        let mut query = sqlx::query(&query_str);
-        
-        query = query.bind(now.to_rfc3339());
-        
-        if let Some(name) = name {
-            query = query.bind(name);
-        }
-        if let Some(description) = description {
-            query = query.bind(description);
-        }
-        if let Some(color) = color {
-            query = query.bind(color);
-        }
-        if let Some(category) = category {
-            query = query.bind(category);
-        }
-        
-        query = query.bind(id);
-        query.execute(&self.pool).await?;
+        sqlx::query(
+            "UPDATE goals SET name = ?, item_type = ?, target_count = ?, period = ?, sprint_days = ?, sprint_start = ?, updated_at = ? WHERE id = ?"
+        )
+        .bind(&goal.name)
+        .bind(&goal.item_type)
+        .bind(goal.target_count)
+        .bind(&goal.period)
+        .bind(goal.sprint_days)
+        .bind(goal.sprint_start.map(|d| d.to_string()))
+        .bind(now.to_rfc3339())
+        .bind(&goal.id)
+        .execute(&self.pool)
+        .await?;
 
-        // Return updated tag
-        let row = sqlx::query("SELECT id, name, description, color, category, created_at, updated_at FROM tags WHERE id = ?")
+        Ok(Goal {
+            updated_at: now,
+            ..goal.clone()
+        })
+    }
+
+    pub async fn delete_goal(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM goals WHERE id = ?")
             .bind(id)
-            .fetch_one(&self.pool)
+            .execute(&self.pool)
             .await?;
+        Ok(())
+    }
 
-        let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-            .with_timezone(&Utc);
-        let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-            .with_timezone(&Utc);
+    fn goal_from_row(row: &SqliteRow) -> Result<Goal, sqlx::Error> {
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
+        let sprint_start = row.get::<Option<String>, _>("sprint_start")
+            .map(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").map_err(|e| sqlx::Error::Decode(Box::new(e))))
+            .transpose()?;
 
-        Ok(Tag {
+        Ok(Goal {
             id: row.get("id"),
             name: row.get("name"),
-            description: row.get("description"),
-            color: row.get("color"),
-            category: row.get("category"),
-            created_at,
-            updated_at,
+            item_type: row.get("item_type"),
+            target_count: row.get("target_count"),
+            period: row.get("period"),
+            sprint_days: row.get("sprint_days"),
+            sprint_start,
+            created_at: parse(row.get("created_at"))?,
+            updated_at: parse(row.get("updated_at"))?,
         })
     }
 
-    pub async fn delete_tag(&self, id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM tags WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
+    // Reminder methods
+    pub async fn get_reminder_states(&self) -> Result<Vec<ReminderState>, sqlx::Error> {
+        let rows = sqlx::query("SELECT target_kind, target_id, due_date, sent_offsets, snoozed_until, dismissed, updated_at FROM reminder_states")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
+
+        let mut states = Vec::new();
+        for row in rows {
+            states.push(ReminderState {
+                target_kind: row.get("target_kind"),
+                target_id: row.get("target_id"),
+                due_date: parse(row.get("due_date"))?,
+                sent_offsets: serde_json::from_str(&row.get::<String, _>("sent_offsets"))
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                snoozed_until: row.get::<Option<String>, _>("snoozed_until").map(parse).transpose()?,
+                dismissed: row.get("dismissed"),
+                updated_at: parse(row.get("updated_at"))?,
+            });
+        }
+
+        Ok(states)
+    }
+
+    pub async fn save_reminder_state(&self, state: &ReminderState) -> Result<(), sqlx::Error> {
+        let sent_offsets = serde_json::to_string(&state.sent_offsets)
+            .map_err(|e| sqlx::Error::Protocol(e.to_string()))?;
+
+        sqlx::query(
+            "INSERT OR REPLACE INTO reminder_states (target_kind, target_id, due_date, sent_offsets, snoozed_until, dismissed, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&state.target_kind)
+        .bind(&state.target_id)
+        .bind(state.due_date.to_rfc3339())
+        .bind(sent_offsets)
+        .bind(state.snoozed_until.map(|t| t.to_rfc3339()))
+        .bind(state.dismissed)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    // Decision register methods
+    pub async fn get_decision_records(&self) -> Result<Vec<DecisionRecord>, sqlx::Error> {
+        let rows = sqlx::query("SELECT entry_item_id, context, status, supersedes_id, updated_at FROM decision_records")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(DecisionRecord {
+                entry_item_id: row.get("entry_item_id"),
+                context: row.get("context"),
+                status: row.get("status"),
+                supersedes_id: row.get("supersedes_id"),
+                updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+                    .with_timezone(&Utc),
+            });
+        }
+
+        Ok(records)
+    }
+
+    /// Save decision records together, so a decision and the one it
+    /// supersedes change at once.
+    pub async fn save_decision_records(&self, records: &[DecisionRecord]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        for record in records {
+            sqlx::query(
+                "INSERT OR REPLACE INTO decision_records (entry_item_id, context, status, supersedes_id, updated_at) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(&record.entry_item_id)
+            .bind(&record.context)
+            .bind(&record.status)
+            .bind(&record.supersedes_id)
+            .bind(record.updated_at.to_rfc3339())
+            .execute(&mut *tx)
             .await?;
+        }
+        tx.commit().await?;
         Ok(())
     }
 
-    // Meeting management methods
-    pub async fn create_meeting(
+    // Waiting-for methods
+    /// Mark an item as waiting on a person, reopening it if it was resolved.
+    pub async fn set_waiting_for(
         &self,
-        title: &str,
-        description: Option<&str>,
-        start_time: Option<DateTime<Utc>>,
-        end_time: Option<DateTime<Utc>>,
-        location: Option<&str>,
-        meeting_type: Option<&str>,
-    ) -> Result<Meeting, sqlx::Error> {
+        entry_item_id: &str,
+        person_id: &str,
+        expected_by: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO waiting_for (entry_item_id, person_id, expected_by, resolved_at, created_at, updated_at) VALUES (?, ?, ?, NULL, ?, ?)
+             ON CONFLICT (entry_item_id) DO UPDATE SET person_id = excluded.person_id, expected_by = excluded.expected_by, resolved_at = NULL, updated_at = excluded.updated_at"
+        )
+        .bind(entry_item_id)
+        .bind(person_id)
+        .bind(expected_by.map(|t| t.to_rfc3339()))
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Set or clear when the wait ended. Returns whether the item was waiting.
+    pub async fn resolve_waiting_for(&self, entry_item_id: &str, resolved_at: Option<DateTime<Utc>>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE waiting_for SET resolved_at = ?, updated_at = ? WHERE entry_item_id = ?")
+            .bind(resolved_at.map(|t| t.to_rfc3339()))
+            .bind(Utc::now().to_rfc3339())
+            .bind(entry_item_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_waiting_for(&self, entry_item_id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM waiting_for WHERE entry_item_id = ?")
+            .bind(entry_item_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Waiting-for records with their item and the entry's timestamp, soonest
+    /// expected first.
+    pub async fn get_waiting_for(&self, include_resolved: bool) -> Result<Vec<(WaitingFor, EntryItem, DateTime<Utc>)>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT w.entry_item_id, w.person_id, p.name AS person_name, w.expected_by, w.resolved_at, w.created_at AS waiting_created_at, w.updated_at AS waiting_updated_at,
+                    i.id, i.entry_id, i.item_type, i.content, i.project, i.position, i.created_at, i.updated_at, e.timestamp
+             FROM waiting_for w
+             JOIN people p ON p.id = w.person_id
+             JOIN entry_items i ON i.id = w.entry_item_id
+             JOIN entries e ON e.id = i.entry_id
+             WHERE ? OR w.resolved_at IS NULL
+             ORDER BY w.expected_by IS NULL, w.expected_by, e.timestamp, i.position"
+        )
+        .bind(include_resolved)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
+
+        let mut waiting = Vec::new();
+        for row in rows {
+            let record = WaitingFor {
+                entry_item_id: row.get("entry_item_id"),
+                person_id: row.get("person_id"),
+                person_name: row.get("person_name"),
+                expected_by: row.get::<Option<String>, _>("expected_by").map(parse).transpose()?,
+                resolved_at: row.get::<Option<String>, _>("resolved_at").map(parse).transpose()?,
+                created_at: parse(row.get("waiting_created_at"))?,
+                updated_at: parse(row.get("waiting_updated_at"))?,
+            };
+            let item = EntryItem {
+                id: row.get("id"),
+                entry_id: row.get("entry_id"),
+                item_type: row.get("item_type"),
+                content: row.get("content"),
+                project: row.get("project"),
+                position: row.get("position"),
+                created_at: parse(row.get("created_at"))?,
+                updated_at: parse(row.get("updated_at"))?,
+            };
+            waiting.push((record, item, parse(row.get("timestamp"))?));
+        }
+
+        Ok(waiting)
+    }
+
+    // Checklist methods
+    pub async fn get_item_checklist(&self, entry_item_id: &str) -> Result<Vec<ChecklistRow>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, entry_item_id, position, text, done, assignee, created_at, updated_at FROM checklist_rows WHERE entry_item_id = ? ORDER BY position, created_at")
+            .bind(entry_item_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(Self::checklist_row_from_row).collect()
+    }
+
+    pub async fn get_checklist_row(&self, id: &str) -> Result<Option<ChecklistRow>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, entry_item_id, position, text, done, assignee, created_at, updated_at FROM checklist_rows WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| Self::checklist_row_from_row(&row)).transpose()
+    }
+
+    /// Append a row to the end of an item's checklist.
+    pub async fn add_checklist_row(&self, entry_item_id: &str, text: &str, assignee: Option<&str>) -> Result<ChecklistRow, sqlx::Error> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now();
-        let meeting_type = meeting_type.unwrap_or("meeting");
 
-        sqlx::query(
-            "INSERT INTO meetings (id, title, description, start_time, end_time, location, meeting_type, status, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-        )
-        .bind(&id)
-        .bind(title)
-        .bind(description)
-        .bind(start_time.map(|t| t.to_rfc3339()))
-        .bind(end_time.map(|t| t.to_rfc3339()))
-        .bind(location)
-        .bind(meeting_type)
-        .bind("scheduled")
+        let position: i64 = sqlx::query("SELECT COALESCE(MAX(position), -1) + 1 AS next FROM checklist_rows WHERE entry_item_id = ?")
+            .bind(entry_item_id)
+            .fetch_one(&self.pool)
+            .await?
+            .get("next");
+
+        sqlx::query(
+            "INSERT INTO checklist_rows (id, entry_item_id, position, text, done, assignee, created_at, updated_at) VALUES (?, ?, ?, ?, 0, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(entry_item_id)
+        .bind(position)
+        .bind(text)
+        .bind(assignee)
         .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(Meeting {
+        Ok(ChecklistRow {
             id,
-            title: title.to_string(),
-            description: description.map(|s| s.to_string()),
-            start_time,
-            end_time,
-            location: location.map(|s| s.to_string()),
-            meeting_type: meeting_type.to_string(),
-            status: "scheduled".to_string(),
+            entry_item_id: entry_item_id.to_string(),
+            position,
+            text: text.to_string(),
+            done: false,
+            assignee: assignee.map(|s| s.to_string()),
             created_at: now,
             updated_at: now,
         })
     }
 
-    pub async fn get_all_meetings(&self) -> Result<Vec<Meeting>, sqlx::Error> {
-        let rows = sqlx::query("SELECT id, title, description, start_time, end_time, location, meeting_type, status, created_at, updated_at FROM meetings ORDER BY start_time DESC")
-            .fetch_all(&self.pool)
+    pub async fn set_checklist_row_done(&self, id: &str, done: bool) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE checklist_rows SET done = ?, updated_at = ? WHERE id = ?")
+            .bind(done)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
             .await?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        let mut meetings = Vec::new();
-        for row in rows {
-            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
-            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
+    /// Number the rows of an item in the order given, in one transaction.
+    pub async fn reorder_checklist(&self, entry_item_id: &str, ordered_ids: &[String]) -> Result<(), sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        for (position, id) in ordered_ids.iter().enumerate() {
+            sqlx::query("UPDATE checklist_rows SET position = ?, updated_at = ? WHERE id = ? AND entry_item_id = ?")
+                .bind(position as i64)
+                .bind(&now)
+                .bind(id)
+                .bind(entry_item_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
 
-            let start_time = row.get::<Option<String>, _>("start_time")
-                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
-            let end_time = row.get::<Option<String>, _>("end_time")
-                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
+    pub async fn delete_checklist_row(&self, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM checklist_rows WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
 
-            meetings.push(Meeting {
-                id: row.get("id"),
-                title: row.get("title"),
-                description: row.get("description"),
-                start_time,
-                end_time,
-                location: row.get("location"),
-                meeting_type: row.get("meeting_type"),
-                status: row.get("status"),
-                created_at,
-                updated_at,
-            });
-        }
+    fn checklist_row_from_row(row: &SqliteRow) -> Result<ChecklistRow, sqlx::Error> {
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
 
-        Ok(meetings)
+        Ok(ChecklistRow {
+            id: row.get("id"),
+            entry_item_id: row.get("entry_item_id"),
+            position: row.get("position"),
+            text: row.get("text"),
+            done: row.get("done"),
+            assignee: row.get("assignee"),
+            created_at: parse(row.get("created_at"))?,
+            updated_at: parse(row.get("updated_at"))?,
+        })
     }
 
-    pub async fn add_meeting_attendee(
-        &self,
-        meeting_id: &str,
-        name: &str,
-        email: Option<&str>,
-        role: Option<&str>,
-    ) -> Result<MeetingAttendee, sqlx::Error> {
+    // Custom field methods
+    pub async fn create_custom_field(&self, name: &str, field_type: &str, options: &[String]) -> Result<CustomField, sqlx::Error> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now();
-        let role = role.unwrap_or("attendee");
+        let options_json = serde_json::to_string(options)
+            .map_err(|e| sqlx::Error::Protocol(e.to_string()))?;
 
         sqlx::query(
-            "INSERT INTO meeting_attendees (id, meeting_id, name, email, role, status, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO custom_fields (id, name, field_type, options, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)"
         )
         .bind(&id)
-        .bind(meeting_id)
         .bind(name)
-        .bind(email)
-        .bind(role)
-        .bind("invited")
+        .bind(field_type)
+        .bind(options_json)
+        .bind(now.to_rfc3339())
         .bind(now.to_rfc3339())
         .execute(&self.pool)
         .await?;
 
-        Ok(MeetingAttendee {
+        Ok(CustomField {
             id,
-            meeting_id: meeting_id.to_string(),
             name: name.to_string(),
-            email: email.map(|s| s.to_string()),
-            role: role.to_string(),
-            status: "invited".to_string(),
+            field_type: field_type.to_string(),
+            options: options.to_vec(),
             created_at: now,
+            updated_at: now,
         })
     }
 
-    pub async fn get_meeting_attendees(&self, meeting_id: &str) -> Result<Vec<MeetingAttendee>, sqlx::Error> {
-        let rows = sqlx::query("SELECT id, meeting_id, name, email, role, status, created_at FROM meeting_attendees WHERE meeting_id = ? ORDER BY name")
-            .bind(meeting_id)
+    pub async fn get_custom_fields(&self) -> Result<Vec<CustomField>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, name, field_type, options, created_at, updated_at FROM custom_fields ORDER BY name")
             .fetch_all(&self.pool)
             .await?;
 
-        let mut attendees = Vec::new();
-        for row in rows {
-            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
+        rows.iter().map(Self::custom_field_from_row).collect()
+    }
 
-            attendees.push(MeetingAttendee {
-                id: row.get("id"),
-                meeting_id: row.get("meeting_id"),
-                name: row.get("name"),
-                email: row.get("email"),
-                role: row.get("role"),
-                status: row.get("status"),
-                created_at,
-            });
-        }
+    pub async fn get_custom_field(&self, id: &str) -> Result<Option<CustomField>, sqlx::Error> {
+        let row = sqlx::query("SELECT id, name, field_type, options, created_at, updated_at FROM custom_fields WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
 
-        Ok(attendees)
+        row.map(|row| Self::custom_field_from_row(&row)).transpose()
     }
 
-    pub async fn create_meeting_action(
-        &self,
-        meeting_id: &str,
-        title: &str,
-        description: Option<&str>,
-        assignee: Option<&str>,
-        due_date: Option<DateTime<Utc>>,
-        priority: Option<&str>,
-    ) -> Result<MeetingAction, sqlx::Error> {
-        let id = Uuid::new_v4().to_string();
+    /// Save a field's name and options. Stored values follow a change in the
+    /// case of their option.
+    pub async fn update_custom_field(&self, field: &CustomField) -> Result<CustomField, sqlx::Error> {
         let now = Utc::now();
-        let priority = priority.unwrap_or("medium");
+        let options_json = serde_json::to_string(&field.options)
+            .map_err(|e| sqlx::Error::Protocol(e.to_string()))?;
 
-        sqlx::query(
-            "INSERT INTO meeting_actions (id, meeting_id, title, description, assignee, due_date, status, priority, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
-        )
-        .bind(&id)
-        .bind(meeting_id)
-        .bind(title)
-        .bind(description)
-        .bind(assignee)
-        .bind(due_date.map(|t| t.to_rfc3339()))
-        .bind("open")
-        .bind(priority)
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
-        .execute(&self.pool)
-        .await?;
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("UPDATE custom_fields SET name = ?, options = ?, updated_at = ? WHERE id = ?")
+            .bind(&field.name)
+            .bind(options_json)
+            .bind(now.to_rfc3339())
+            .bind(&field.id)
+            .execute(&mut *tx)
+            .await?;
+        for option in &field.options {
+            sqlx::query("UPDATE item_field_values SET value = ? WHERE field_id = ? AND value = ? COLLATE NOCASE AND value <> ?")
+                .bind(option)
+                .bind(&field.id)
+                .bind(option)
+                .bind(option)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
 
-        Ok(MeetingAction {
-            id,
-            meeting_id: meeting_id.to_string(),
-            entry_item_id: None,
-            title: title.to_string(),
-            description: description.map(|s| s.to_string()),
-            assignee: assignee.map(|s| s.to_string()),
-            due_date,
-            status: "open".to_string(),
-            priority: priority.to_string(),
-            created_at: now,
+        Ok(CustomField {
             updated_at: now,
+            ..field.clone()
         })
     }
 
-    pub async fn get_meeting_actions(&self, meeting_id: &str) -> Result<Vec<MeetingAction>, sqlx::Error> {
-        let rows = sqlx::query("SELECT id, meeting_id, entry_item_id, title, description, assignee, due_date, status, priority, created_at, updated_at FROM meeting_actions WHERE meeting_id = ? ORDER BY created_at DESC")
-            .bind(meeting_id)
+    pub async fn delete_custom_field(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM custom_fields WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Distinct values stored for a field.
+    pub async fn get_custom_field_values_in_use(&self, field_id: &str) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT DISTINCT value FROM item_field_values WHERE field_id = ?")
+            .bind(field_id)
             .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn get_item_field_values(&self, entry_item_id: &str) -> Result<Vec<ItemFieldValue>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT f.id, f.name, f.field_type, v.value FROM item_field_values v
+             JOIN custom_fields f ON f.id = v.field_id
+             WHERE v.entry_item_id = ?
+             ORDER BY f.name"
+        )
+        .bind(entry_item_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter()
+            .map(|row| ItemFieldValue {
+                field_id: row.get("id"),
+                name: row.get("name"),
+                field_type: row.get("field_type"),
+                value: row.get("value"),
+            })
+            .collect())
+    }
+
+    /// Replace all of an item's field values.
+    pub async fn set_item_field_values(&self, entry_item_id: &str, values: &[(String, String)]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        Self::set_item_field_values_in(&mut tx, entry_item_id, values).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn set_item_field_values_in(conn: &mut SqliteConnection, entry_item_id: &str, values: &[(String, String)]) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM item_field_values WHERE entry_item_id = ?")
+            .bind(entry_item_id)
+            .execute(&mut *conn)
             .await?;
+        for (field_id, value) in values {
+            sqlx::query("INSERT INTO item_field_values (entry_item_id, field_id, value) VALUES (?, ?, ?)")
+                .bind(entry_item_id)
+                .bind(field_id)
+                .bind(value)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Ok(())
+    }
 
-        let mut actions = Vec::new();
-        for row in rows {
-            let created_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
-            let updated_at = DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
-                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
-                .with_timezone(&Utc);
+    fn custom_field_from_row(row: &SqliteRow) -> Result<CustomField, sqlx::Error> {
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
+
+        Ok(CustomField {
+            id: row.get("id"),
+            name: row.get("name"),
+            field_type: row.get("field_type"),
+            options: serde_json::from_str(&row.get::<String, _>("options"))
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            created_at: parse(row.get("created_at"))?,
+            updated_at: parse(row.get("updated_at"))?,
+        })
+    }
 
-            let due_date = row.get::<Option<String>, _>("due_date")
-                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
+    /// Apply `ops` to each item in one transaction, copying every item into
+    /// an undo snapshot before changing it. Returns the batch id, `None` when
+    /// no item could be changed, and one result per item.
+    pub async fn bulk_update_items(
+        &self,
+        item_ids: &[String],
+        ops: &ResolvedOps,
+    ) -> Result<(Option<String>, Vec<BulkItemResult>), sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        let mut snapshots = Vec::new();
+        let mut results = Vec::new();
 
-            actions.push(MeetingAction {
-                id: row.get("id"),
-                meeting_id: row.get("meeting_id"),
-                entry_item_id: row.get("entry_item_id"),
-                title: row.get("title"),
-                description: row.get("description"),
-                assignee: row.get("assignee"),
-                due_date,
-                status: row.get("status"),
-                priority: row.get("priority"),
-                created_at,
-                updated_at,
-            });
+        let mut add_tag_ids = Vec::new();
+        for name in &ops.add_tag_names {
+            add_tag_ids.push(Self::get_or_create_tag_in(&mut tx, name).await?.id);
+        }
+        let mut add_person_ids = Vec::new();
+        for name in &ops.add_person_names {
+            add_person_ids.push(Self::get_or_create_person_in(&mut tx, name).await?.id);
+        }
+
+        for item_id in item_ids {
+            let Some(snapshot) = Self::snapshot_item(&mut tx, item_id).await? else {
+                results.push(BulkItemResult::failed(item_id, "Item not found"));
+                continue;
+            };
+            snapshots.push(snapshot);
+
+            if ops.delete {
+                sqlx::query("DELETE FROM entry_items WHERE id = ?")
+                    .bind(item_id)
+                    .execute(&mut *tx)
+                    .await?;
+                results.push(BulkItemResult::ok(item_id));
+                continue;
+            }
+
+            if let Some(item_type) = &ops.set_type {
+                sqlx::query("UPDATE entry_items SET item_type = ? WHERE id = ?")
+                    .bind(item_type)
+                    .bind(item_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            if let Some(project) = &ops.set_project {
+                sqlx::query("UPDATE entry_items SET project = ? WHERE id = ?")
+                    .bind(project)
+                    .bind(item_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            for tag_id in &add_tag_ids {
+                sqlx::query("INSERT OR IGNORE INTO item_tags (entry_item_id, tag_id) VALUES (?, ?)")
+                    .bind(item_id)
+                    .bind(tag_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            for name in &ops.remove_tag_names {
+                sqlx::query("DELETE FROM item_tags WHERE entry_item_id = ? AND tag_id IN (SELECT id FROM tags WHERE name = ?)")
+                    .bind(item_id)
+                    .bind(name)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            for person_id in &add_person_ids {
+                sqlx::query("INSERT OR IGNORE INTO item_people (entry_item_id, person_id) VALUES (?, ?)")
+                    .bind(item_id)
+                    .bind(person_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            sqlx::query("UPDATE entry_items SET updated_at = ? WHERE id = ?")
+                .bind(&now)
+                .bind(item_id)
+                .execute(&mut *tx)
+                .await?;
+            results.push(BulkItemResult::ok(item_id));
+        }
+
+        if let Some(target_entry_id) = &ops.move_to_entry {
+            let moved: Vec<String> = snapshots.iter().map(|s| s.item_id.clone()).collect();
+            Self::move_items_in(&mut tx, &moved, target_entry_id).await?;
         }
+        // Entries left without items go, as when moving; undo brings them back
+        if ops.delete {
+            let mut entry_ids: Vec<String> = snapshots.iter()
+                .filter_map(|s| s.entry.iter().find(|(column, _)| column == "id"))
+                .filter_map(|(_, id)| id.as_str().map(str::to_string))
+                .collect();
+            entry_ids.sort();
+            entry_ids.dedup();
+            for entry_id in &entry_ids {
+                sqlx::query("DELETE FROM entries WHERE id = ? AND NOT EXISTS (SELECT 1 FROM entry_items WHERE entry_id = ?)")
+                    .bind(entry_id)
+                    .bind(entry_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        // Nothing changed, so tags and people created for the batch go too
+        if snapshots.is_empty() {
+            tx.rollback().await?;
+            return Ok((None, results));
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let snapshot = serde_json::to_string(&snapshots).map_err(|e| sqlx::Error::Protocol(e.to_string()))?;
+        sqlx::query("INSERT INTO bulk_batches (id, snapshot, created_at) VALUES (?, ?, ?)")
+            .bind(&id)
+            .bind(snapshot)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM bulk_batches WHERE id NOT IN (SELECT id FROM bulk_batches ORDER BY created_at DESC LIMIT ?)")
+            .bind(crate::bulk::UNDO_HISTORY)
+            .execute(&mut *tx)
+            .await?;
 
-        Ok(actions)
+        tx.commit().await?;
+        Ok((Some(id), results))
     }
 
-    pub async fn delete_meeting(&self, id: &str) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM meetings WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
+    /// The most recent batch that has not been undone.
+    pub async fn get_latest_bulk_batch_id(&self) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT id FROM bulk_batches WHERE undone_at IS NULL ORDER BY created_at DESC LIMIT 1")
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Put every item of a batch back as it was, in one transaction. Returns
+    /// the restored item ids, or `None` if the batch is unknown or undone.
+    pub async fn undo_bulk_batch(&self, batch_id: &str) -> Result<Option<Vec<String>>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let snapshot: Option<String> = sqlx::query_scalar("SELECT snapshot FROM bulk_batches WHERE id = ? AND undone_at IS NULL")
+            .bind(batch_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some(snapshot) = snapshot else {
+            return Ok(None);
+        };
+        let snapshots: Vec<ItemSnapshot> = serde_json::from_str(&snapshot)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+
+        for snapshot in &snapshots {
+            Self::restore_item(&mut tx, snapshot).await?;
+        }
+        sqlx::query("UPDATE bulk_batches SET undone_at = ? WHERE id = ?")
+            .bind(Utc::now().to_rfc3339())
+            .bind(batch_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(Some(snapshots.into_iter().map(|s| s.item_id).collect()))
+    }
+
+    async fn snapshot_item(conn: &mut SqliteConnection, item_id: &str) -> Result<Option<ItemSnapshot>, sqlx::Error> {
+        let Some(item) = sqlx::query("SELECT * FROM entry_items WHERE id = ?")
+            .bind(item_id)
+            .fetch_optional(&mut *conn)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let entry = sqlx::query("SELECT * FROM entries WHERE id = ?")
+            .bind(item.get::<String, _>("entry_id"))
+            .fetch_one(&mut *conn)
+            .await?;
+
+        let mut dependents = Vec::new();
+        for table in ITEM_DEPENDENT_TABLES {
+            let rows = sqlx::query(&format!("SELECT * FROM {} WHERE entry_item_id = ?", table))
+                .bind(item_id)
+                .fetch_all(&mut *conn)
+                .await?;
+            let rows = rows.iter().map(Self::row_values).collect::<Result<Vec<_>, _>>()?;
+            dependents.push((table.to_string(), rows));
+        }
+        let action_ids = sqlx::query_scalar("SELECT id FROM meeting_actions WHERE entry_item_id = ?")
+            .bind(item_id)
+            .fetch_all(&mut *conn)
+            .await?;
+        let superseded_by = sqlx::query_scalar("SELECT entry_item_id FROM decision_records WHERE supersedes_id = ?")
+            .bind(item_id)
+            .fetch_all(&mut *conn)
             .await?;
+        let time_entry_ids = sqlx::query_scalar("SELECT id FROM time_entries WHERE entry_item_id = ?")
+            .bind(item_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        Ok(Some(ItemSnapshot {
+            item_id: item_id.to_string(),
+            entry: Self::row_values(&entry)?,
+            item: Self::row_values(&item)?,
+            dependents,
+            action_ids,
+            superseded_by,
+            time_entry_ids,
+        }))
+    }
+
+    /// Bring an item back to its snapshot. Tags and people are reset to the
+    /// snapshot; the other dependent rows and cleared links only come back if
+    /// the item had been deleted, so later edits to them are kept.
+    async fn restore_item(conn: &mut SqliteConnection, snapshot: &ItemSnapshot) -> Result<(), sqlx::Error> {
+        let existed = sqlx::query("SELECT 1 FROM entry_items WHERE id = ?")
+            .bind(&snapshot.item_id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .is_some();
+
+        Self::insert_row(conn, "INSERT OR IGNORE", "entries", &snapshot.entry, "").await?;
+        let updates: Vec<String> = snapshot.item.iter()
+            .filter(|(column, _)| column != "id")
+            .map(|(column, _)| format!("{0} = excluded.{0}", column))
+            .collect();
+        let upsert = format!("ON CONFLICT (id) DO UPDATE SET {}", updates.join(", "));
+        Self::insert_row(conn, "INSERT", "entry_items", &snapshot.item, &upsert).await?;
+
+        for (table, rows) in &snapshot.dependents {
+            let links = table == "item_tags" || table == "item_people";
+            if links {
+                sqlx::query(&format!("DELETE FROM {} WHERE entry_item_id = ?", table))
+                    .bind(&snapshot.item_id)
+                    .execute(&mut *conn)
+                    .await?;
+            } else if existed {
+                continue;
+            }
+            for row in rows {
+                Self::insert_row(conn, "INSERT OR IGNORE", table, row, "").await?;
+            }
+        }
+
+        if !existed {
+            for action_id in &snapshot.action_ids {
+                sqlx::query("UPDATE meeting_actions SET entry_item_id = ? WHERE id = ? AND entry_item_id IS NULL")
+                    .bind(&snapshot.item_id)
+                    .bind(action_id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            for time_entry_id in &snapshot.time_entry_ids {
+                sqlx::query("UPDATE time_entries SET entry_item_id = ? WHERE id = ? AND entry_item_id IS NULL")
+                    .bind(&snapshot.item_id)
+                    .bind(time_entry_id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            for decision_id in &snapshot.superseded_by {
+                sqlx::query("UPDATE decision_records SET supersedes_id = ? WHERE entry_item_id = ? AND supersedes_id IS NULL")
+                    .bind(&snapshot.item_id)
+                    .bind(decision_id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn row_values(row: &SqliteRow) -> Result<RowValues, sqlx::Error> {
+        row.columns()
+            .iter()
+            .map(|column| {
+                let index = column.ordinal();
+                let raw = row.try_get_raw(index)?;
+                let value = if raw.is_null() {
+                    serde_json::Value::Null
+                } else {
+                    match raw.type_info().name() {
+                        "INTEGER" => row.try_get::<i64, _>(index)?.into(),
+                        "REAL" => row.try_get::<f64, _>(index)?.into(),
+                        _ => row.try_get::<String, _>(index)?.into(),
+                    }
+                };
+                Ok((column.name().to_string(), value))
+            })
+            .collect()
+    }
+
+    async fn insert_row(
+        conn: &mut SqliteConnection,
+        verb: &str,
+        table: &str,
+        values: &RowValues,
+        suffix: &str,
+    ) -> Result<(), sqlx::Error> {
+        let columns: Vec<&str> = values.iter().map(|(column, _)| column.as_str()).collect();
+        let sql = format!(
+            "{} INTO {} ({}) VALUES ({}) {}",
+            verb,
+            table,
+            columns.join(", "),
+            vec!["?"; columns.len()].join(", "),
+            suffix
+        );
+
+        let mut query = sqlx::query::<sqlx::Sqlite>(&sql);
+        for (_, value) in values {
+            query = Self::bind_json(query, value);
+        }
+        query.execute(&mut *conn).await?;
         Ok(())
     }
+
+    fn bind_json<'q>(
+        query: sqlx::query::Query<'q, sqlx::Sqlite, SqliteArguments<'q>>,
+        value: &'q serde_json::Value,
+    ) -> sqlx::query::Query<'q, sqlx::Sqlite, SqliteArguments<'q>> {
+        match value {
+            serde_json::Value::Null => query.bind(None::<String>),
+            serde_json::Value::Bool(flag) => query.bind(*flag),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => query.bind(i),
+                None => query.bind(n.as_f64()),
+            },
+            serde_json::Value::String(text) => query.bind(text.as_str()),
+            other => query.bind(other.to_string()),
+        }
+    }
+
+    /// How often each value of `kind` was used: per day for entries from
+    /// `since`, and in total as a row without a day. Tags, people and projects
+    /// that were never used have a total of zero.
+    pub async fn get_usage_by_day(&self, kind: SuggestionKind, since: DateTime<Utc>) -> Result<Vec<UsageDay>, sqlx::Error> {
+        // CROSS JOIN keeps SQLite starting from the recent entries instead
+        // of scanning every link
+        let sql = match kind {
+            SuggestionKind::Tag => {
+                "SELECT t.name AS value, NULL AS day, COALESCE(c.uses, 0) AS uses
+                 FROM tags t
+                 LEFT JOIN (SELECT tag_id, COUNT(*) AS uses FROM item_tags GROUP BY tag_id) c ON c.tag_id = t.id
+                 UNION ALL
+                 SELECT t.name, date(e.timestamp), COUNT(*)
+                 FROM entries e
+                 CROSS JOIN entry_items i ON i.entry_id = e.id
+                 CROSS JOIN item_tags it ON it.entry_item_id = i.id
+                 JOIN tags t ON t.id = it.tag_id
+                 WHERE e.timestamp >= ?
+                 GROUP BY t.id, date(e.timestamp)"
+            }
+            SuggestionKind::Person => {
+                "SELECT p.name AS value, NULL AS day, COALESCE(c.uses, 0) AS uses
+                 FROM people p
+                 LEFT JOIN (SELECT person_id, COUNT(*) AS uses FROM item_people GROUP BY person_id) c ON c.person_id = p.id
+                 UNION ALL
+                 SELECT p.name, date(e.timestamp), COUNT(*)
+                 FROM entries e
+                 CROSS JOIN entry_items i ON i.entry_id = e.id
+                 CROSS JOIN item_people ip ON ip.entry_item_id = i.id
+                 JOIN people p ON p.id = ip.person_id
+                 WHERE e.timestamp >= ?
+                 GROUP BY p.id, date(e.timestamp)"
+            }
+            SuggestionKind::Project => {
+                "SELECT project AS value, NULL AS day, COUNT(*) AS uses
+                 FROM entry_items WHERE project IS NOT NULL AND project != ''
+                 GROUP BY project
+                 UNION ALL
+                 SELECT name, NULL, 0 FROM projects
+                 UNION ALL
+                 SELECT i.project, date(e.timestamp), COUNT(*)
+                 FROM entries e CROSS JOIN entry_items i ON i.entry_id = e.id
+                 WHERE e.timestamp >= ? AND i.project IS NOT NULL AND i.project != ''
+                 GROUP BY i.project, date(e.timestamp)"
+            }
+            SuggestionKind::Jira => {
+                "SELECT jira_key AS value, NULL AS day, COUNT(*) AS uses
+                 FROM jira_refs GROUP BY jira_key
+                 UNION ALL
+                 SELECT r.jira_key, date(e.timestamp), COUNT(*)
+                 FROM entries e
+                 CROSS JOIN entry_items i ON i.entry_id = e.id
+                 CROSS JOIN jira_refs r ON r.entry_item_id = i.id
+                 WHERE e.timestamp >= ?
+                 GROUP BY r.jira_key, date(e.timestamp)"
+            }
+        };
+
+        let rows = sqlx::query(sql).bind(since.to_rfc3339()).fetch_all(&self.pool).await?;
+        rows.iter()
+            .map(|row| {
+                let day = row.get::<Option<String>, _>("day")
+                    .map(|day| NaiveDate::parse_from_str(&day, "%Y-%m-%d"))
+                    .transpose()
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                Ok(UsageDay {
+                    value: row.get("value"),
+                    day,
+                    uses: row.get("uses"),
+                })
+            })
+            .collect()
+    }
+
+    /// Column names of `table`, in table order.
+    pub async fn get_table_columns(&self, table: &str) -> Result<Vec<String>, sqlx::Error> {
+        let rows = sqlx::query(&format!("PRAGMA table_info({})", table))
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(|row| row.get("name")).collect())
+    }
+
+    /// Every row of each table, read in one transaction so they agree.
+    pub async fn dump_tables(&self, tables: &[&str]) -> Result<Vec<(String, Vec<RowValues>)>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut dump = Vec::new();
+        for table in tables {
+            let rows = sqlx::query(&format!("SELECT * FROM {} ORDER BY rowid", table))
+                .fetch_all(&mut *tx)
+                .await?;
+            let rows = rows.iter().map(Self::row_values).collect::<Result<Vec<_>, _>>()?;
+            dump.push((table.to_string(), rows));
+        }
+        tx.commit().await?;
+        Ok(dump)
+    }
+
+    /// Change a merged row that would clash with a unique column of an
+    /// existing row: an invoice whose number is taken gets the next free
+    /// number, and a running timer is stopped at its last heartbeat while
+    /// another timer runs. Returns whether the row was changed.
+    async fn fit_merged_row(conn: &mut SqliteConnection, table: &str, row: &mut RowValues) -> Result<bool, sqlx::Error> {
+        let value_of = |row: &RowValues, name: &str| row.iter().find(|(c, _)| c == name).map(|(_, v)| v.clone());
+        let Some(serde_json::Value::String(id)) = value_of(row, "id") else {
+            return Ok(false);
+        };
+
+        match table {
+            "invoices" => {
+                let Some(number) = value_of(row, "number").and_then(|n| n.as_i64()) else {
+                    return Ok(false);
+                };
+                let taken: Option<i64> = sqlx::query_scalar("SELECT 1 FROM invoices WHERE number = ? AND id != ?")
+                    .bind(number)
+                    .bind(&id)
+                    .fetch_optional(&mut *conn)
+                    .await?;
+                if taken.is_none() {
+                    return Ok(false);
+                }
+                let next: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(number), 0) + 1 FROM invoices")
+                    .fetch_one(&mut *conn)
+                    .await?;
+                if let Some((_, value)) = row.iter_mut().find(|(c, _)| c == "number") {
+                    *value = next.into();
+                }
+                Ok(true)
+            }
+            "time_entries" => {
+                if !value_of(row, "end_time").is_some_and(|end| end.is_null()) {
+                    return Ok(false);
+                }
+                let running: Option<String> = sqlx::query_scalar("SELECT id FROM time_entries WHERE end_time IS NULL AND id != ?")
+                    .bind(&id)
+                    .fetch_optional(&mut *conn)
+                    .await?;
+                if running.is_none() {
+                    return Ok(false);
+                }
+                let end = value_of(row, "last_heartbeat")
+                    .filter(|v| !v.is_null())
+                    .or_else(|| value_of(row, "start_time"))
+                    .unwrap_or(serde_json::Value::Null);
+                if let Some((_, value)) = row.iter_mut().find(|(c, _)| c == "end_time") {
+                    *value = end;
+                }
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Write exported rows in one transaction, after emptying every table
+    /// when replacing. Merging updates rows with the same primary key and
+    /// matches rows of `backup::NATURAL_KEYS` tables to existing rows of
+    /// the same name, pointing later rows at those; `fit_merged_row` changes
+    /// rows that would clash otherwise. Foreign keys are checked once
+    /// everything is written. Column names must have been checked against
+    /// the tables.
+    pub async fn load_tables(&self, tables: &[(&str, Vec<RowValues>)], mode: ImportMode) -> Result<Vec<TableImport>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("PRAGMA defer_foreign_keys = ON").execute(&mut *tx).await?;
+
+        if mode == ImportMode::Replace {
+            let cleared = backup::TABLES.iter().rev().chain(backup::CLEARED_ON_REPLACE.iter());
+            for table in cleared {
+                sqlx::query(&format!("DELETE FROM {}", table)).execute(&mut *tx).await?;
+            }
+        }
+
+        // Ids of imported rows matched to existing rows, by table
+        let mut matched_ids: HashMap<(String, String), String> = HashMap::new();
+        let mut summary = Vec::new();
+
+        for (table, rows) in tables {
+            let info = sqlx::query(&format!("PRAGMA table_info({})", table))
+                .fetch_all(&mut *tx)
+                .await?;
+            let mut key_columns: Vec<(i64, String)> = info.iter()
+                .map(|row| (row.get::<i64, _>("pk"), row.get::<String, _>("name")))
+                .filter(|(pk, _)| *pk > 0)
+                .collect();
+            key_columns.sort();
+            let key_columns: Vec<String> = key_columns.into_iter().map(|(_, name)| name).collect();
+
+            let foreign_keys: Vec<(String, String)> = sqlx::query(&format!("PRAGMA foreign_key_list({})", table))
+                .fetch_all(&mut *tx)
+                .await?
+                .iter()
+                .map(|row| (row.get("from"), row.get("table")))
+                .collect();
+            let natural_key = backup::NATURAL_KEYS.iter()
+                .find(|(t, _)| t == table)
+                .map(|(_, column)| *column);
+
+            let mut imported = TableImport { table: table.to_string(), ..Default::default() };
+            for row in rows {
+                let mut row = row.clone();
+                for (column, value) in row.iter_mut() {
+                    let Some((_, parent)) = foreign_keys.iter().find(|(from, _)| from == column) else {
+                        continue;
+                    };
+                    if let serde_json::Value::String(id) = value {
+                        if let Some(existing) = matched_ids.get(&(parent.clone(), id.clone())) {
+                            *id = existing.clone();
+                        }
+                    }
+                }
+
+                if let (ImportMode::Merge, Some(column)) = (mode, natural_key) {
+                    let value_of = |name: &str| row.iter().find(|(c, _)| c == name).and_then(|(_, v)| v.as_str());
+                    if let (Some(id), Some(name)) = (value_of("id"), value_of(column)) {
+                        let existing: Option<String> = sqlx::query_scalar(&format!("SELECT id FROM {} WHERE {} = ? AND id != ?", table, column))
+                            .bind(name)
+                            .bind(id)
+                            .fetch_optional(&mut *tx)
+                            .await?;
+                        if let Some(existing) = existing {
+                            matched_ids.insert((table.to_string(), id.to_string()), existing);
+                            imported.matched += 1;
+                            continue;
+                        }
+                    }
+                }
+
+                if mode == ImportMode::Merge && Self::fit_merged_row(&mut tx, table, &mut row).await? {
+                    imported.adjusted += 1;
+                }
+
+                let suffix = match mode {
+                    ImportMode::Replace => String::new(),
+                    ImportMode::Merge => {
+                        let updates: Vec<String> = row.iter()
+                            .map(|(column, _)| column)
+                            .filter(|column| !key_columns.contains(column))
+                            .map(|column| format!("{0} = excluded.{0}", column))
+                            .collect();
+                        if updates.is_empty() {
+                            format!("ON CONFLICT ({}) DO NOTHING", key_columns.join(", "))
+                        } else {
+                            format!("ON CONFLICT ({}) DO UPDATE SET {}", key_columns.join(", "), updates.join(", "))
+                        }
+                    }
+                };
+                Self::insert_row(&mut tx, "INSERT", table, &row, &suffix).await?;
+                imported.rows += 1;
+            }
+            summary.push(imported);
+        }
+
+        tx.commit().await?;
+        Ok(summary)
+    }
 }
@@ -0,0 +1,334 @@
+// The decision register. Every Decision item is in it; context, status and
+// a link to the decision it supersedes are kept alongside in
+// `decision_records`. Decisions are numbered in the order they were logged,
+// which is also how the ADR export names its files, so numbers stay put as
+// long as decisions are not deleted or back-dated.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::database::{DecisionRecord, EntryItemWithMetadata};
+
+/// Item type of the entries in the register.
+pub const DECISION_TYPE: &str = "Decision";
+
+/// Longest slug used in ADR file names.
+const MAX_SLUG_LEN: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionStatus {
+    Proposed,
+    Accepted,
+    Superseded,
+}
+
+impl DecisionStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DecisionStatus::Proposed => "proposed",
+            DecisionStatus::Accepted => "accepted",
+            DecisionStatus::Superseded => "superseded",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "proposed" => Ok(DecisionStatus::Proposed),
+            "accepted" => Ok(DecisionStatus::Accepted),
+            "superseded" => Ok(DecisionStatus::Superseded),
+            other => Err(format!("Invalid decision status '{}'; use proposed, accepted or superseded", other)),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            DecisionStatus::Proposed => "Proposed",
+            DecisionStatus::Accepted => "Accepted",
+            DecisionStatus::Superseded => "Superseded",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Decision {
+    pub number: usize,
+    pub item_id: String,
+    pub title: String,
+    pub content: String,
+    pub timestamp: DateTime<Utc>,
+    pub project: Option<String>,
+    pub people: Vec<String>,
+    pub tags: Vec<String>,
+    pub context: Option<String>,
+    pub status: DecisionStatus,
+    pub supersedes_id: Option<String>,
+    pub supersedes_number: Option<usize>,
+    /// Numbers of later decisions that supersede this one.
+    pub superseded_by: Vec<usize>,
+}
+
+/// The first line of a decision, which titles its ADR.
+fn title_of(content: &str) -> String {
+    content.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("Untitled decision").to_string()
+}
+
+/// Number every decision in `items` (oldest first) and attach its record.
+/// Decisions without a record are proposed.
+pub fn build_register(items: &[(DateTime<Utc>, EntryItemWithMetadata)], records: Vec<DecisionRecord>) -> Vec<Decision> {
+    let mut records: HashMap<String, DecisionRecord> = records
+        .into_iter()
+        .map(|record| (record.entry_item_id.clone(), record))
+        .collect();
+    let numbers: HashMap<&str, usize> = items
+        .iter()
+        .enumerate()
+        .map(|(index, (_, item))| (item.item.id.as_str(), index + 1))
+        .collect();
+
+    let mut register: Vec<Decision> = items
+        .iter()
+        .enumerate()
+        .map(|(index, (timestamp, item))| {
+            let record = records.remove(&item.item.id);
+            let status = record.as_ref()
+                .and_then(|r| DecisionStatus::parse(&r.status).ok())
+                .unwrap_or(DecisionStatus::Proposed);
+            let supersedes_id = record.as_ref().and_then(|r| r.supersedes_id.clone());
+
+            Decision {
+                number: index + 1,
+                item_id: item.item.id.clone(),
+                title: title_of(&item.item.content),
+                content: item.item.content.clone(),
+                timestamp: *timestamp,
+                project: item.item.project.clone(),
+                people: item.people.iter().map(|p| p.name.clone()).collect(),
+                tags: item.tags.iter().map(|t| t.name.clone()).collect(),
+                context: record.and_then(|r| r.context),
+                status,
+                supersedes_number: supersedes_id.as_deref().and_then(|id| numbers.get(id).copied()),
+                supersedes_id,
+                superseded_by: Vec::new(),
+            }
+        })
+        .collect();
+
+    let links: Vec<(usize, usize)> = register
+        .iter()
+        .filter_map(|d| d.supersedes_number.map(|earlier| (earlier, d.number)))
+        .collect();
+    for (earlier, later) in links {
+        register[earlier - 1].superseded_by.push(later);
+    }
+
+    register
+}
+
+/// The records to save for `record` replacing what is stored for its
+/// decision in `records`. Pointing at a different earlier decision marks
+/// that one superseded, and a decision nothing supersedes any more goes
+/// back to accepted.
+pub fn supersession_changes(records: &HashMap<String, DecisionRecord>, record: DecisionRecord) -> Vec<DecisionRecord> {
+    let previous = records.get(&record.entry_item_id).and_then(|r| r.supersedes_id.clone());
+    let now = record.updated_at;
+    let mut changed = Vec::new();
+
+    if record.supersedes_id != previous {
+        if let Some(earlier) = &record.supersedes_id {
+            let earlier_record = records.get(earlier).cloned().unwrap_or(DecisionRecord {
+                entry_item_id: earlier.clone(),
+                context: None,
+                status: String::new(),
+                supersedes_id: None,
+                updated_at: now,
+            });
+            changed.push(DecisionRecord {
+                status: DecisionStatus::Superseded.as_str().to_string(),
+                updated_at: now,
+                ..earlier_record
+            });
+        }
+        if let Some(previous) = previous {
+            let still_superseded = records.values()
+                .filter(|r| r.entry_item_id != record.entry_item_id)
+                .any(|r| r.supersedes_id.as_ref() == Some(&previous));
+            let previous_record = records.get(&previous)
+                .filter(|r| r.status == DecisionStatus::Superseded.as_str());
+            if let (false, Some(previous_record)) = (still_superseded, previous_record) {
+                changed.push(DecisionRecord {
+                    status: DecisionStatus::Accepted.as_str().to_string(),
+                    updated_at: now,
+                    ..previous_record.clone()
+                });
+            }
+        }
+    }
+
+    changed.insert(0, record);
+    changed
+}
+
+/// Lower-case words joined by dashes, e.g. `use-tera-for-templates`.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        if !slug.is_empty() {
+            if slug.len() + 1 + word.len() > MAX_SLUG_LEN {
+                break;
+            }
+            slug.push('-');
+        }
+        slug.extend(word.chars().flat_map(char::to_lowercase));
+    }
+    if slug.is_empty() {
+        slug.push_str("decision");
+    }
+    slug.chars().take(MAX_SLUG_LEN).collect()
+}
+
+pub fn adr_file_name(decision: &Decision) -> String {
+    format!("{:04}-{}.md", decision.number, slugify(&decision.title))
+}
+
+fn adr_link(register: &[Decision], number: usize) -> String {
+    match register.get(number - 1) {
+        Some(d) => format!("[{}. {}]({})", d.number, d.title, adr_file_name(d)),
+        None => format!("ADR {}", number),
+    }
+}
+
+/// One decision as an ADR in the usual Status / Context / Decision layout.
+pub fn render_adr(decision: &Decision, register: &[Decision]) -> String {
+    let mut md = format!("# {}. {}\n\n", decision.number, decision.title);
+    md.push_str(&format!("Date: {}\n", decision.timestamp.with_timezone(&Local).format("%Y-%m-%d")));
+    if let Some(project) = &decision.project {
+        md.push_str(&format!("\nProject: {}\n", project));
+    }
+    if !decision.people.is_empty() {
+        md.push_str(&format!("\nPeople: {}\n", decision.people.join(", ")));
+    }
+    if !decision.tags.is_empty() {
+        md.push_str(&format!("\nTags: {}\n", decision.tags.join(", ")));
+    }
+
+    md.push_str(&format!("\n## Status\n\n{}\n", decision.status.label()));
+    if let Some(number) = decision.supersedes_number {
+        md.push_str(&format!("\nSupersedes {}\n", adr_link(register, number)));
+    }
+    for number in &decision.superseded_by {
+        md.push_str(&format!("\nSuperseded by {}\n", adr_link(register, *number)));
+    }
+
+    md.push_str("\n## Context\n\n");
+    match decision.context.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        Some(context) => md.push_str(&format!("{}\n", context)),
+        None => md.push_str("_No context recorded._\n"),
+    }
+
+    md.push_str(&format!("\n## Decision\n\n{}\n", decision.content.trim()));
+    md
+}
+
+/// Write one ADR per decision into `dir`, overwriting files of the same name.
+pub fn write_adrs(dir: &Path, register: &[Decision]) -> Result<Vec<PathBuf>, String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let mut written = Vec::new();
+    for decision in register {
+        let path = dir.join(adr_file_name(decision));
+        std::fs::write(&path, render_adr(decision, register))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn record(id: &str, status: DecisionStatus, supersedes_id: Option<&str>) -> DecisionRecord {
+        DecisionRecord {
+            entry_item_id: id.to_string(),
+            context: Some(format!("context of {}", id)),
+            status: status.as_str().to_string(),
+            supersedes_id: supersedes_id.map(str::to_string),
+            updated_at: Utc.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap(),
+        }
+    }
+
+    fn apply(records: &mut HashMap<String, DecisionRecord>, changed: Vec<DecisionRecord>) {
+        for record in changed {
+            records.insert(record.entry_item_id.clone(), record);
+        }
+    }
+
+    fn status_of(records: &HashMap<String, DecisionRecord>, id: &str) -> String {
+        records[id].status.clone()
+    }
+
+    fn register(records: &[DecisionRecord]) -> HashMap<String, DecisionRecord> {
+        records.iter().map(|r| (r.entry_item_id.clone(), r.clone())).collect()
+    }
+
+    #[test]
+    fn superseding_marks_the_earlier_decision() {
+        let records = register(&[record("a", DecisionStatus::Accepted, None)]);
+        let changed = supersession_changes(&records, record("c", DecisionStatus::Accepted, Some("a")));
+
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed[0].entry_item_id, "c");
+        assert_eq!(changed[1].entry_item_id, "a");
+        assert_eq!(changed[1].status, "superseded");
+        assert_eq!(changed[1].context.as_deref(), Some("context of a"));
+    }
+
+    #[test]
+    fn changing_and_clearing_supersedes_restores_earlier_decisions() {
+        let mut records = register(&[
+            record("a", DecisionStatus::Superseded, None),
+            record("b", DecisionStatus::Accepted, None),
+            record("c", DecisionStatus::Accepted, Some("a")),
+        ]);
+
+        let changed = supersession_changes(&records, record("c", DecisionStatus::Accepted, Some("b")));
+        apply(&mut records, changed);
+        assert_eq!(status_of(&records, "a"), "accepted");
+        assert_eq!(status_of(&records, "b"), "superseded");
+
+        let changed = supersession_changes(&records, record("c", DecisionStatus::Accepted, None));
+        apply(&mut records, changed);
+        assert_eq!(status_of(&records, "a"), "accepted");
+        assert_eq!(status_of(&records, "b"), "accepted");
+        assert_eq!(records["c"].supersedes_id, None);
+    }
+
+    #[test]
+    fn decision_still_superseded_by_another_stays_superseded() {
+        let records = register(&[
+            record("a", DecisionStatus::Superseded, None),
+            record("c", DecisionStatus::Accepted, Some("a")),
+            record("d", DecisionStatus::Accepted, Some("a")),
+        ]);
+        let changed = supersession_changes(&records, record("c", DecisionStatus::Accepted, None));
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].entry_item_id, "c");
+    }
+
+    #[test]
+    fn unchanged_link_saves_only_the_decision() {
+        let records = register(&[
+            record("a", DecisionStatus::Superseded, None),
+            record("c", DecisionStatus::Proposed, Some("a")),
+        ]);
+        let changed = supersession_changes(&records, record("c", DecisionStatus::Accepted, Some("a")));
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].status, "accepted");
+    }
+}
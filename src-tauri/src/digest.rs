@@ -0,0 +1,457 @@
+// Digest reports for a period: what was decided, which actions were raised
+// and closed, which meetings took place and where the time went, grouped by
+// project. Rendering goes through Tera templates kept in the data
+// directory, so the layout can be changed without a release; the defaults
+// below are written there the first time a digest is generated.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::commands::MeetingOccurrenceResponse;
+use crate::database::{EntryItemWithMetadata, MeetingAction, TimeEntry};
+use crate::timesheet;
+
+/// Directory under the app data directory holding the templates.
+pub const TEMPLATE_DIR: &str = "templates";
+
+/// Extension of template files; `digest.md` lives in `digest.md.tera`.
+pub const TEMPLATE_EXTENSION: &str = "tera";
+
+/// Label of the group for items without a project.
+pub const NO_PROJECT: &str = "No project";
+
+const DEFAULT_TEMPLATES: [(&str, &str); 2] = [
+    ("digest.md", DEFAULT_MARKDOWN_TEMPLATE),
+    ("digest.html", DEFAULT_HTML_TEMPLATE),
+];
+
+const DEFAULT_MARKDOWN_TEMPLATE: &str = r#"# Digest {{ start_label }} – {{ end_label }}
+
+**Decisions:** 🔵 {{ totals.decisions }} · **Actions opened:** 🔴 {{ totals.actions_opened }} · **Actions closed:** ✅ {{ totals.actions_closed }} · **Meetings:** 🟣 {{ totals.meetings }} · **Time:** ⏱️ {{ totals.tracked }}
+
+{% for project in projects -%}
+## 📂 {{ project.name }}{% if project.tracked_seconds > 0 %} — ⏱️ {{ project.tracked }}{% endif %}
+
+{% if project.decisions -%}
+### 🔵 Decisions
+{% for item in project.decisions %}- {{ item.content }}{% if item.people %} (👤 {{ item.people | join(sep=", ") }}){% endif %}
+{% endfor %}
+{% endif -%}
+{% if project.actions_opened -%}
+### 🔴 Actions opened
+{% for item in project.actions_opened %}- {{ item.content }}{% if item.assignee %} (👤 {{ item.assignee }}){% endif %}{% if item.source %} — {{ item.source }}{% endif %}
+{% endfor %}
+{% endif -%}
+{% if project.actions_closed -%}
+### ✅ Actions closed
+{% for item in project.actions_closed %}- {{ item.content }}{% if item.assignee %} (👤 {{ item.assignee }}){% endif %}{% if item.source %} — {{ item.source }}{% endif %}
+{% endfor %}
+{% endif -%}
+{% if project.meetings -%}
+### 🟣 Meeting notes
+{% for item in project.meetings %}- {{ item.content }}
+{% endfor %}
+{% endif -%}
+{% if project.notes -%}
+### 🟢 Notes
+{% for item in project.notes %}- {{ item.content }}
+{% endfor %}
+{% endif -%}
+{% endfor -%}
+{% if meetings -%}
+## 📅 Meetings held
+{% for meeting in meetings %}- {{ meeting.date }} {{ meeting.time }} {{ meeting.title }}
+{% endfor %}
+{% endif -%}
+"#;
+
+const DEFAULT_HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Digest {{ start_label }} – {{ end_label }}</title>
+<style>body{font-family:sans-serif;max-width:800px;margin:2em auto}h2{border-bottom:1px solid #ccc}.totals span{margin-right:1em}</style>
+</head>
+<body>
+<h1>Digest {{ start_label }} – {{ end_label }}</h1>
+<p class="totals"><span><strong>Decisions:</strong> {{ totals.decisions }}</span><span><strong>Actions opened:</strong> {{ totals.actions_opened }}</span><span><strong>Actions closed:</strong> {{ totals.actions_closed }}</span><span><strong>Meetings:</strong> {{ totals.meetings }}</span><span><strong>Time:</strong> {{ totals.tracked }}</span></p>
+{% for project in projects %}
+<h2>{{ project.name }}{% if project.tracked_seconds > 0 %} <small>({{ project.tracked }})</small>{% endif %}</h2>
+{% if project.decisions %}
+<h3>Decisions</h3>
+<ul>
+{% for item in project.decisions %}<li>{{ item.content }}</li>
+{% endfor %}</ul>
+{% endif %}
+{% if project.actions_opened %}
+<h3>Actions opened</h3>
+<ul>
+{% for item in project.actions_opened %}<li>{{ item.content }}{% if item.assignee %} ({{ item.assignee }}){% endif %}{% if item.source %} <em>{{ item.source }}</em>{% endif %}</li>
+{% endfor %}</ul>
+{% endif %}
+{% if project.actions_closed %}
+<h3>Actions closed</h3>
+<ul>
+{% for item in project.actions_closed %}<li>{{ item.content }}{% if item.assignee %} ({{ item.assignee }}){% endif %}{% if item.source %} <em>{{ item.source }}</em>{% endif %}</li>
+{% endfor %}</ul>
+{% endif %}
+{% if project.meetings %}
+<h3>Meeting notes</h3>
+<ul>
+{% for item in project.meetings %}<li>{{ item.content }}</li>
+{% endfor %}</ul>
+{% endif %}
+{% if project.notes %}
+<h3>Notes</h3>
+<ul>
+{% for item in project.notes %}<li>{{ item.content }}</li>
+{% endfor %}</ul>
+{% endif %}
+{% endfor %}
+{% if meetings %}
+<h2>Meetings held</h2>
+<ul>
+{% for meeting in meetings %}<li>{{ meeting.date }} {{ meeting.time }} {{ meeting.title }}</li>
+{% endfor %}</ul>
+{% endif %}
+</body>
+</html>
+"#;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestItem {
+    pub date: String,
+    pub content: String,
+    pub item_type: String,
+    pub tags: Vec<String>,
+    pub people: Vec<String>,
+    pub jira: Vec<String>,
+    pub assignee: Option<String>,
+    /// Where a meeting action came from, e.g. `Sprint planning`.
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectDigest {
+    pub name: String,
+    pub decisions: Vec<DigestItem>,
+    pub actions_opened: Vec<DigestItem>,
+    pub actions_closed: Vec<DigestItem>,
+    pub meetings: Vec<DigestItem>,
+    pub notes: Vec<DigestItem>,
+    pub tracked_seconds: i64,
+    pub tracked: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestMeeting {
+    pub meeting_id: String,
+    pub title: String,
+    pub date: String,
+    pub time: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DigestTotals {
+    pub decisions: usize,
+    pub actions_opened: usize,
+    pub actions_closed: usize,
+    pub meetings: usize,
+    pub tracked_seconds: i64,
+    pub tracked: String,
+}
+
+/// Everything a template can use.
+#[derive(Debug, Clone, Serialize)]
+pub struct Digest {
+    pub range_start: String,
+    pub range_end: String,
+    pub start_label: String,
+    /// The last day covered; the range itself is half-open.
+    pub end_label: String,
+    pub projects: Vec<ProjectDigest>,
+    pub meetings: Vec<DigestMeeting>,
+    pub totals: DigestTotals,
+}
+
+fn local_date(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local).format("%Y-%m-%d").to_string()
+}
+
+fn item_from_entry(timestamp: DateTime<Utc>, item: &EntryItemWithMetadata) -> DigestItem {
+    DigestItem {
+        date: local_date(timestamp),
+        content: item.item.content.clone(),
+        item_type: item.item.item_type.clone(),
+        tags: item.tags.iter().map(|t| t.name.clone()).collect(),
+        people: item.people.iter().map(|p| p.name.clone()).collect(),
+        jira: item.jira_refs.iter().map(|j| j.jira_key.clone()).collect(),
+        assignee: None,
+        source: None,
+    }
+}
+
+fn item_from_action(at: DateTime<Utc>, action: &MeetingAction, meeting_title: &str) -> DigestItem {
+    DigestItem {
+        date: local_date(at),
+        content: action.title.clone(),
+        item_type: "Action".to_string(),
+        tags: Vec::new(),
+        people: Vec::new(),
+        jira: Vec::new(),
+        assignee: action.assignee.clone(),
+        source: Some(meeting_title.to_string()),
+    }
+}
+
+fn project_name(project: Option<&str>) -> String {
+    match project.map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => NO_PROJECT.to_string(),
+    }
+}
+
+fn group(projects: &mut BTreeMap<String, ProjectDigest>, name: String) -> &mut ProjectDigest {
+    projects.entry(name.clone()).or_insert_with(|| ProjectDigest { name, ..Default::default() })
+}
+
+/// Group the period's data by project. Meeting actions carry no project of
+/// their own; they come with the project of the item they were logged from.
+pub fn build_digest(
+    range_start: DateTime<Utc>,
+    range_end: DateTime<Utc>,
+    items: &[(DateTime<Utc>, EntryItemWithMetadata)],
+    actions: &[(MeetingAction, String, Option<String>)],
+    occurrences: &[MeetingOccurrenceResponse],
+    time_entries: &[TimeEntry],
+    now: DateTime<Utc>,
+) -> Digest {
+    let mut projects: BTreeMap<String, ProjectDigest> = BTreeMap::new();
+    let in_range = |at: DateTime<Utc>| at >= range_start && at < range_end;
+
+    // Each action is counted once: a meeting action stands in for the
+    // Action item it was logged from
+    let mut seen_actions = HashSet::new();
+    let actions: Vec<_> = actions.iter().filter(|(action, _, _)| seen_actions.insert(action.id.as_str())).collect();
+    let logged_actions: HashSet<&str> = actions.iter()
+        .filter(|(action, _, _)| in_range(action.created_at))
+        .filter_map(|(action, _, _)| action.entry_item_id.as_deref())
+        .collect();
+
+    for (timestamp, item) in items {
+        let digest_item = item_from_entry(*timestamp, item);
+        let project = group(&mut projects, project_name(item.item.project.as_deref()));
+        match item.item.item_type.as_str() {
+            "Decision" => project.decisions.push(digest_item),
+            "Action" if logged_actions.contains(item.item.id.as_str()) => {}
+            "Action" => project.actions_opened.push(digest_item),
+            "Meeting" => project.meetings.push(digest_item),
+            _ => project.notes.push(digest_item),
+        }
+    }
+
+    for (action, meeting_title, project) in actions {
+        let project = group(&mut projects, project_name(project.as_deref()));
+        if in_range(action.created_at) {
+            project.actions_opened.push(item_from_action(action.created_at, action, meeting_title));
+        }
+        if action.status == "done" && in_range(action.updated_at) {
+            project.actions_closed.push(item_from_action(action.updated_at, action, meeting_title));
+        }
+    }
+
+    for entry in time_entries {
+        let start = entry.start_time.max(range_start);
+        let end = entry.end_time.unwrap_or(now).min(range_end);
+        if end > start {
+            group(&mut projects, project_name(entry.project.as_deref())).tracked_seconds += (end - start).num_seconds();
+        }
+    }
+
+    // Projects sort by name, with unassigned items last
+    let mut projects: Vec<ProjectDigest> = projects.into_values()
+        .filter(|p| {
+            p.tracked_seconds > 0
+                || !(p.decisions.is_empty() && p.actions_opened.is_empty() && p.actions_closed.is_empty()
+                    && p.meetings.is_empty() && p.notes.is_empty())
+        })
+        .collect();
+    projects.sort_by_key(|p| p.name == NO_PROJECT);
+
+    let mut totals = DigestTotals::default();
+    for project in &mut projects {
+        project.tracked = timesheet::format_hours(project.tracked_seconds);
+        totals.decisions += project.decisions.len();
+        totals.actions_opened += project.actions_opened.len();
+        totals.actions_closed += project.actions_closed.len();
+        totals.tracked_seconds += project.tracked_seconds;
+    }
+    totals.tracked = timesheet::format_hours(totals.tracked_seconds);
+
+    let meetings: Vec<DigestMeeting> = occurrences.iter()
+        .filter_map(|occurrence| {
+            let start = DateTime::parse_from_rfc3339(&occurrence.occurrence_start).ok()?.with_timezone(&Local);
+            (start <= now).then(|| DigestMeeting {
+                meeting_id: occurrence.meeting_id.clone(),
+                title: occurrence.title.clone(),
+                date: start.format("%Y-%m-%d").to_string(),
+                time: start.format("%H:%M").to_string(),
+            })
+        })
+        .collect();
+    totals.meetings = meetings.len();
+
+    Digest {
+        range_start: range_start.to_rfc3339(),
+        range_end: range_end.to_rfc3339(),
+        start_label: local_date(range_start),
+        end_label: local_date(range_end - Duration::seconds(1)),
+        projects,
+        meetings,
+        totals,
+    }
+}
+
+pub fn template_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(TEMPLATE_DIR)
+}
+
+/// Write the default templates that are missing, leaving edited ones alone.
+pub fn ensure_default_templates(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create template directory: {}", e))?;
+    for (name, content) in DEFAULT_TEMPLATES {
+        let path = dir.join(format!("{}.{}", name, TEMPLATE_EXTENSION));
+        if !path.exists() {
+            std::fs::write(&path, content)
+                .map_err(|e| format!("Failed to write template {}: {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Template names available in `dir`, e.g. `digest.md`.
+pub fn list_templates(dir: &Path) -> Result<Vec<String>, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read template directory: {}", e))?;
+    let suffix = format!(".{}", TEMPLATE_EXTENSION);
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str()?.strip_suffix(&suffix).map(str::to_string))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Tera's own messages stop at "Failed to render"; the cause is further down.
+fn describe_error(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Render `digest` with the template called `name`. Names ending in `.html`
+/// are HTML-escaped; anything else is rendered as written.
+pub fn render(dir: &Path, name: &str, digest: &Digest) -> Result<String, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(format!("Invalid template name: {}", name));
+    }
+    let path = dir.join(format!("{}.{}", name, TEMPLATE_EXTENSION));
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read template {}: {}", path.display(), e))?;
+
+    let mut tera = Tera::default();
+    tera.add_raw_template(name, &source)
+        .map_err(|e| format!("Invalid template {}: {}", name, describe_error(&e)))?;
+    let context = Context::from_serialize(digest)
+        .map_err(|e| format!("Failed to prepare digest: {}", describe_error(&e)))?;
+
+    tera.render(name, &context)
+        .map_err(|e| format!("Failed to render digest: {}", describe_error(&e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    use crate::database::EntryItem;
+
+    fn at(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap()
+    }
+
+    fn item(id: &str, item_type: &str) -> (DateTime<Utc>, EntryItemWithMetadata) {
+        let item = EntryItem {
+            id: id.to_string(),
+            entry_id: "entry".to_string(),
+            item_type: item_type.to_string(),
+            content: format!("{} content", id),
+            project: Some("Apollo".to_string()),
+            position: 0,
+            created_at: at(6),
+            updated_at: at(6),
+        };
+        (at(6), EntryItemWithMetadata { item, tags: vec![], people: vec![], jira_refs: vec![], checklist: vec![], fields: vec![] })
+    }
+
+    fn action(id: &str, entry_item_id: Option<&str>, status: &str) -> (MeetingAction, String, Option<String>) {
+        let action = MeetingAction {
+            id: id.to_string(),
+            meeting_id: "meeting".to_string(),
+            entry_item_id: entry_item_id.map(str::to_string),
+            title: format!("{} title", id),
+            description: None,
+            assignee: Some("Ann".to_string()),
+            due_date: None,
+            status: status.to_string(),
+            priority: "medium".to_string(),
+            occurrence_start: None,
+            created_at: at(6),
+            updated_at: at(7),
+        };
+        (action, "Planning".to_string(), Some("Apollo".to_string()))
+    }
+
+    fn digest(items: &[(DateTime<Utc>, EntryItemWithMetadata)], actions: &[(MeetingAction, String, Option<String>)]) -> Digest {
+        build_digest(at(5), at(12), items, actions, &[], &[], at(12))
+    }
+
+    #[test]
+    fn action_logged_from_an_item_is_counted_once() {
+        let items = [item("logged", "Action"), item("plain", "Action")];
+        let actions = [action("a1", Some("logged"), "done")];
+        let digest = digest(&items, &actions);
+
+        assert_eq!(digest.totals.actions_opened, 2);
+        assert_eq!(digest.totals.actions_closed, 1);
+        let opened: Vec<&str> = digest.projects[0].actions_opened.iter().map(|i| i.content.as_str()).collect();
+        assert_eq!(opened, ["plain content", "a1 title"]);
+        assert_eq!(digest.projects[0].actions_opened[1].source.as_deref(), Some("Planning"));
+    }
+
+    #[test]
+    fn repeated_actions_are_counted_once() {
+        let actions = [action("a1", None, "open"), action("a1", None, "open")];
+        assert_eq!(digest(&[], &actions).totals.actions_opened, 1);
+    }
+
+    #[test]
+    fn item_behind_an_action_raised_earlier_is_still_listed() {
+        let items = [item("logged", "Action")];
+        let (mut earlier, title, project) = action("a1", Some("logged"), "done");
+        earlier.created_at = Utc.with_ymd_and_hms(2026, 9, 1, 12, 0, 0).unwrap();
+        let digest = digest(&items, &[(earlier, title, project)]);
+
+        assert_eq!(digest.totals.actions_opened, 1);
+        assert_eq!(digest.totals.actions_closed, 1);
+    }
+}
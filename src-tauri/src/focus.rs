@@ -0,0 +1,347 @@
+// Focus sessions (Pomodoro) on top of the backend timer. A session
+// alternates work blocks and breaks; the timer runs during work blocks only,
+// so focused time lands on the session's project like any other tracked
+// time. A background task waits for the current phase to end, advances the
+// session, records finished work blocks and tells the frontend and the tray.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::commands::AppState;
+use crate::database::Database;
+use crate::notifications;
+
+/// Key of the focus settings in the `settings` table.
+pub const FOCUS_SETTINGS_KEY: &str = "focus";
+
+/// Notes put on the time entries of work blocks.
+pub const FOCUS_TIMER_NOTES: &str = "Focus session";
+
+/// Longest single sleep of the session task. Tokio timers do not advance
+/// while the machine sleeps, so the wall clock is checked at least this often.
+const MAX_SLEEP_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusSettings {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    /// Work blocks per session; the long break follows the last one.
+    pub cycles: u32,
+    /// Ask for a Note entry at the end of each work block.
+    pub prompt_for_note: bool,
+}
+
+impl Default for FocusSettings {
+    fn default() -> Self {
+        FocusSettings {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            cycles: 4,
+            prompt_for_note: true,
+        }
+    }
+}
+
+impl FocusSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.work_minutes == 0 {
+            return Err("Work blocks must be at least one minute long".to_string());
+        }
+        if self.cycles == 0 {
+            return Err("A focus session needs at least one work block".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+#[derive(Debug, Clone)]
+pub struct FocusSession {
+    pub project: Option<String>,
+    pub settings: FocusSettings,
+    pub phase: FocusPhase,
+    /// 1-based number of the current work block.
+    pub cycle: u32,
+    pub phase_started_at: DateTime<Utc>,
+    pub phase_ends_at: DateTime<Utc>,
+    /// The timer started for the current work block, if this session owns it.
+    pub time_entry_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusStatus {
+    pub active: bool,
+    pub project: Option<String>,
+    pub phase: Option<FocusPhase>,
+    pub cycle: u32,
+    pub cycles: u32,
+    pub phase_started_at: Option<String>,
+    pub phase_ends_at: Option<String>,
+    pub remaining_seconds: i64,
+}
+
+/// Payload of the `focus-note-prompt` event sent when a work block ends.
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusNotePrompt {
+    pub project: Option<String>,
+    pub cycle: u32,
+    pub start_time: String,
+    pub end_time: String,
+}
+
+pub fn phase_length(settings: &FocusSettings, phase: FocusPhase) -> Duration {
+    let minutes = match phase {
+        FocusPhase::Work => settings.work_minutes,
+        FocusPhase::ShortBreak => settings.short_break_minutes,
+        FocusPhase::LongBreak => settings.long_break_minutes,
+    };
+    Duration::minutes(minutes as i64)
+}
+
+/// The phase that follows `phase` in work block `cycle`, or `None` when the
+/// session is over. Breaks configured as zero minutes are skipped.
+pub fn next_phase(settings: &FocusSettings, phase: FocusPhase, cycle: u32) -> Option<(FocusPhase, u32)> {
+    match phase {
+        FocusPhase::Work if cycle < settings.cycles => {
+            if settings.short_break_minutes > 0 {
+                Some((FocusPhase::ShortBreak, cycle))
+            } else {
+                Some((FocusPhase::Work, cycle + 1))
+            }
+        }
+        FocusPhase::Work if settings.long_break_minutes > 0 => Some((FocusPhase::LongBreak, cycle)),
+        FocusPhase::Work => None,
+        FocusPhase::ShortBreak => Some((FocusPhase::Work, cycle + 1)),
+        FocusPhase::LongBreak => None,
+    }
+}
+
+pub fn status(session: Option<&FocusSession>, now: DateTime<Utc>) -> FocusStatus {
+    match session {
+        Some(session) => FocusStatus {
+            active: true,
+            project: session.project.clone(),
+            phase: Some(session.phase),
+            cycle: session.cycle,
+            cycles: session.settings.cycles,
+            phase_started_at: Some(session.phase_started_at.to_rfc3339()),
+            phase_ends_at: Some(session.phase_ends_at.to_rfc3339()),
+            remaining_seconds: (session.phase_ends_at - now).num_seconds().max(0),
+        },
+        None => FocusStatus {
+            active: false,
+            project: None,
+            phase: None,
+            cycle: 0,
+            cycles: 0,
+            phase_started_at: None,
+            phase_ends_at: None,
+            remaining_seconds: 0,
+        },
+    }
+}
+
+pub async fn load_settings(db: &Database) -> Result<FocusSettings, String> {
+    let stored = db.get_setting(FOCUS_SETTINGS_KEY)
+        .await
+        .map_err(|e| format!("Failed to get focus settings: {}", e))?;
+
+    match stored {
+        Some(json) => serde_json::from_str(&json).map_err(|e| format!("Invalid focus settings: {}", e)),
+        None => Ok(FocusSettings::default()),
+    }
+}
+
+/// The running session and the task driving it.
+#[derive(Default)]
+pub struct FocusRuntime {
+    pub session: Option<FocusSession>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl FocusRuntime {
+    /// Swap the session task, cancelling the previous one.
+    pub fn replace_task(&mut self, task: Option<JoinHandle<()>>) {
+        if let Some(previous) = std::mem::replace(&mut self.task, task) {
+            previous.abort();
+        }
+    }
+}
+
+pub type FocusState = Arc<Mutex<FocusRuntime>>;
+
+pub fn spawn_session_task(app: AppHandle, state: AppState, focus: FocusState) -> JoinHandle<()> {
+    tokio::spawn(run_session(app, state, focus))
+}
+
+/// Start the timer for a work block unless the user already has one running.
+pub async fn start_work_timer(db: &Database, project: Option<&str>, at: DateTime<Utc>) -> Result<Option<String>, String> {
+    let active = db.get_active_timer()
+        .await
+        .map_err(|e| format!("Failed to get active timer: {}", e))?;
+    if active.is_some() {
+        return Ok(None);
+    }
+
+    let timer = db.start_timer(project, None, None, Some(FOCUS_TIMER_NOTES), None, true, at)
+        .await
+        .map_err(|e| format!("Failed to start timer: {}", e))?;
+    Ok(Some(timer.id))
+}
+
+/// Stop the session's own timer; timers the user started themselves are left
+/// running.
+pub async fn stop_work_timer(db: &Database, session: &FocusSession, at: DateTime<Utc>) -> Result<(), String> {
+    let active = db.get_active_timer()
+        .await
+        .map_err(|e| format!("Failed to get active timer: {}", e))?;
+    if active.is_some_and(|timer| Some(&timer.id) == session.time_entry_id.as_ref()) {
+        db.stop_timer(at)
+            .await
+            .map_err(|e| format!("Failed to stop timer: {}", e))?;
+    }
+    Ok(())
+}
+
+/// End the current phase at `at` and move to the next one. Returns `false`
+/// when the session is over.
+pub async fn advance(app: &AppHandle, db: &Database, session: &mut FocusSession, at: DateTime<Utc>) -> Result<bool, String> {
+    if session.phase == FocusPhase::Work {
+        stop_work_timer(db, session, at).await?;
+        db.record_focus_session(
+            session.project.as_deref(),
+            session.time_entry_id.as_deref(),
+            session.cycle as i64,
+            session.phase_started_at,
+            at,
+        )
+        .await
+        .map_err(|e| format!("Failed to record focus session: {}", e))?;
+
+        if session.settings.prompt_for_note {
+            let prompt = FocusNotePrompt {
+                project: session.project.clone(),
+                cycle: session.cycle,
+                start_time: session.phase_started_at.to_rfc3339(),
+                end_time: at.to_rfc3339(),
+            };
+            if let Err(e) = app.emit_all("focus-note-prompt", prompt) {
+                eprintln!("Failed to emit focus note prompt: {}", e);
+            }
+        }
+    }
+
+    let Some((phase, cycle)) = next_phase(&session.settings, session.phase, session.cycle) else {
+        session.time_entry_id = None;
+        return Ok(false);
+    };
+
+    session.time_entry_id = if phase == FocusPhase::Work {
+        start_work_timer(db, session.project.as_deref(), at).await?
+    } else {
+        None
+    };
+    session.phase = phase;
+    session.cycle = cycle;
+    session.phase_started_at = at;
+    session.phase_ends_at = at + phase_length(&session.settings, phase);
+
+    announce(app, Some(session));
+    Ok(true)
+}
+
+/// Tell the frontend, the tray and the user about the session's new state.
+/// `None` announces that the session has ended.
+pub fn announce(app: &AppHandle, session: Option<&FocusSession>) {
+    if let Err(e) = app.emit_all("focus-phase-changed", status(session, Utc::now())) {
+        eprintln!("Failed to emit focus phase change: {}", e);
+    }
+
+    let (title, body, tooltip) = match session {
+        Some(session) => {
+            let until = session.phase_ends_at.with_timezone(&Local).format("%H:%M");
+            match session.phase {
+                FocusPhase::Work => (
+                    format!("Focus block {} of {}", session.cycle, session.settings.cycles),
+                    format!("Focus until {}", until),
+                    format!("ScoBro Logbook — focus {}/{} until {}", session.cycle, session.settings.cycles, until),
+                ),
+                FocusPhase::ShortBreak => (
+                    "Time for a short break".to_string(),
+                    format!("Back to work at {}", until),
+                    format!("ScoBro Logbook — break until {}", until),
+                ),
+                FocusPhase::LongBreak => (
+                    "Session complete — take a long break".to_string(),
+                    format!("{} focus blocks done. Break until {}", session.cycle, until),
+                    format!("ScoBro Logbook — long break until {}", until),
+                ),
+            }
+        }
+        None => (
+            "Focus session finished".to_string(),
+            "Nice work.".to_string(),
+            notifications::DEFAULT_TOOLTIP.to_string(),
+        ),
+    };
+
+    notifications::show(app, &title, &body);
+    notifications::set_tooltip(app, &tooltip);
+}
+
+async fn run_session(app: AppHandle, state: AppState, focus: FocusState) {
+    loop {
+        let ends_at = match &focus.lock().await.session {
+            Some(session) => session.phase_ends_at,
+            None => return,
+        };
+
+        let now = Utc::now();
+        if now < ends_at {
+            let wait = (ends_at - now).to_std().unwrap_or_default();
+            tokio::time::sleep(wait.min(std::time::Duration::from_secs(MAX_SLEEP_SECS))).await;
+            continue;
+        }
+
+        let mut runtime = focus.lock().await;
+        let db = state.lock().await;
+        let Some(session) = runtime.session.as_mut() else {
+            return;
+        };
+
+        // Phases are closed at their scheduled end, even if the machine was
+        // asleep, so a block never counts longer than configured.
+        let at = session.phase_ends_at;
+        match advance(&app, &db, session, at).await {
+            Ok(true) => {}
+            Ok(false) => {
+                runtime.session = None;
+                // Detach rather than abort: this is the task being replaced.
+                drop(runtime.task.take());
+                announce(&app, None);
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to advance focus session: {}", e);
+                drop(db);
+                drop(runtime);
+                tokio::time::sleep(std::time::Duration::from_secs(MAX_SLEEP_SECS)).await;
+            }
+        }
+    }
+}
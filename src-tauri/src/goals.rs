@@ -0,0 +1,312 @@
+// Logging goals and streaks. A goal asks for a number of items, optionally
+// of one type, per workday, week, sprint or month. Streaks count consecutive
+// periods that met the target; days off in the working-days calendar are
+// skipped rather than breaking a streak. The period that is still running
+// only counts once it is met, so a streak is not broken until it is over.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, Duration, Local, NaiveDate, Timelike};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::AppState;
+use crate::database::{Database, Goal};
+use crate::notifications;
+
+/// Key of the working-days calendar in the `settings` table.
+pub const CALENDAR_SETTINGS_KEY: &str = "work_calendar";
+
+/// Local hour after which streaks at risk are nudged about.
+pub const STREAK_NUDGE_HOUR: u32 = 16;
+
+const NUDGE_CHECK_INTERVAL_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkCalendar {
+    /// ISO weekday numbers, 1 = Monday to 7 = Sunday.
+    pub working_days: Vec<u32>,
+    pub holidays: Vec<NaiveDate>,
+}
+
+impl Default for WorkCalendar {
+    fn default() -> Self {
+        WorkCalendar {
+            working_days: vec![1, 2, 3, 4, 5],
+            holidays: Vec::new(),
+        }
+    }
+}
+
+impl WorkCalendar {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.working_days.is_empty() {
+            return Err("At least one working day is required".to_string());
+        }
+        if let Some(day) = self.working_days.iter().find(|d| !(1..=7).contains(*d)) {
+            return Err(format!("Invalid weekday {}; use 1 (Monday) to 7 (Sunday)", day));
+        }
+        Ok(())
+    }
+
+    pub fn is_working_day(&self, day: NaiveDate) -> bool {
+        self.working_days.contains(&day.weekday().number_from_monday()) && !self.holidays.contains(&day)
+    }
+}
+
+pub async fn load_calendar(db: &Database) -> Result<WorkCalendar, String> {
+    let stored = db.get_setting(CALENDAR_SETTINGS_KEY)
+        .await
+        .map_err(|e| format!("Failed to get working-days calendar: {}", e))?;
+
+    match stored {
+        Some(json) => serde_json::from_str(&json).map_err(|e| format!("Invalid working-days calendar: {}", e)),
+        None => Ok(WorkCalendar::default()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalPeriod {
+    Workday,
+    Week,
+    Sprint { days: i64, start: NaiveDate },
+    Month,
+}
+
+impl GoalPeriod {
+    pub fn from_goal(goal: &Goal) -> Result<Self, String> {
+        match goal.period.as_str() {
+            "workday" => Ok(GoalPeriod::Workday),
+            "week" => Ok(GoalPeriod::Week),
+            "month" => Ok(GoalPeriod::Month),
+            "sprint" => match (goal.sprint_days, goal.sprint_start) {
+                (Some(days), Some(start)) if days > 0 => Ok(GoalPeriod::Sprint { days, start }),
+                _ => Err("Sprint goals need a sprint length and a start date".to_string()),
+            },
+            other => Err(format!("Unknown goal period: {}", other)),
+        }
+    }
+
+    /// First and last day of the period containing `day`.
+    pub fn bounds(self, day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            GoalPeriod::Workday => (day, day),
+            GoalPeriod::Week => {
+                let start = day - Duration::days(day.weekday().num_days_from_monday() as i64);
+                (start, start + Duration::days(6))
+            }
+            GoalPeriod::Sprint { days, start } => {
+                let offset = (day - start).num_days().div_euclid(days);
+                let first = start + Duration::days(offset * days);
+                (first, first + Duration::days(days - 1))
+            }
+            GoalPeriod::Month => {
+                let first = day.with_day(1).unwrap();
+                let next = if first.month() == 12 {
+                    NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+                }
+                .unwrap();
+                (first, next - Duration::days(1))
+            }
+        }
+    }
+}
+
+/// Periods from the one containing `first` up to the one containing `today`,
+/// leaving out periods without a working day.
+fn periods(period: GoalPeriod, calendar: &WorkCalendar, first: NaiveDate, today: NaiveDate) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut result = Vec::new();
+    let mut day = period.bounds(first).0;
+    while day <= today {
+        let (start, end) = period.bounds(day);
+        if days_between(start, end).any(|d| calendar.is_working_day(d)) {
+            result.push((start, end));
+        }
+        day = end + Duration::days(1);
+    }
+    result
+}
+
+fn days_between(start: NaiveDate, end: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    (0..=(end - start).num_days()).map(move |offset| start + Duration::days(offset))
+}
+
+/// Current and longest run of `true`s. When `last_in_progress` is set, a
+/// last `false` does not end the current run yet.
+pub fn streak_of(met: &[bool], last_in_progress: bool) -> (i64, i64) {
+    let mut run = 0;
+    let mut longest = 0;
+    for (index, &ok) in met.iter().enumerate() {
+        if ok {
+            run += 1;
+        } else if !(last_in_progress && index == met.len() - 1) {
+            run = 0;
+        }
+        longest = longest.max(run);
+    }
+    (run, longest)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalProgress {
+    pub goal_id: String,
+    pub name: String,
+    pub item_type: Option<String>,
+    pub period: String,
+    pub target_count: i64,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub count: i64,
+    pub remaining: i64,
+    pub met: bool,
+    pub current_streak: i64,
+    pub longest_streak: i64,
+    /// Today is the last working day of an unmet period.
+    pub ends_today: bool,
+}
+
+/// Progress on `goal` as of `today`, given item counts per local day.
+pub fn evaluate(
+    goal: &Goal,
+    calendar: &WorkCalendar,
+    daily_counts: &HashMap<NaiveDate, i64>,
+    today: NaiveDate,
+) -> Result<GoalProgress, String> {
+    let period = GoalPeriod::from_goal(goal)?;
+    let count_in = |(start, end): (NaiveDate, NaiveDate)| -> i64 {
+        days_between(start, end).map(|d| daily_counts.get(&d).copied().unwrap_or(0)).sum()
+    };
+
+    // Workday goals only have periods on working days
+    let in_scope = |d: NaiveDate| period != GoalPeriod::Workday || calendar.is_working_day(d);
+    let first = daily_counts.keys().copied().filter(|d| in_scope(*d)).min().unwrap_or(today).min(today);
+    let history = periods(period, calendar, first, today);
+
+    let current = period.bounds(today);
+    let current_active = history.last() == Some(&current);
+    let met: Vec<bool> = history.iter().map(|p| count_in(*p) >= goal.target_count).collect();
+    let (current_streak, longest_streak) = streak_of(&met, current_active);
+
+    let count = count_in(current);
+    let last_working_day = days_between(current.0, current.1).filter(|d| calendar.is_working_day(*d)).last();
+
+    Ok(GoalProgress {
+        goal_id: goal.id.clone(),
+        name: goal.name.clone(),
+        item_type: goal.item_type.clone(),
+        period: goal.period.clone(),
+        target_count: goal.target_count,
+        period_start: current.0,
+        period_end: current.1,
+        count,
+        remaining: (goal.target_count - count).max(0),
+        met: count >= goal.target_count,
+        current_streak,
+        longest_streak,
+        ends_today: count < goal.target_count && last_working_day == Some(today),
+    })
+}
+
+/// Consecutive working days with at least one entry. Entries on days off
+/// neither extend nor break the streak.
+pub fn logging_streak(active_days: &[NaiveDate], calendar: &WorkCalendar, today: NaiveDate) -> (i64, i64) {
+    let active: HashSet<NaiveDate> = active_days.iter().copied().collect();
+    let Some(first) = active_days.iter().copied().filter(|d| calendar.is_working_day(*d)).min() else {
+        return (0, 0);
+    };
+    if first > today {
+        return (0, 0);
+    }
+
+    let days: Vec<NaiveDate> = days_between(first, today).filter(|d| calendar.is_working_day(*d)).collect();
+    let met: Vec<bool> = days.iter().map(|d| active.contains(d)).collect();
+    streak_of(&met, days.last() == Some(&today))
+}
+
+pub async fn goal_progress(db: &Database, calendar: &WorkCalendar, today: NaiveDate) -> Result<Vec<GoalProgress>, String> {
+    let goals = db.get_goals()
+        .await
+        .map_err(|e| format!("Failed to get goals: {}", e))?;
+
+    let mut progress = Vec::new();
+    for goal in goals {
+        let counts: HashMap<NaiveDate, i64> = db.count_items_per_day(goal.item_type.as_deref())
+            .await
+            .map_err(|e| format!("Failed to count items: {}", e))?
+            .into_iter()
+            .collect();
+        progress.push(evaluate(&goal, calendar, &counts, today)?);
+    }
+
+    Ok(progress)
+}
+
+/// Messages for streaks that end today unless something is logged.
+pub async fn streaks_at_risk(db: &Database, today: NaiveDate) -> Result<Vec<String>, String> {
+    let calendar = load_calendar(db).await?;
+    let mut messages = Vec::new();
+
+    if calendar.is_working_day(today) {
+        let active_days = db.get_active_days()
+            .await
+            .map_err(|e| format!("Failed to get active days: {}", e))?;
+        let (current, _) = logging_streak(&active_days, &calendar, today);
+        if current > 0 && !active_days.contains(&today) {
+            messages.push(format!("Log something today to keep your {}-day streak going.", current));
+        }
+    }
+
+    for progress in goal_progress(db, &calendar, today).await? {
+        if progress.ends_today && progress.current_streak > 0 {
+            let noun = progress.item_type.as_deref().unwrap_or("item").to_lowercase();
+            messages.push(format!(
+                "Log {} more {}{} today to keep \"{}\" going ({} in a row).",
+                progress.remaining,
+                noun,
+                if progress.remaining == 1 { "" } else { "s" },
+                progress.name,
+                progress.current_streak
+            ));
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Once per working day, after `STREAK_NUDGE_HOUR`, nudge through the tray
+/// when a streak is about to break.
+pub async fn run_streak_nudges(app: AppHandle, state: AppState) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(NUDGE_CHECK_INTERVAL_SECS));
+    let mut nudged_on: Option<NaiveDate> = None;
+    loop {
+        interval.tick().await;
+
+        let now = Local::now();
+        let today = now.date_naive();
+        if now.hour() < STREAK_NUDGE_HOUR || nudged_on == Some(today) {
+            continue;
+        }
+
+        let db = state.lock().await;
+        let messages = match streaks_at_risk(&db, today).await {
+            Ok(messages) => messages,
+            Err(e) => {
+                eprintln!("Failed to check streaks: {}", e);
+                continue;
+            }
+        };
+        drop(db);
+
+        if messages.is_empty() {
+            continue;
+        }
+        nudged_on = Some(today);
+        notifications::show(&app, "Your streak is about to break", &messages.join("\n"));
+        if let Err(e) = app.emit_all("streak-at-risk", messages) {
+            eprintln!("Failed to emit streak nudge: {}", e);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+// Invoices built from tracked time. Each finished, billable time entry on a
+// project becomes one line priced at the person's rate on that project, or
+// the project rate when the person has none. Hours and amounts are rounded
+// to cents per line so the lines always add up to the printed total.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, Local};
+
+use crate::database::{Invoice, InvoiceLine, Project, ProjectPersonRate, TimeEntry};
+
+/// Invoice numbers as printed, e.g. `INV-0007`.
+pub fn format_invoice_number(number: i64) -> String {
+    format!("INV-{:04}", number)
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+pub fn format_amount(amount: f64, currency: &str) -> String {
+    format!("{:.2} {}", amount, currency)
+}
+
+fn line_description(entry: &TimeEntry) -> String {
+    match (entry.jira_key.as_deref(), entry.notes.as_deref()) {
+        (Some(key), Some(notes)) => format!("{}: {}", key, notes),
+        (Some(key), None) => key.to_string(),
+        (None, Some(notes)) => notes.to_string(),
+        (None, None) => "Time tracked".to_string(),
+    }
+}
+
+/// Price each entry. Fails with the offending entry's description when no
+/// rate applies, rather than silently billing it at zero.
+pub fn build_lines(
+    project: &Project,
+    person_rates: &[ProjectPersonRate],
+    entries: &[TimeEntry],
+) -> Result<Vec<InvoiceLine>, String> {
+    let rates: HashMap<&str, f64> = person_rates
+        .iter()
+        .map(|r| (r.person.as_str(), r.hourly_rate))
+        .collect();
+
+    let mut lines = Vec::new();
+    for entry in entries {
+        let Some(end_time) = entry.end_time else {
+            continue;
+        };
+
+        let rate = entry.person.as_deref()
+            .and_then(|person| rates.get(person).copied())
+            .or(project.hourly_rate)
+            .ok_or_else(|| format!(
+                "No hourly rate for \"{}\" on {}; set a project or person rate first",
+                line_description(entry),
+                entry.start_time.with_timezone(&Local).format("%Y-%m-%d")
+            ))?;
+
+        let hours = round_cents((end_time - entry.start_time).num_seconds() as f64 / 3600.0);
+        lines.push(InvoiceLine {
+            id: String::new(),
+            invoice_id: String::new(),
+            time_entry_id: Some(entry.id.clone()),
+            date: entry.start_time,
+            description: line_description(entry),
+            person: entry.person.clone(),
+            hours,
+            rate,
+            amount: round_cents(hours * rate),
+        });
+    }
+
+    Ok(lines)
+}
+
+/// The period is stored half-open, so the label shows the last day billed.
+fn period_label(invoice: &Invoice) -> String {
+    format!(
+        "{} – {}",
+        invoice.period_start.with_timezone(&Local).format("%Y-%m-%d"),
+        (invoice.period_end - Duration::seconds(1)).with_timezone(&Local).format("%Y-%m-%d")
+    )
+}
+
+pub fn render_markdown(invoice: &Invoice, lines: &[InvoiceLine]) -> String {
+    let mut markdown = format!("# Invoice {}\n\n", format_invoice_number(invoice.number));
+    markdown.push_str(&format!("**Project:** 📂 {}\n\n", invoice.project_name));
+    markdown.push_str(&format!("**Period:** 📅 {}\n\n", period_label(invoice)));
+    markdown.push_str(&format!(
+        "**Issued:** {}\n\n",
+        invoice.created_at.with_timezone(&Local).format("%Y-%m-%d")
+    ));
+
+    markdown.push_str("| Date | Description | Person | Hours | Rate | Amount |\n");
+    markdown.push_str("|---|---|---|---|---|---|\n");
+    for line in lines {
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {:.2} | {} | {} |\n",
+            line.date.with_timezone(&Local).format("%Y-%m-%d"),
+            line.description.replace('|', "\\|"),
+            line.person.as_deref().unwrap_or("").replace('|', "\\|"),
+            line.hours,
+            format_amount(line.rate, &invoice.currency),
+            format_amount(line.amount, &invoice.currency)
+        ));
+    }
+
+    let hours: f64 = lines.iter().map(|l| l.hours).sum();
+    markdown.push_str(&format!(
+        "| **Total** | | | **{:.2}** | | **{}** |\n",
+        hours,
+        format_amount(invoice.total, &invoice.currency)
+    ));
+
+    markdown
+}
+
+pub fn render_html(invoice: &Invoice, lines: &[InvoiceLine]) -> String {
+    let escape = crate::commands::escape_html;
+    let number = format_invoice_number(invoice.number);
+
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>Invoice {}</title>\n", number));
+    html.push_str("<style>body{font-family:sans-serif;max-width:800px;margin:2em auto}table{border-collapse:collapse;width:100%}th,td{border:1px solid #ccc;padding:4px 8px;text-align:left}td.num,th.num{text-align:right}tfoot td{font-weight:bold}</style>\n");
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&format!("<h1>Invoice {}</h1>\n", number));
+    html.push_str(&format!(
+        "<p><strong>Project:</strong> {}<br>\n<strong>Period:</strong> {}<br>\n<strong>Issued:</strong> {}</p>\n",
+        escape(&invoice.project_name),
+        period_label(invoice),
+        invoice.created_at.with_timezone(&Local).format("%Y-%m-%d")
+    ));
+
+    html.push_str("<table>\n<thead><tr><th>Date</th><th>Description</th><th>Person</th><th class=\"num\">Hours</th><th class=\"num\">Rate</th><th class=\"num\">Amount</th></tr></thead>\n<tbody>\n");
+    for line in lines {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{:.2}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            line.date.with_timezone(&Local).format("%Y-%m-%d"),
+            escape(&line.description),
+            escape(line.person.as_deref().unwrap_or("")),
+            line.hours,
+            escape(&format_amount(line.rate, &invoice.currency)),
+            escape(&format_amount(line.amount, &invoice.currency))
+        ));
+    }
+
+    let hours: f64 = lines.iter().map(|l| l.hours).sum();
+    html.push_str(&format!(
+        "</tbody>\n<tfoot><tr><td colspan=\"3\">Total</td><td class=\"num\">{:.2}</td><td></td><td class=\"num\">{}</td></tr></tfoot>\n</table>\n",
+        hours,
+        escape(&format_amount(invoice.total, &invoice.currency))
+    ));
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
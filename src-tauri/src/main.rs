@@ -6,24 +6,69 @@
 // `tauri::generate_handler!` macro.
 
 use std::sync::Arc;
+use tauri::RunEvent;
 use tokio::sync::Mutex;
-// use tauri::Manager; // Not needed for now
 
 mod database;
+mod csv_export;
+mod csv_import;
+mod custom_fields;
 mod commands;
+mod recurrence;
+mod timer_recovery;
+mod timesheet;
+mod invoice;
+mod focus;
+mod analytics;
+mod autocomplete;
+mod backup;
+mod bulk;
+mod goals;
+mod notifications;
+mod decisions;
+mod digest;
+mod nudges;
+mod one_on_one;
+mod reminders;
+mod standup;
+mod tray;
+mod waiting;
 
 use database::Database;
-use commands::{AppState, create_entry, get_all_entries, update_entry_item, delete_entry_item, delete_entry, export_entries_csv, export_entries_markdown, create_project, get_all_projects, update_project, delete_project, create_tag, get_all_tags, update_tag, delete_tag, create_meeting, get_all_meetings, add_meeting_attendee, get_meeting_attendees, create_meeting_action, get_meeting_actions, delete_meeting};
+use focus::FocusState;
+use nudges::NudgeState;
+use commands::{AppState, create_entry, get_all_entries, update_entry_item, delete_entry_item, delete_entry, export_entries_csv, export_entries_markdown, create_project, get_all_projects, update_project, delete_project, create_tag, get_all_tags, update_tag, delete_tag, create_meeting, get_all_meetings, add_meeting_attendee, get_meeting_attendees, create_meeting_action, get_meeting_actions, delete_meeting, link_meeting_note, unlink_meeting_note, generate_meeting_minutes, set_meeting_recurrence, cancel_meeting_occurrence, get_meeting_occurrences, start_timer, stop_timer, get_active_timer, list_time_entries, edit_time_entry, delete_time_entry, get_timer_recovery, resolve_timer_recovery, get_timesheet, export_timesheet, set_project_person_rate, get_project_person_rates, generate_invoice, list_invoices, export_invoice, get_focus_settings, update_focus_settings, start_focus_session, stop_focus_session, skip_focus_phase, get_focus_status, list_focus_sessions, get_analytics, get_work_calendar, update_work_calendar, create_goal, get_all_goals, update_goal, delete_goal, get_goal_progress, get_nudge_settings, update_nudge_settings, take_pending_nudge, open_quick_entry, get_reminder_settings, update_reminder_settings, list_reminders, snooze_reminder, dismiss_reminder, update_meeting_action_status, generate_digest, list_digest_templates, generate_standup, list_decisions, update_decision, export_decisions_adr, build_one_on_one_agenda, set_waiting_for, resolve_waiting_for, clear_waiting_for, list_waiting_for, add_checklist_row, toggle_checklist_row, reorder_checklist_rows, delete_checklist_row, create_custom_field, get_custom_fields, update_custom_field, delete_custom_field, update_entry_timestamp, move_item, split_entry, merge_entries, reorder_items, bulk_update_items, undo_bulk_update, autocomplete, export_json, import_json, import_csv};
 
 
 #[tokio::main]
 async fn main() {
-  // Initialize database
-  let database = Database::new().await.expect("Failed to initialize database");
+  let context = tauri::generate_context!();
+
+  // Initialize database in the app data directory so it survives restarts
+  let data_dir = tauri::api::path::app_data_dir(context.config());
+  let database = Database::new(data_dir.as_deref()).await.expect("Failed to initialize database");
   let app_state: AppState = Arc::new(Mutex::new(database));
 
+  // Keep the running timer's heartbeat fresh for idle-gap detection
+  tokio::spawn(timer_recovery::run_heartbeat(app_state.clone()));
+
+  // Focus sessions live in memory; their work blocks are ordinary timers
+  let focus_state: FocusState = Default::default();
+
+  // For the streak, scheduler and reminder tasks started in setup
+  let background_state = app_state.clone();
+
+  // The last smart-prompt nudge, picked up by the quick-entry window
+  let pending_nudge: NudgeState = Default::default();
+  let scheduler_nudge = pending_nudge.clone();
+
   tauri::Builder::default()
     .manage(app_state)
+    .manage(focus_state)
+    .manage(pending_nudge)
+    .system_tray(tray::build())
+    .on_system_tray_event(tray::handle_event)
+    .on_window_event(tray::handle_window_event)
         .invoke_handler(tauri::generate_handler![
           create_entry,
           get_all_entries,
@@ -46,13 +91,104 @@ async fn main() {
           get_meeting_attendees,
           create_meeting_action,
           get_meeting_actions,
-          delete_meeting
+          delete_meeting,
+          link_meeting_note,
+          unlink_meeting_note,
+          generate_meeting_minutes,
+          set_meeting_recurrence,
+          cancel_meeting_occurrence,
+          get_meeting_occurrences,
+          start_timer,
+          stop_timer,
+          get_active_timer,
+          list_time_entries,
+          edit_time_entry,
+          delete_time_entry,
+          get_timer_recovery,
+          resolve_timer_recovery,
+          get_timesheet,
+          export_timesheet,
+          set_project_person_rate,
+          get_project_person_rates,
+          generate_invoice,
+          list_invoices,
+          export_invoice,
+          get_focus_settings,
+          update_focus_settings,
+          start_focus_session,
+          stop_focus_session,
+          skip_focus_phase,
+          get_focus_status,
+          list_focus_sessions,
+          get_analytics,
+          get_work_calendar,
+          update_work_calendar,
+          create_goal,
+          get_all_goals,
+          update_goal,
+          delete_goal,
+          get_goal_progress,
+          get_nudge_settings,
+          update_nudge_settings,
+          take_pending_nudge,
+          open_quick_entry,
+          get_reminder_settings,
+          update_reminder_settings,
+          list_reminders,
+          snooze_reminder,
+          dismiss_reminder,
+          update_meeting_action_status,
+          generate_digest,
+          list_digest_templates,
+          generate_standup,
+          list_decisions,
+          update_decision,
+          export_decisions_adr,
+          build_one_on_one_agenda,
+          set_waiting_for,
+          resolve_waiting_for,
+          clear_waiting_for,
+          list_waiting_for,
+          add_checklist_row,
+          toggle_checklist_row,
+          reorder_checklist_rows,
+          delete_checklist_row,
+          create_custom_field,
+          get_custom_fields,
+          update_custom_field,
+          delete_custom_field,
+          update_entry_timestamp,
+          move_item,
+          split_entry,
+          merge_entries,
+          reorder_items,
+          bulk_update_items,
+          undo_bulk_update,
+          autocomplete,
+          export_json,
+          import_json,
+          import_csv
         ])
-    .setup(|_app| {
+    .setup(move |app| {
       // Note: Global shortcuts are not available in Tauri 1.x
       // Users can use the tray menu or the New Entry button instead
+
+      // Nudge through the tray when a logging streak is about to break
+      tokio::spawn(goals::run_streak_nudges(app.handle(), background_state.clone()));
+
+      // Smart prompts and meeting reminders, even while the window is closed
+      tokio::spawn(nudges::run_scheduler(app.handle(), background_state.clone(), scheduler_nudge));
+
+      // Due-date reminders for open actions
+      tokio::spawn(reminders::run_reminders(app.handle(), background_state));
       Ok(())
     })
-    .run(tauri::generate_context!())
-    .expect("error while running Tauri application");
+    .build(context)
+    .expect("error while building Tauri application")
+    .run(|_app, event| {
+      // Stay in the tray when the last window goes; "Quit" exits for real
+      if let RunEvent::ExitRequested { api, .. } = event {
+        api.prevent_exit();
+      }
+    });
 }
\ No newline at end of file
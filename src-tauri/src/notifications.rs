@@ -0,0 +1,23 @@
+// Native notifications and the tray tooltip, shared by the background tasks.
+// Failures are logged and otherwise ignored: a missed notification should
+// never stop a timer or a scheduler.
+
+use tauri::{AppHandle, Manager};
+
+pub const DEFAULT_TOOLTIP: &str = "ScoBro Logbook";
+
+pub fn show(app: &AppHandle, title: &str, body: &str) {
+    if let Err(e) = tauri::api::notification::Notification::new(&app.config().tauri.bundle.identifier)
+        .title(title)
+        .body(body)
+        .show()
+    {
+        eprintln!("Failed to show notification: {}", e);
+    }
+}
+
+pub fn set_tooltip(app: &AppHandle, tooltip: &str) {
+    if let Err(e) = app.tray_handle().set_tooltip(tooltip) {
+        eprintln!("Failed to update tray tooltip: {}", e);
+    }
+}
@@ -0,0 +1,371 @@
+// Smart-prompt nudges scheduled by the backend, so they arrive while the
+// window is hidden or closed. The settings are the frontend's smart-prompt
+// config (same camelCase fields), stored in the `settings` table. Nudges
+// respect quiet hours and the working-days calendar, fire at most once per
+// time slot a day and no more often than the chosen frequency allows;
+// upcoming meetings are announced once per occurrence.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::commands::{self, AppState};
+use crate::database::Database;
+use crate::goals;
+use crate::notifications;
+
+/// Key of the nudge settings in the `settings` table.
+pub const NUDGE_SETTINGS_KEY: &str = "nudges";
+
+/// How far ahead meetings are announced.
+pub const MEETING_LEAD_MINUTES: i64 = 15;
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Local hours of the time slots, inclusive at both ends like the frontend's.
+const MORNING_HOURS: (u32, u32) = (8, 10);
+const AFTERNOON_HOURS: (u32, u32) = (14, 16);
+const EVENING_HOURS: (u32, u32) = (17, 19);
+const WEEKLY_DAY: Weekday = Weekday::Fri;
+const WEEKLY_FROM_HOUR: u32 = 17;
+
+const MORNING_PROMPTS: [&str; 5] = [
+    "🌅 Good morning! What's your top priority for today?",
+    "☀️ Ready to tackle the day? What's your first action item?",
+    "🌞 Morning! Any important decisions you need to make today?",
+    "☕ Coffee time! What meetings or calls do you have scheduled?",
+    "🌅 Start your day right - what's your main focus today?",
+];
+const AFTERNOON_PROMPTS: [&str; 5] = [
+    "🌤️ How's your day going? Any progress updates to log?",
+    "📝 Afternoon check-in: What have you accomplished so far?",
+    "🔄 Time for a mid-day review - any decisions made?",
+    "💡 Any new ideas or insights from your morning work?",
+    "📊 How are you tracking against your daily goals?",
+];
+const EVENING_PROMPTS: [&str; 5] = [
+    "🌙 Evening reflection time - what went well today?",
+    "📋 End of day wrap-up: any final notes or decisions?",
+    "🎯 What did you accomplish today?",
+    "🤔 Any lessons learned or insights to capture?",
+    "📝 Time to log your day - what were the key highlights?",
+];
+const WEEKLY_PROMPTS: [&str; 5] = [
+    "📅 Weekly review time - how did this week go?",
+    "📊 What were your biggest wins this week?",
+    "🎯 What should you focus on next week?",
+    "📈 Any patterns or trends you've noticed?",
+    "🔄 What would you do differently next week?",
+];
+const PROJECT_PROMPTS: [&str; 5] = [
+    "📂 How's your [PROJECT] project progressing?",
+    "🎯 Any updates on [PROJECT] you'd like to capture?",
+    "📋 Time to check in on [PROJECT] - any blockers?",
+    "💡 Any new ideas for [PROJECT]?",
+    "📊 [PROJECT] status update needed?",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NudgeFrequency {
+    Low,
+    Normal,
+    High,
+}
+
+impl NudgeFrequency {
+    /// Shortest gap between two time-slot nudges.
+    pub fn min_interval(self) -> Duration {
+        match self {
+            NudgeFrequency::Low => Duration::hours(4),
+            NudgeFrequency::Normal => Duration::hours(2),
+            NudgeFrequency::High => Duration::hours(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl QuietHours {
+    /// Whether `hour` is quiet. A start after the end wraps past midnight.
+    pub fn contains(self, hour: u32) -> bool {
+        if self.start > self.end {
+            hour >= self.start || hour < self.end
+        } else {
+            hour >= self.start && hour < self.end
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NudgeSettings {
+    pub enabled: bool,
+    pub morning_nudges: bool,
+    pub afternoon_nudges: bool,
+    pub evening_nudges: bool,
+    pub weekly_nudges: bool,
+    pub meeting_nudges: bool,
+    pub project_nudges: bool,
+    pub frequency: NudgeFrequency,
+    pub quiet_hours: QuietHours,
+}
+
+impl Default for NudgeSettings {
+    fn default() -> Self {
+        NudgeSettings {
+            enabled: true,
+            morning_nudges: true,
+            afternoon_nudges: true,
+            evening_nudges: true,
+            weekly_nudges: true,
+            meeting_nudges: true,
+            project_nudges: true,
+            frequency: NudgeFrequency::Normal,
+            quiet_hours: QuietHours { start: 22, end: 7 },
+        }
+    }
+}
+
+impl NudgeSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.quiet_hours.start > 23 || self.quiet_hours.end > 23 {
+            return Err("Quiet hours must be between 0 and 23".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub async fn load_settings(db: &Database) -> Result<NudgeSettings, String> {
+    let stored = db.get_setting(NUDGE_SETTINGS_KEY)
+        .await
+        .map_err(|e| format!("Failed to get nudge settings: {}", e))?;
+
+    match stored {
+        Some(json) => serde_json::from_str(&json).map_err(|e| format!("Invalid nudge settings: {}", e)),
+        None => Ok(NudgeSettings::default()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NudgeSlot {
+    Morning,
+    Afternoon,
+    Evening,
+    Weekly,
+    Project,
+    Meeting,
+}
+
+/// Payload of the `smart-prompt` event; the shape the frontend's
+/// `SmartPromptsService.getSmartPrompt` returns.
+#[derive(Debug, Clone, Serialize)]
+pub struct SmartPrompt {
+    pub prompt: String,
+    pub context: NudgeSlot,
+    pub timestamp: String,
+    pub priority: String,
+}
+
+/// The last nudge fired, until the quick-entry window picks it up.
+pub type NudgeState = Arc<Mutex<Option<SmartPrompt>>>;
+
+/// The enabled time slot `now` falls in, if any. The weekly review wins over
+/// the evening slot on Friday.
+pub fn current_slot(settings: &NudgeSettings, now: DateTime<Local>) -> Option<NudgeSlot> {
+    let hour = now.hour();
+    let within = |(start, end): (u32, u32)| hour >= start && hour <= end;
+
+    if settings.weekly_nudges && now.weekday() == WEEKLY_DAY && hour >= WEEKLY_FROM_HOUR {
+        Some(NudgeSlot::Weekly)
+    } else if settings.morning_nudges && within(MORNING_HOURS) {
+        Some(NudgeSlot::Morning)
+    } else if (settings.afternoon_nudges || settings.project_nudges) && within(AFTERNOON_HOURS) {
+        Some(NudgeSlot::Afternoon)
+    } else if settings.evening_nudges && within(EVENING_HOURS) {
+        Some(NudgeSlot::Evening)
+    } else {
+        None
+    }
+}
+
+/// Prompts rotate by day so the same slot does not repeat itself daily.
+fn pick(prompts: &[&str], day: NaiveDate) -> String {
+    prompts[day.ordinal0() as usize % prompts.len()].to_string()
+}
+
+fn priority(slot: NudgeSlot) -> &'static str {
+    match slot {
+        NudgeSlot::Meeting => "high",
+        NudgeSlot::Morning | NudgeSlot::Weekly => "medium",
+        _ => "low",
+    }
+}
+
+/// The prompt for a time slot from `current_slot`. The afternoon slot asks
+/// about the most active project of the last week on alternate days, or
+/// always when plain afternoon nudges are off; with neither, there is none.
+pub fn slot_prompt(
+    settings: &NudgeSettings,
+    slot: NudgeSlot,
+    recent_project: Option<&str>,
+    now: DateTime<Local>,
+) -> Option<SmartPrompt> {
+    let day = now.date_naive();
+    let (context, prompt) = match slot {
+        NudgeSlot::Morning => (slot, pick(&MORNING_PROMPTS, day)),
+        NudgeSlot::Evening => (slot, pick(&EVENING_PROMPTS, day)),
+        NudgeSlot::Weekly => (slot, pick(&WEEKLY_PROMPTS, day)),
+        _ => match recent_project {
+            Some(project)
+                if settings.project_nudges && (!settings.afternoon_nudges || day.ordinal() % 2 == 1) =>
+            {
+                (NudgeSlot::Project, pick(&PROJECT_PROMPTS, day).replace("[PROJECT]", project))
+            }
+            _ if settings.afternoon_nudges => (NudgeSlot::Afternoon, pick(&AFTERNOON_PROMPTS, day)),
+            _ => return None,
+        },
+    };
+
+    Some(SmartPrompt {
+        prompt,
+        context,
+        timestamp: now.with_timezone(&Utc).to_rfc3339(),
+        priority: priority(context).to_string(),
+    })
+}
+
+fn meeting_prompt(title: &str, start: DateTime<Local>, now: DateTime<Local>) -> SmartPrompt {
+    let minutes = (start - now).num_minutes().max(0);
+    SmartPrompt {
+        prompt: format!(
+            "📅 \"{}\" starts in {} minute{} ({}) - any prep notes?",
+            title,
+            minutes,
+            if minutes == 1 { "" } else { "s" },
+            start.format("%H:%M")
+        ),
+        context: NudgeSlot::Meeting,
+        timestamp: now.with_timezone(&Utc).to_rfc3339(),
+        priority: priority(NudgeSlot::Meeting).to_string(),
+    }
+}
+
+/// What has been nudged about already. Reset at the start of each day.
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    day: Option<NaiveDate>,
+    last_nudge_at: Option<DateTime<Utc>>,
+    slots: HashSet<NudgeSlot>,
+    meetings: HashSet<(String, String)>,
+}
+
+impl Scheduler {
+    /// The nudges due at `now`: a time-slot prompt, then upcoming meetings.
+    pub async fn due(&mut self, db: &Database, now: DateTime<Local>) -> Result<Vec<SmartPrompt>, String> {
+        let today = now.date_naive();
+        if self.day != Some(today) {
+            self.day = Some(today);
+            self.slots.clear();
+            self.meetings.clear();
+        }
+
+        let settings = load_settings(db).await?;
+        if !settings.enabled || settings.quiet_hours.contains(now.hour()) {
+            return Ok(Vec::new());
+        }
+        let calendar = goals::load_calendar(db).await?;
+        if !calendar.is_working_day(today) {
+            return Ok(Vec::new());
+        }
+
+        let mut due = Vec::new();
+        let now_utc = now.with_timezone(&Utc);
+        let rested = self.last_nudge_at
+            .is_none_or(|last| now_utc - last >= settings.frequency.min_interval());
+
+        if let Some(slot) = current_slot(&settings, now).filter(|slot| rested && !self.slots.contains(slot)) {
+            let recent = db.top_projects(Some(now_utc - Duration::days(7)), Some(now_utc), 1)
+                .await
+                .map_err(|e| format!("Failed to get recent projects: {}", e))?;
+            let project = recent.first().map(|p| p.key.as_str());
+
+            self.slots.insert(slot);
+            due.extend(slot_prompt(&settings, slot, project, now));
+        }
+
+        if settings.meeting_nudges {
+            let meetings = db.get_all_meetings()
+                .await
+                .map_err(|e| format!("Failed to get meetings: {}", e))?;
+            let upcoming = commands::expand_occurrences(
+                meetings,
+                now_utc,
+                now_utc + Duration::minutes(MEETING_LEAD_MINUTES),
+            )?;
+
+            for occurrence in upcoming {
+                let key = (occurrence.meeting_id.clone(), occurrence.occurrence_start.clone());
+                if !self.meetings.insert(key) {
+                    continue;
+                }
+                let Ok(start) = DateTime::parse_from_rfc3339(&occurrence.occurrence_start) else {
+                    continue;
+                };
+                due.push(meeting_prompt(&occurrence.title, start.with_timezone(&Local), now));
+            }
+        }
+
+        if !due.is_empty() {
+            self.last_nudge_at = Some(now_utc);
+        }
+        Ok(due)
+    }
+}
+
+/// Notify through the tray and tell an open window. Clicking the tray opens
+/// the quick-entry window, which shows the pending prompt.
+pub async fn fire(app: &AppHandle, pending: &NudgeState, prompt: SmartPrompt) {
+    let title = match prompt.context {
+        NudgeSlot::Meeting => "Meeting soon",
+        NudgeSlot::Weekly => "Weekly review",
+        _ => "ScoBro Logbook",
+    };
+    notifications::show(app, title, &format!("{}\nClick the tray icon to log it.", prompt.prompt));
+    notifications::set_tooltip(app, &format!("{} — {}", notifications::DEFAULT_TOOLTIP, prompt.prompt));
+
+    if let Err(e) = app.emit_all("smart-prompt", prompt.clone()) {
+        eprintln!("Failed to emit smart prompt: {}", e);
+    }
+    *pending.lock().await = Some(prompt);
+}
+
+pub async fn run_scheduler(app: AppHandle, state: AppState, pending: NudgeState) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    let mut scheduler = Scheduler::default();
+    loop {
+        interval.tick().await;
+
+        let db = state.lock().await;
+        let due = scheduler.due(&db, Local::now()).await;
+        drop(db);
+
+        match due {
+            Ok(prompts) => {
+                for prompt in prompts {
+                    fire(&app, &pending, prompt).await;
+                }
+            }
+            Err(e) => eprintln!("Failed to check nudges: {}", e),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+// 1:1 agendas. Everything about one colleague since the last 1:1 with them:
+// open meeting actions they own, what we owe them (our open actions from
+// our 1:1s and Action items mentioning them), and the decisions and notes
+// that mention them. A 1:1 is a meeting of type `one_on_one` they attend.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+
+use crate::database::{EntryItemWithMetadata, MeetingAction};
+
+/// Meeting type of 1:1s.
+pub const ONE_ON_ONE_TYPE: &str = "one_on_one";
+
+/// How far back the agenda reaches when there has been no 1:1 yet.
+pub const DEFAULT_LOOKBACK_DAYS: i64 = 30;
+
+/// Length of a 1:1 created from the agenda.
+pub const DEFAULT_DURATION_MINUTES: i64 = 30;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgendaLine {
+    pub text: String,
+    pub item_id: Option<String>,
+    pub action_id: Option<String>,
+    pub due_date: Option<String>,
+    pub overdue: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OneOnOneAgenda {
+    pub person: String,
+    pub since: DateTime<Utc>,
+    /// Start of the last 1:1, which `since` defaults to.
+    pub last_meeting: Option<DateTime<Utc>>,
+    pub they_own: Vec<AgendaLine>,
+    pub we_owe: Vec<AgendaLine>,
+    pub decisions: Vec<AgendaLine>,
+    pub notes: Vec<AgendaLine>,
+}
+
+fn same_person(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+fn action_line(action: &MeetingAction, meeting_title: &str, now: DateTime<Utc>) -> AgendaLine {
+    let mut text = action.title.clone();
+    if let Some(due) = action.due_date {
+        text.push_str(&format!(" — due {}", due.with_timezone(&Local).format("%a %d %b")));
+    }
+    text.push_str(&format!(" ({})", meeting_title));
+
+    AgendaLine {
+        text,
+        item_id: action.entry_item_id.clone(),
+        action_id: Some(action.id.clone()),
+        due_date: action.due_date.map(|d| d.to_rfc3339()),
+        overdue: action.due_date.is_some_and(|d| d < now),
+    }
+}
+
+fn item_line(timestamp: DateTime<Utc>, item: &EntryItemWithMetadata) -> AgendaLine {
+    let mut text = item.item.content.clone();
+    if let Some(project) = &item.item.project {
+        text.push_str(&format!(" [{}]", project));
+    }
+    text.push_str(&format!(" — {}", timestamp.with_timezone(&Local).format("%a %d %b")));
+
+    AgendaLine {
+        text,
+        item_id: Some(item.item.id.clone()),
+        action_id: None,
+        due_date: None,
+        overdue: false,
+    }
+}
+
+/// Sort what concerns `person` into the agenda. `open_actions` are all open
+/// meeting actions, `one_on_ones` the ids of their 1:1 meetings and `items`
+/// the items mentioning them since `since`, oldest first.
+pub fn build_agenda(
+    person: &str,
+    since: DateTime<Utc>,
+    last_meeting: Option<DateTime<Utc>>,
+    open_actions: &[(MeetingAction, String)],
+    one_on_ones: &HashSet<String>,
+    items: &[(DateTime<Utc>, EntryItemWithMetadata)],
+    now: DateTime<Utc>,
+) -> OneOnOneAgenda {
+    let mut they_own = Vec::new();
+    let mut we_owe = Vec::new();
+    for (action, meeting_title) in open_actions {
+        let theirs = action.assignee.as_deref().is_some_and(|a| same_person(a, person));
+        if theirs {
+            they_own.push(action_line(action, meeting_title, now));
+        } else if one_on_ones.contains(&action.meeting_id) {
+            we_owe.push(action_line(action, meeting_title, now));
+        }
+    }
+
+    let mut decisions = Vec::new();
+    let mut notes = Vec::new();
+    for (timestamp, item) in items {
+        let line = item_line(*timestamp, item);
+        match item.item.item_type.as_str() {
+            "Action" => we_owe.push(line),
+            "Decision" => decisions.push(line),
+            _ => notes.push(line),
+        }
+    }
+
+    OneOnOneAgenda {
+        person: person.to_string(),
+        since,
+        last_meeting,
+        they_own,
+        we_owe,
+        decisions,
+        notes,
+    }
+}
+
+fn push_section(md: &mut String, heading: &str, lines: &[AgendaLine], checkbox: bool) {
+    md.push_str(&format!("## {}\n\n", heading));
+    if lines.is_empty() {
+        md.push_str("_Nothing since last time._\n\n");
+        return;
+    }
+    for line in lines {
+        let marker = if checkbox { "- [ ] " } else { "- " };
+        let flag = if line.overdue { " ⚠️ overdue" } else { "" };
+        md.push_str(&format!("{}{}{}\n", marker, line.text, flag));
+    }
+    md.push('\n');
+}
+
+/// The agenda as Markdown, ending with room for new topics.
+pub fn render_markdown(agenda: &OneOnOneAgenda) -> String {
+    let since = agenda.since.with_timezone(&Local).format("%a %d %b %Y");
+    let mut md = format!("# 1:1 with {}\n\n", agenda.person);
+    match agenda.last_meeting {
+        Some(last) if last == agenda.since => md.push_str(&format!("Since the last 1:1 on {}.\n\n", since)),
+        _ => md.push_str(&format!("Since {}.\n\n", since)),
+    }
+
+    push_section(&mut md, &format!("Actions {} owns", agenda.person), &agenda.they_own, true);
+    push_section(&mut md, &format!("Actions we owe {}", agenda.person), &agenda.we_owe, true);
+    push_section(&mut md, "Decisions", &agenda.decisions, false);
+    push_section(&mut md, "Notes", &agenda.notes, false);
+    md.push_str("## Topics\n\n- \n");
+    md
+}
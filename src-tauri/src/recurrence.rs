@@ -0,0 +1,488 @@
+// RFC 5545 recurrence rule support for meetings. Only the subset of RRULE
+// that calendar apps commonly emit for standups and 1:1s is implemented:
+// FREQ, INTERVAL, COUNT, UNTIL, BYDAY, BYMONTHDAY and BYMONTH. Expansion
+// happens in the series' own time zone so that a 09:00 standup stays at
+// 09:00 across daylight-saving changes.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+
+/// Upper bound on the number of periods walked while expanding a rule, so a
+/// rule without COUNT or UNTIL can never spin forever.
+const MAX_PERIODS: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+    /// BYDAY entries as (ordinal, weekday); the ordinal is only meaningful for
+    /// MONTHLY rules, e.g. `-1FR` is the last Friday of the month.
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+}
+
+impl RecurrenceRule {
+    /// Parse an RRULE value such as `FREQ=WEEKLY;BYDAY=MO,WE,FR`. A leading
+    /// `RRULE:` prefix is accepted.
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+        let mut by_month_day = Vec::new();
+        let mut by_month = Vec::new();
+
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Malformed RRULE part: {}", part))?;
+
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        other => return Err(format!("Unsupported FREQ: {}", other)),
+                    });
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|i| *i > 0)
+                        .ok_or_else(|| format!("Invalid INTERVAL: {}", value))?;
+                }
+                "COUNT" => {
+                    count = Some(value.parse::<u32>().map_err(|_| format!("Invalid COUNT: {}", value))?);
+                }
+                "UNTIL" => until = Some(parse_until(value)?),
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        by_day.push(parse_by_day(day)?);
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for day in value.split(',') {
+                        let day = day
+                            .parse::<i32>()
+                            .ok()
+                            .filter(|d| *d != 0 && (-31..=31).contains(d))
+                            .ok_or_else(|| format!("Invalid BYMONTHDAY: {}", day))?;
+                        by_month_day.push(day);
+                    }
+                }
+                "BYMONTH" => {
+                    for month in value.split(',') {
+                        let month = month
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|m| (1..=12).contains(m))
+                            .ok_or_else(|| format!("Invalid BYMONTH: {}", month))?;
+                        by_month.push(month);
+                    }
+                }
+                "WKST" => {
+                    if !value.eq_ignore_ascii_case("MO") {
+                        return Err("Only WKST=MO is supported".to_string());
+                    }
+                }
+                other => return Err(format!("Unsupported RRULE part: {}", other)),
+            }
+        }
+
+        let freq = freq.ok_or_else(|| "RRULE is missing FREQ".to_string())?;
+        if count.is_some() && until.is_some() {
+            return Err("RRULE cannot have both COUNT and UNTIL".to_string());
+        }
+        if by_day.iter().any(|(n, _)| n.is_some()) && freq != Frequency::Monthly {
+            return Err("Ordinal BYDAY values are only supported with FREQ=MONTHLY".to_string());
+        }
+        if freq == Frequency::Yearly && !by_day.is_empty() {
+            return Err("BYDAY is not supported with FREQ=YEARLY".to_string());
+        }
+        if freq == Frequency::Weekly && !by_month_day.is_empty() {
+            return Err("BYMONTHDAY cannot be used with FREQ=WEEKLY".to_string());
+        }
+
+        Ok(RecurrenceRule {
+            freq,
+            interval,
+            count,
+            until,
+            by_day,
+            by_month_day,
+            by_month,
+        })
+    }
+
+    /// Occurrence start times of a series beginning at `dtstart` that fall
+    /// inside `[range_start, range_end]`, skipping any `exceptions` (EXDATEs).
+    /// COUNT is applied from `dtstart`, so occurrences before the range still
+    /// use up the count.
+    pub fn occurrences_between<Tz: TimeZone>(
+        &self,
+        dtstart: &DateTime<Tz>,
+        range_start: DateTime<Utc>,
+        range_end: DateTime<Utc>,
+        exceptions: &[DateTime<Utc>],
+    ) -> Vec<DateTime<Utc>> {
+        let tz = dtstart.timezone();
+        let start_local = dtstart.naive_local();
+        let time = start_local.time();
+        let start_utc = dtstart.with_timezone(&Utc);
+
+        let mut occurrences = Vec::new();
+        let mut emitted = 0u32;
+
+        for period in 0..MAX_PERIODS {
+            let Some(mut candidates) = self.period_dates(start_local.date(), period) else {
+                break;
+            };
+            candidates.sort();
+
+            for date in candidates {
+                let Some(occurrence) = resolve_local(&tz, date.and_time(time)) else {
+                    continue;
+                };
+                if occurrence < start_utc {
+                    continue;
+                }
+                if self.until.is_some_and(|until| occurrence > until) || occurrence > range_end {
+                    return occurrences;
+                }
+                if self.count.is_some_and(|count| emitted >= count) {
+                    return occurrences;
+                }
+
+                emitted += 1;
+                if occurrence >= range_start && !exceptions.contains(&occurrence) {
+                    occurrences.push(occurrence);
+                }
+            }
+        }
+
+        occurrences
+    }
+
+    /// Candidate dates for the `period`-th interval after the one holding `start`,
+    /// or `None` once the period lies beyond the dates chrono can represent.
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = i64::from(period) * i64::from(self.interval);
+
+        match self.freq {
+            Frequency::Daily => {
+                let date = start.checked_add_signed(Duration::try_days(step)?)?;
+                Some(if self.matches_filters(date) { vec![date] } else { Vec::new() })
+            }
+            Frequency::Weekly => {
+                let week_start = (start - Duration::days(start.weekday().num_days_from_monday() as i64))
+                    .checked_add_signed(Duration::try_weeks(step)?)?;
+                let days: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![start.weekday()]
+                } else {
+                    self.by_day.iter().map(|(_, day)| *day).collect()
+                };
+                Some(days.into_iter()
+                    .filter_map(|day| week_start.checked_add_signed(Duration::days(day.num_days_from_monday() as i64)))
+                    .filter(|date| self.by_month.is_empty() || self.by_month.contains(&date.month()))
+                    .collect())
+            }
+            Frequency::Monthly => {
+                let months = i64::from(start.year()) * 12 + i64::from(start.month0()) + step;
+                let year = i32::try_from(months.div_euclid(12)).ok()?;
+                let month = months.rem_euclid(12) as u32 + 1;
+                NaiveDate::from_ymd_opt(year, month, 1)?;
+                if !self.by_month.is_empty() && !self.by_month.contains(&month) {
+                    return Some(Vec::new());
+                }
+                Some(self.month_dates(year, month, start.day()))
+            }
+            Frequency::Yearly => {
+                let year = i32::try_from(i64::from(start.year()) + step).ok()?;
+                NaiveDate::from_ymd_opt(year, 12, 31)?;
+                // BYMONTHDAY alone repeats in every month of the year
+                let months = if !self.by_month.is_empty() {
+                    self.by_month.clone()
+                } else if !self.by_month_day.is_empty() {
+                    (1..=12).collect()
+                } else {
+                    vec![start.month()]
+                };
+                Some(months
+                    .into_iter()
+                    .flat_map(|month| self.month_dates(year, month, start.day()))
+                    .collect())
+            }
+        }
+    }
+
+    fn month_dates(&self, year: i32, month: u32, default_day: u32) -> Vec<NaiveDate> {
+        let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            return Vec::new();
+        };
+        let days_in_month = days_in_month(year, month);
+
+        if !self.by_month_day.is_empty() {
+            return self
+                .by_month_day
+                .iter()
+                .filter_map(|day| resolve_month_day(*day, days_in_month))
+                .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+                .filter(|date| self.by_day.is_empty() || self.by_day.iter().any(|(_, wd)| *wd == date.weekday()))
+                .collect();
+        }
+
+        if !self.by_day.is_empty() {
+            let mut dates = Vec::new();
+            for (ordinal, weekday) in &self.by_day {
+                let matching: Vec<NaiveDate> = (0..days_in_month)
+                    .map(|offset| first + Duration::days(offset as i64))
+                    .filter(|date| date.weekday() == *weekday)
+                    .collect();
+                match ordinal {
+                    None => dates.extend(matching),
+                    Some(n) if *n > 0 => dates.extend(matching.get(*n as usize - 1)),
+                    Some(n) => dates.extend(matching.len().checked_sub(n.unsigned_abs() as usize).and_then(|i| matching.get(i))),
+                }
+            }
+            return dates;
+        }
+
+        // Months without the series' day of month (e.g. the 31st) are skipped,
+        // as RFC 5545 requires.
+        NaiveDate::from_ymd_opt(year, month, default_day).into_iter().collect()
+    }
+
+    fn matches_filters(&self, date: NaiveDate) -> bool {
+        let days_in_month = days_in_month(date.year(), date.month());
+        (self.by_day.is_empty() || self.by_day.iter().any(|(_, day)| *day == date.weekday()))
+            && (self.by_month_day.is_empty()
+                || self.by_month_day.iter().any(|day| resolve_month_day(*day, days_in_month) == Some(date.day())))
+            && (self.by_month.is_empty() || self.by_month.contains(&date.month()))
+    }
+}
+
+/// The day a BYMONTHDAY value names in a month of `days_in_month` days;
+/// negative values count back from the end, `-1` being the last day.
+fn resolve_month_day(day: i32, days_in_month: u32) -> Option<u32> {
+    let day = if day > 0 { day } else { days_in_month as i32 + day + 1 };
+    u32::try_from(day).ok().filter(|d| (1..=days_in_month).contains(d))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(28)
+}
+
+/// Map a wall-clock time back to UTC. Times that fall into a DST gap are
+/// skipped; ambiguous times resolve to the earlier instant.
+fn resolve_local<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&local).earliest().map(|dt| dt.with_timezone(&Utc))
+}
+
+fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Some(naive) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(naive, "%Y%m%dT%H%M%S")
+            .map(|dt| Utc.from_utc_datetime(&dt))
+            .map_err(|_| format!("Invalid UNTIL: {}", value));
+    }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        return Ok(Utc.from_utc_datetime(&dt));
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .map(|date| Utc.from_utc_datetime(&date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap())))
+        .map_err(|_| format!("Invalid UNTIL: {}", value))
+}
+
+fn parse_by_day(value: &str) -> Result<(Option<i32>, Weekday), String> {
+    let value = value.trim().to_ascii_uppercase();
+    if value.len() < 2 {
+        return Err(format!("Invalid BYDAY: {}", value));
+    }
+    let (ordinal, day) = value.split_at(value.len() - 2);
+    let weekday = match day {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return Err(format!("Invalid BYDAY: {}", value)),
+    };
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        Some(
+            ordinal
+                .trim_start_matches('+')
+                .parse::<i32>()
+                .ok()
+                .filter(|n| *n != 0 && (-5..=5).contains(n))
+                .ok_or_else(|| format!("Invalid BYDAY: {}", value))?,
+        )
+    };
+    Ok((ordinal, weekday))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    /// Occurrences of `rule` from `start` (UTC) through the end of 2027.
+    fn expand(rule: &str, start: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        RecurrenceRule::parse(rule)
+            .unwrap()
+            .occurrences_between(&start, start, utc(2027, 12, 31, 23, 59), &[])
+    }
+
+    fn dates(occurrences: &[DateTime<Utc>]) -> Vec<String> {
+        occurrences.iter().map(|o| o.format("%Y-%m-%d").to_string()).collect()
+    }
+
+    #[test]
+    fn parses_a_prefixed_rule() {
+        let rule = RecurrenceRule::parse("RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR").unwrap();
+        assert_eq!(rule.freq, Frequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_day, vec![(None, Weekday::Mon), (None, Weekday::Fri)]);
+    }
+
+    #[test]
+    fn rejects_unsupported_combinations() {
+        for rule in [
+            "INTERVAL=2",
+            "FREQ=HOURLY",
+            "FREQ=DAILY;COUNT=3;UNTIL=20261231",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=YEARLY;BYDAY=MO",
+            "FREQ=WEEKLY;BYMONTHDAY=1",
+            "FREQ=MONTHLY;BYMONTHDAY=0",
+            "FREQ=MONTHLY;BYDAY=6MO",
+            "FREQ=DAILY;INTERVAL=0",
+        ] {
+            assert!(RecurrenceRule::parse(rule).is_err(), "{} should be rejected", rule);
+        }
+    }
+
+    #[test]
+    fn weekly_by_day_with_count() {
+        // Monday 2026-10-05
+        let occurrences = expand("FREQ=WEEKLY;BYDAY=MO,WE,FR;COUNT=5", utc(2026, 10, 5, 9, 0));
+        assert_eq!(dates(&occurrences), ["2026-10-05", "2026-10-07", "2026-10-09", "2026-10-12", "2026-10-14"]);
+    }
+
+    #[test]
+    fn weekly_interval_skips_weeks() {
+        let occurrences = expand("FREQ=WEEKLY;INTERVAL=2;COUNT=3", utc(2026, 10, 5, 9, 0));
+        assert_eq!(dates(&occurrences), ["2026-10-05", "2026-10-19", "2026-11-02"]);
+    }
+
+    #[test]
+    fn monthly_ordinal_by_day() {
+        let occurrences = expand("FREQ=MONTHLY;BYDAY=2TU;COUNT=3", utc(2026, 10, 1, 9, 0));
+        assert_eq!(dates(&occurrences), ["2026-10-13", "2026-11-10", "2026-12-08"]);
+    }
+
+    #[test]
+    fn monthly_negative_ordinal_by_day() {
+        let occurrences = expand("FREQ=MONTHLY;BYDAY=-1FR;COUNT=3", utc(2026, 10, 1, 9, 0));
+        assert_eq!(dates(&occurrences), ["2026-10-30", "2026-11-27", "2026-12-25"]);
+    }
+
+    #[test]
+    fn monthly_on_the_31st_skips_shorter_months() {
+        let occurrences = expand("FREQ=MONTHLY;COUNT=4", utc(2026, 1, 31, 9, 0));
+        assert_eq!(dates(&occurrences), ["2026-01-31", "2026-03-31", "2026-05-31", "2026-07-31"]);
+    }
+
+    #[test]
+    fn negative_month_day_is_the_last_day() {
+        let monthly = expand("FREQ=MONTHLY;BYMONTHDAY=-1;COUNT=3", utc(2026, 1, 1, 9, 0));
+        assert_eq!(dates(&monthly), ["2026-01-31", "2026-02-28", "2026-03-31"]);
+        let daily = expand("FREQ=DAILY;BYMONTHDAY=-1;COUNT=3", utc(2026, 1, 1, 9, 0));
+        assert_eq!(dates(&daily), ["2026-01-31", "2026-02-28", "2026-03-31"]);
+    }
+
+    #[test]
+    fn yearly_by_month_day_repeats_every_month() {
+        let occurrences = expand("FREQ=YEARLY;BYMONTHDAY=15;COUNT=4", utc(2026, 10, 1, 9, 0));
+        assert_eq!(dates(&occurrences), ["2026-10-15", "2026-11-15", "2026-12-15", "2027-01-15"]);
+        let by_month = expand("FREQ=YEARLY;BYMONTH=3;BYMONTHDAY=1;COUNT=2", utc(2026, 1, 1, 9, 0));
+        assert_eq!(dates(&by_month), ["2026-03-01", "2027-03-01"]);
+    }
+
+    #[test]
+    fn until_is_inclusive() {
+        let occurrences = expand("FREQ=DAILY;UNTIL=20261007T090000Z", utc(2026, 10, 5, 9, 0));
+        assert_eq!(dates(&occurrences), ["2026-10-05", "2026-10-06", "2026-10-07"]);
+        let date_only = expand("FREQ=DAILY;UNTIL=20261006", utc(2026, 10, 5, 9, 0));
+        assert_eq!(dates(&date_only), ["2026-10-05", "2026-10-06"]);
+    }
+
+    #[test]
+    fn count_includes_occurrences_before_the_range() {
+        let rule = RecurrenceRule::parse("FREQ=DAILY;COUNT=5").unwrap();
+        let start = utc(2026, 10, 5, 9, 0);
+        let occurrences = rule.occurrences_between(&start, utc(2026, 10, 8, 0, 0), utc(2026, 12, 31, 0, 0), &[]);
+        assert_eq!(dates(&occurrences), ["2026-10-08", "2026-10-09"]);
+    }
+
+    #[test]
+    fn exdates_are_skipped_but_still_counted() {
+        let rule = RecurrenceRule::parse("FREQ=DAILY;COUNT=3").unwrap();
+        let start = utc(2026, 10, 5, 9, 0);
+        let occurrences = rule.occurrences_between(&start, start, utc(2026, 12, 31, 0, 0), &[utc(2026, 10, 6, 9, 0)]);
+        assert_eq!(dates(&occurrences), ["2026-10-05", "2026-10-07"]);
+    }
+
+    #[test]
+    fn local_start_time_holds_across_dst() {
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;COUNT=3").unwrap();
+        // 09:00 in New York on the Thursdays around the 2026-03-08 change
+        let start = New_York.with_ymd_and_hms(2026, 3, 5, 9, 0, 0).unwrap();
+        let occurrences = rule.occurrences_between(&start, utc(2026, 1, 1, 0, 0), utc(2026, 12, 31, 0, 0), &[]);
+        assert_eq!(occurrences, vec![utc(2026, 3, 5, 14, 0), utc(2026, 3, 12, 13, 0), utc(2026, 3, 19, 13, 0)]);
+    }
+
+    #[test]
+    fn times_in_a_dst_gap_are_skipped() {
+        let rule = RecurrenceRule::parse("FREQ=DAILY;COUNT=3").unwrap();
+        let start = New_York.with_ymd_and_hms(2026, 3, 7, 2, 30, 0).unwrap();
+        let occurrences = rule.occurrences_between(&start, utc(2026, 1, 1, 0, 0), utc(2026, 12, 31, 0, 0), &[]);
+        assert_eq!(occurrences, vec![utc(2026, 3, 7, 7, 30), utc(2026, 3, 9, 6, 30), utc(2026, 3, 10, 6, 30)]);
+    }
+
+    #[test]
+    fn rule_without_end_stops_at_the_range() {
+        let occurrences = RecurrenceRule::parse("FREQ=YEARLY")
+            .unwrap()
+            .occurrences_between(&utc(2026, 2, 28, 9, 0), utc(2026, 1, 1, 0, 0), utc(2030, 1, 1, 0, 0), &[]);
+        assert_eq!(occurrences.len(), 4);
+    }
+}
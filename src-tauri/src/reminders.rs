@@ -0,0 +1,321 @@
+// Due-date reminders. Open meeting actions with a due date and items
+// waiting on someone by an expected date are reminded about the day
+// before, on the morning they are due and once they are overdue, each at
+// most once per due date. What was sent, snoozed or
+// dismissed lives in `reminder_states`, so a restart does not repeat
+// anything; moving a due date starts the reminders over.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::AppState;
+use crate::database::{Database, ReminderState};
+use crate::notifications;
+
+/// Key of the reminder settings in the `settings` table.
+pub const REMINDER_SETTINGS_KEY: &str = "reminders";
+
+const CHECK_INTERVAL_SECS: u64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderOffset {
+    DayBefore,
+    MorningOf,
+    Overdue,
+}
+
+impl ReminderOffset {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReminderOffset::DayBefore => "day_before",
+            ReminderOffset::MorningOf => "morning_of",
+            ReminderOffset::Overdue => "overdue",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderTarget {
+    MeetingAction,
+    WaitingFor,
+}
+
+impl ReminderTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReminderTarget::MeetingAction => "meeting_action",
+            ReminderTarget::WaitingFor => "waiting_for",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "meeting_action" => Ok(ReminderTarget::MeetingAction),
+            "waiting_for" => Ok(ReminderTarget::WaitingFor),
+            other => Err(format!("Unknown reminder target: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReminderSettings {
+    pub enabled: bool,
+    pub day_before: bool,
+    pub morning_of: bool,
+    pub overdue: bool,
+    /// Local hour from which the day's reminders go out.
+    pub hour: u32,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        ReminderSettings {
+            enabled: true,
+            day_before: true,
+            morning_of: true,
+            overdue: true,
+            hour: 9,
+        }
+    }
+}
+
+impl ReminderSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.hour > 23 {
+            return Err("The reminder hour must be between 0 and 23".to_string());
+        }
+        Ok(())
+    }
+
+    fn includes(&self, offset: ReminderOffset) -> bool {
+        match offset {
+            ReminderOffset::DayBefore => self.day_before,
+            ReminderOffset::MorningOf => self.morning_of,
+            ReminderOffset::Overdue => self.overdue,
+        }
+    }
+}
+
+pub async fn load_settings(db: &Database) -> Result<ReminderSettings, String> {
+    let stored = db.get_setting(REMINDER_SETTINGS_KEY)
+        .await
+        .map_err(|e| format!("Failed to get reminder settings: {}", e))?;
+
+    match stored {
+        Some(json) => serde_json::from_str(&json).map_err(|e| format!("Invalid reminder settings: {}", e)),
+        None => Ok(ReminderSettings::default()),
+    }
+}
+
+/// Where `today` stands relative to the local day something is due.
+pub fn offset_on(due_day: NaiveDate, today: NaiveDate) -> Option<ReminderOffset> {
+    if today > due_day {
+        Some(ReminderOffset::Overdue)
+    } else if today == due_day {
+        Some(ReminderOffset::MorningOf)
+    } else if today + Duration::days(1) == due_day {
+        Some(ReminderOffset::DayBefore)
+    } else {
+        None
+    }
+}
+
+/// Something with a due date, together with its reminder state.
+#[derive(Debug, Clone, Serialize)]
+pub struct Reminder {
+    pub target_kind: ReminderTarget,
+    pub target_id: String,
+    pub title: String,
+    /// The meeting an action came from, or who an item is waiting on.
+    pub context: Option<String>,
+    pub assignee: Option<String>,
+    pub due_date: String,
+    pub offset: Option<ReminderOffset>,
+    pub sent_offsets: Vec<String>,
+    pub snoozed_until: Option<String>,
+    pub dismissed: bool,
+}
+
+/// Everything open with a due date as of `now`, paired with its state.
+/// States are fresh for targets never reminded about and for moved due dates.
+pub async fn collect(db: &Database, now: DateTime<Utc>) -> Result<Vec<(Reminder, ReminderState)>, String> {
+    let actions = db.get_open_actions_with_due_dates()
+        .await
+        .map_err(|e| format!("Failed to get actions: {}", e))?;
+    let waiting = db.get_waiting_for(false)
+        .await
+        .map_err(|e| format!("Failed to get waiting-for items: {}", e))?;
+    let mut states: HashMap<(String, String), ReminderState> = db.get_reminder_states()
+        .await
+        .map_err(|e| format!("Failed to get reminder states: {}", e))?
+        .into_iter()
+        .map(|state| ((state.target_kind.clone(), state.target_id.clone()), state))
+        .collect();
+
+    let mut targets = Vec::new();
+    for (action, meeting_title) in actions {
+        if let Some(due_date) = action.due_date {
+            targets.push((ReminderTarget::MeetingAction, action.id, action.title, meeting_title, action.assignee, due_date));
+        }
+    }
+    for (record, item, _) in waiting {
+        if let Some(expected_by) = record.expected_by {
+            let context = format!("Waiting on {}", record.person_name);
+            targets.push((ReminderTarget::WaitingFor, item.id, item.content, context, Some(record.person_name), expected_by));
+        }
+    }
+
+    let today = now.with_timezone(&Local).date_naive();
+    let mut collected = Vec::new();
+    for (kind, target_id, title, context, assignee, due_date) in targets {
+        let state = states.remove(&(kind.as_str().to_string(), target_id.clone()))
+            .filter(|state| state.due_date == due_date)
+            .unwrap_or_else(|| ReminderState {
+                target_kind: kind.as_str().to_string(),
+                target_id: target_id.clone(),
+                due_date,
+                sent_offsets: Vec::new(),
+                snoozed_until: None,
+                dismissed: false,
+                updated_at: Utc::now(),
+            });
+
+        let reminder = Reminder {
+            target_kind: kind,
+            target_id,
+            title,
+            context: Some(context),
+            assignee,
+            due_date: due_date.to_rfc3339(),
+            offset: offset_on(due_date.with_timezone(&Local).date_naive(), today),
+            sent_offsets: state.sent_offsets.clone(),
+            snoozed_until: state.snoozed_until.map(|t| t.to_rfc3339()),
+            dismissed: state.dismissed,
+        };
+        collected.push((reminder, state));
+    }
+
+    Ok(collected)
+}
+
+/// Whether `reminder` should go out at `now`. An expired snooze reminds
+/// again whatever was sent before; otherwise each enabled offset goes out
+/// once, from the configured hour on.
+pub fn is_due(settings: &ReminderSettings, reminder: &Reminder, state: &ReminderState, now: DateTime<Utc>) -> bool {
+    if !settings.enabled || state.dismissed {
+        return false;
+    }
+    match state.snoozed_until {
+        Some(until) if until > now => return false,
+        Some(_) => return true,
+        None => {}
+    }
+    if now.with_timezone(&Local).hour() < settings.hour {
+        return false;
+    }
+    reminder.offset.is_some_and(|offset| {
+        settings.includes(offset) && !state.sent_offsets.iter().any(|sent| sent == offset.as_str())
+    })
+}
+
+/// Reminders due at `now`, marked as sent.
+pub async fn due_reminders(db: &Database, now: DateTime<Utc>) -> Result<Vec<Reminder>, String> {
+    let settings = load_settings(db).await?;
+    let mut due = Vec::new();
+
+    for (mut reminder, mut state) in collect(db, now).await? {
+        if !is_due(&settings, &reminder, &state, now) {
+            continue;
+        }
+
+        state.snoozed_until = None;
+        if let Some(offset) = reminder.offset {
+            if !state.sent_offsets.iter().any(|sent| sent == offset.as_str()) {
+                state.sent_offsets.push(offset.as_str().to_string());
+            }
+        }
+        db.save_reminder_state(&state)
+            .await
+            .map_err(|e| format!("Failed to save reminder state: {}", e))?;
+
+        reminder.sent_offsets = state.sent_offsets;
+        reminder.snoozed_until = None;
+        due.push(reminder);
+    }
+
+    Ok(due)
+}
+
+fn describe(reminder: &Reminder) -> String {
+    let due = DateTime::parse_from_rfc3339(&reminder.due_date)
+        .map(|d| d.with_timezone(&Local).format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| reminder.due_date.clone());
+    let when = match reminder.offset {
+        Some(ReminderOffset::DayBefore) => "due tomorrow".to_string(),
+        Some(ReminderOffset::MorningOf) => "due today".to_string(),
+        Some(ReminderOffset::Overdue) => format!("overdue since {}", due),
+        None => format!("due {}", due),
+    };
+
+    match reminder.target_kind {
+        ReminderTarget::MeetingAction => {
+            let mut text = format!("{} — {}", reminder.title, when);
+            if let Some(assignee) = &reminder.assignee {
+                text.push_str(&format!(" ({})", assignee));
+            }
+            text
+        }
+        ReminderTarget::WaitingFor => {
+            let person = reminder.assignee.as_deref().unwrap_or("someone");
+            format!("Follow up with {}: {} — {}", person, reminder.title, when)
+        }
+    }
+}
+
+/// One notification for everything due, so a restart after a long break
+/// does not flood the desktop.
+pub fn notify(app: &AppHandle, reminders: &[Reminder]) {
+    let title = match reminders {
+        [] => return,
+        [single] => {
+            let what = match single.target_kind {
+                ReminderTarget::MeetingAction => "Action",
+                ReminderTarget::WaitingFor => "Follow-up",
+            };
+            match single.offset {
+                Some(ReminderOffset::Overdue) => format!("{} overdue", what),
+                Some(ReminderOffset::MorningOf) => format!("{} due today", what),
+                _ => format!("{} due soon", what),
+            }
+        }
+        _ => format!("{} reminders need attention", reminders.len()),
+    };
+    let body = reminders.iter().map(describe).collect::<Vec<_>>().join("\n");
+
+    notifications::show(app, &title, &body);
+    if let Err(e) = app.emit_all("reminders-due", reminders.to_vec()) {
+        eprintln!("Failed to emit reminders: {}", e);
+    }
+}
+
+pub async fn run_reminders(app: AppHandle, state: AppState) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+
+        let db = state.lock().await;
+        let due = due_reminders(&db, Utc::now()).await;
+        drop(db);
+
+        match due {
+            Ok(reminders) => notify(&app, &reminders),
+            Err(e) => eprintln!("Failed to check reminders: {}", e),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+// Daily standup notes: what was logged since the last working day, which
+// actions are due today and what is blocked. "Yesterday" is the previous
+// working day in the working-days calendar, running up to the standup day,
+// so Monday's standup covers Friday and anything logged over the weekend.
+// The text is plain bullets so it pastes cleanly into any chat client.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::database::{EntryItemWithMetadata, MeetingAction};
+use crate::goals::WorkCalendar;
+
+/// Tags that mark an item as a blocker, compared case-insensitively.
+pub const BLOCKER_TAGS: [&str; 3] = ["blocker", "blockers", "blocked"];
+
+/// How far back to look for a working day before giving up on the calendar.
+const MAX_LOOKBACK_DAYS: i64 = 31;
+
+/// The working day before `day`, or the calendar day before if the calendar
+/// has no working day in the last month.
+pub fn previous_working_day(calendar: &WorkCalendar, day: NaiveDate) -> NaiveDate {
+    (1..=MAX_LOOKBACK_DAYS)
+        .map(|offset| day - Duration::days(offset))
+        .find(|candidate| calendar.is_working_day(*candidate))
+        .unwrap_or(day - Duration::days(1))
+}
+
+pub fn is_blocker(item: &EntryItemWithMetadata) -> bool {
+    item.tags.iter().any(|tag| BLOCKER_TAGS.iter().any(|b| tag.name.eq_ignore_ascii_case(b)))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StandupLine {
+    pub text: String,
+    pub item_id: Option<String>,
+    pub action_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Standup {
+    pub date: NaiveDate,
+    /// First day covered by "Yesterday".
+    pub since: NaiveDate,
+    pub yesterday: Vec<StandupLine>,
+    pub today: Vec<StandupLine>,
+    pub blockers: Vec<StandupLine>,
+}
+
+fn describe_item(item: &EntryItemWithMetadata) -> String {
+    let mut text = match item.item.item_type.as_str() {
+        "Decision" => format!("Decided: {}", item.item.content),
+        "Action" => format!("Action: {}", item.item.content),
+        "Meeting" => format!("Meeting: {}", item.item.content),
+        _ => item.item.content.clone(),
+    };
+    if let Some(project) = &item.item.project {
+        text.push_str(&format!(" [{}]", project));
+    }
+    text
+}
+
+/// Build the standup for `date`. `items` are those logged from the start of
+/// `since` to the end of `date`, oldest first; `actions` are open actions
+/// with due dates and the meeting they came from.
+pub fn build_standup(
+    date: NaiveDate,
+    since: NaiveDate,
+    items: &[(DateTime<Utc>, EntryItemWithMetadata)],
+    actions: &[(MeetingAction, String)],
+) -> Standup {
+    let mut yesterday = Vec::new();
+    let mut blockers = Vec::new();
+    for (timestamp, item) in items {
+        let line = StandupLine {
+            text: describe_item(item),
+            item_id: Some(item.item.id.clone()),
+            action_id: None,
+        };
+        if is_blocker(item) {
+            blockers.push(line);
+        } else if timestamp.with_timezone(&Local).date_naive() < date {
+            yesterday.push(line);
+        }
+    }
+
+    let today = actions
+        .iter()
+        .filter_map(|(action, meeting_title)| {
+            let due_day = action.due_date?.with_timezone(&Local).date_naive();
+            if due_day > date {
+                return None;
+            }
+            let mut text = action.title.clone();
+            if due_day < date {
+                text.push_str(&format!(" (overdue since {})", due_day.format("%a %d %b")));
+            }
+            if let Some(assignee) = &action.assignee {
+                text.push_str(&format!(" — {}", assignee));
+            }
+            text.push_str(&format!(" [{}]", meeting_title));
+            Some(StandupLine {
+                text,
+                item_id: None,
+                action_id: Some(action.id.clone()),
+            })
+        })
+        .collect();
+
+    Standup { date, since, yesterday, today, blockers }
+}
+
+fn push_section(text: &mut String, heading: &str, lines: &[StandupLine], empty: &str) {
+    text.push_str(heading);
+    text.push('\n');
+    if lines.is_empty() {
+        text.push_str(&format!("- {}\n", empty));
+    }
+    for line in lines {
+        text.push_str(&format!("- {}\n", line.text));
+    }
+}
+
+/// Plain text with one bullet per line, ready to paste into chat.
+pub fn render_text(standup: &Standup) -> String {
+    let yesterday = if standup.since + Duration::days(1) == standup.date {
+        "Yesterday:".to_string()
+    } else {
+        format!("Since {}:", standup.since.format("%A"))
+    };
+
+    let mut text = format!("Standup {}\n\n", standup.date.format("%a %d %b %Y"));
+    push_section(&mut text, &yesterday, &standup.yesterday, "Nothing logged");
+    text.push('\n');
+    push_section(&mut text, "Today:", &standup.today, "No actions due");
+    text.push('\n');
+    push_section(&mut text, "Blockers:", &standup.blockers, "None");
+    text
+}
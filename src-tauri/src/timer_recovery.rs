@@ -0,0 +1,199 @@
+// Keeps the running timer honest across app restarts and machine sleep.
+// While a timer runs, a background task stamps it with a heartbeat. A gap
+// between the last heartbeat and "now" that is longer than a few missed
+// beats means the app was closed or the machine slept, and the user decides
+// whether that time counts. The decision logic below is plain data in, plan
+// out, so it does not need a window or a database to reason about.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use crate::commands::AppState;
+use crate::database::TimeEntry;
+
+/// How often the running timer is stamped.
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 60;
+
+/// Gaps longer than this are reported instead of silently counted.
+pub fn idle_threshold() -> Duration {
+    Duration::minutes(5)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleGap {
+    pub last_heartbeat: DateTime<Utc>,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl IdleGap {
+    pub fn duration(&self) -> Duration {
+        self.detected_at - self.last_heartbeat
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GapDecision {
+    /// Count the gap and keep the timer running.
+    Keep,
+    /// Drop the gap and stop the timer at the last heartbeat.
+    Trim,
+    /// Drop the gap, stop at the last heartbeat and resume tracking now.
+    Split,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecoveryPlan {
+    /// Leave the timer running and refresh its heartbeat.
+    Continue { heartbeat: DateTime<Utc> },
+    /// Stop the timer at `end`.
+    Stop { end: DateTime<Utc> },
+    /// Stop the timer at `end` and start an identical one at `restart_at`.
+    StopAndRestart { end: DateTime<Utc>, restart_at: DateTime<Utc> },
+}
+
+/// The idle gap on a running timer, if its last heartbeat is older than
+/// `threshold`. Timers without a heartbeat yet are measured from their start.
+pub fn detect_idle_gap(timer: &TimeEntry, now: DateTime<Utc>, threshold: Duration) -> Option<IdleGap> {
+    if timer.end_time.is_some() {
+        return None;
+    }
+
+    let last_heartbeat = timer.last_heartbeat.unwrap_or(timer.start_time).max(timer.start_time);
+    if now - last_heartbeat > threshold {
+        Some(IdleGap {
+            last_heartbeat,
+            detected_at: now,
+        })
+    } else {
+        None
+    }
+}
+
+pub fn plan_recovery(gap: &IdleGap, decision: GapDecision) -> RecoveryPlan {
+    match decision {
+        GapDecision::Keep => RecoveryPlan::Continue {
+            heartbeat: gap.detected_at,
+        },
+        GapDecision::Trim => RecoveryPlan::Stop {
+            end: gap.last_heartbeat,
+        },
+        GapDecision::Split => RecoveryPlan::StopAndRestart {
+            end: gap.last_heartbeat,
+            restart_at: gap.detected_at,
+        },
+    }
+}
+
+/// Stamp the running timer every `HEARTBEAT_INTERVAL_SECS`. A timer with an
+/// unresolved idle gap is left alone so the gap is still there for the user
+/// to decide on.
+pub async fn run_heartbeat(state: AppState) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(HEARTBEAT_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+
+        let db = state.lock().await;
+        let now = Utc::now();
+        let timer = match db.get_active_timer().await {
+            Ok(Some(timer)) => timer,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("Failed to read active timer for heartbeat: {}", e);
+                continue;
+            }
+        };
+
+        if detect_idle_gap(&timer, now, idle_threshold()).is_some() {
+            continue;
+        }
+        if let Err(e) = db.record_timer_heartbeat(&timer.id, now).await {
+            eprintln!("Failed to record timer heartbeat: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 5, hour, minute, 0).unwrap()
+    }
+
+    fn timer(start: DateTime<Utc>, last_heartbeat: Option<DateTime<Utc>>) -> TimeEntry {
+        TimeEntry {
+            id: "timer".to_string(),
+            project: None,
+            entry_item_id: None,
+            jira_key: None,
+            start_time: start,
+            end_time: None,
+            notes: None,
+            last_heartbeat,
+            person: None,
+            billable: true,
+            invoice_id: None,
+            created_at: start,
+            updated_at: start,
+        }
+    }
+
+    fn gap() -> IdleGap {
+        IdleGap { last_heartbeat: at(10, 0), detected_at: at(11, 30) }
+    }
+
+    #[test]
+    fn no_gap_while_heartbeats_are_recent() {
+        let running = timer(at(9, 0), Some(at(10, 0)));
+        assert_eq!(detect_idle_gap(&running, at(10, 1), idle_threshold()), None);
+    }
+
+    #[test]
+    fn stopped_timers_have_no_gap() {
+        let mut stopped = timer(at(9, 0), Some(at(10, 0)));
+        stopped.end_time = Some(at(10, 0));
+        assert_eq!(detect_idle_gap(&stopped, at(12, 0), idle_threshold()), None);
+    }
+
+    #[test]
+    fn gap_at_or_below_threshold_is_counted() {
+        let running = timer(at(9, 0), Some(at(10, 0)));
+        assert_eq!(detect_idle_gap(&running, at(10, 4), idle_threshold()), None);
+        assert_eq!(detect_idle_gap(&running, at(10, 5), idle_threshold()), None);
+    }
+
+    #[test]
+    fn gap_above_threshold_is_reported() {
+        let running = timer(at(9, 0), Some(at(10, 0)));
+        let gap = detect_idle_gap(&running, at(10, 6), idle_threshold()).unwrap();
+        assert_eq!(gap, IdleGap { last_heartbeat: at(10, 0), detected_at: at(10, 6) });
+        assert_eq!(gap.duration(), Duration::minutes(6));
+    }
+
+    #[test]
+    fn gap_without_heartbeat_is_measured_from_start() {
+        let running = timer(at(9, 0), None);
+        let gap = detect_idle_gap(&running, at(9, 30), idle_threshold()).unwrap();
+        assert_eq!(gap.last_heartbeat, at(9, 0));
+    }
+
+    #[test]
+    fn keep_counts_the_gap_and_refreshes_the_heartbeat() {
+        assert_eq!(plan_recovery(&gap(), GapDecision::Keep), RecoveryPlan::Continue { heartbeat: at(11, 30) });
+    }
+
+    #[test]
+    fn trim_stops_at_the_last_heartbeat() {
+        assert_eq!(plan_recovery(&gap(), GapDecision::Trim), RecoveryPlan::Stop { end: at(10, 0) });
+    }
+
+    #[test]
+    fn split_stops_at_the_last_heartbeat_and_restarts_now() {
+        assert_eq!(
+            plan_recovery(&gap(), GapDecision::Split),
+            RecoveryPlan::StopAndRestart { end: at(10, 0), restart_at: at(11, 30) }
+        );
+    }
+}
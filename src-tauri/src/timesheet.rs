@@ -0,0 +1,403 @@
+// Weekly timesheet aggregation over tracked time entries. Entries are split
+// at local midnight so each day gets the time actually worked on it, and
+// rounding is applied to each entry's share of a day before summing, which
+// is how Toggl and Clockify round as well.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::database::TimeEntry;
+
+pub const DAYS_PER_WEEK: usize = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimesheetGrouping {
+    Project,
+    Jira,
+    ProjectJira,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimesheetRow {
+    pub project: Option<String>,
+    pub jira_key: Option<String>,
+    pub daily_seconds: [i64; DAYS_PER_WEEK],
+    pub total_seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Timesheet {
+    pub week_start: NaiveDate,
+    pub days: Vec<NaiveDate>,
+    pub group_by: TimesheetGrouping,
+    pub rounding_minutes: Option<u32>,
+    pub rows: Vec<TimesheetRow>,
+    pub daily_totals: [i64; DAYS_PER_WEEK],
+    pub total_seconds: i64,
+}
+
+/// One time entry's share of a single day, already rounded.
+#[derive(Debug, Clone)]
+pub struct TimesheetSegment<'a> {
+    pub entry: &'a TimeEntry,
+    pub day: usize,
+    pub start: DateTime<Utc>,
+    pub seconds: i64,
+}
+
+/// Round to the nearest multiple of `rounding_minutes`; halves round up.
+pub fn round_seconds(seconds: i64, rounding_minutes: Option<u32>) -> i64 {
+    match rounding_minutes {
+        Some(minutes) if minutes > 0 => {
+            let step = minutes as i64 * 60;
+            (seconds + step / 2) / step * step
+        }
+        _ => seconds,
+    }
+}
+
+/// Local midnight at the start of each day of the week, plus the midnight
+/// that ends the week.
+pub fn day_boundaries<Tz: TimeZone>(tz: &Tz, week_start: NaiveDate) -> Vec<DateTime<Utc>> {
+    (0..=DAYS_PER_WEEK as i64)
+        .map(|offset| start_of_day(tz, week_start + Duration::days(offset)))
+        .collect()
+}
+
+/// The instant `day` starts in `tz`.
+pub fn start_of_day<Tz: TimeZone>(tz: &Tz, day: NaiveDate) -> DateTime<Utc> {
+    let midnight = day.and_hms_opt(0, 0, 0).unwrap();
+    // Midnight can only be skipped by a DST jump, which moves the clock on by
+    // whole quarter hours; the day starts at the first local time after it.
+    (0..=24 * 4)
+        .find_map(|quarter| tz.from_local_datetime(&(midnight + Duration::minutes(15 * quarter))).earliest())
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&midnight))
+}
+
+/// Split entries at day boundaries and round each piece. Running timers are
+/// counted up to `now`.
+pub fn segments<'a>(
+    entries: &'a [TimeEntry],
+    boundaries: &[DateTime<Utc>],
+    rounding_minutes: Option<u32>,
+    now: DateTime<Utc>,
+) -> Vec<TimesheetSegment<'a>> {
+    let mut result = Vec::new();
+    for entry in entries {
+        let end = entry.end_time.unwrap_or(now);
+        for day in 0..boundaries.len().saturating_sub(1) {
+            let start = entry.start_time.max(boundaries[day]);
+            let stop = end.min(boundaries[day + 1]);
+            if stop <= start {
+                continue;
+            }
+            let seconds = round_seconds((stop - start).num_seconds(), rounding_minutes);
+            if seconds > 0 {
+                result.push(TimesheetSegment { entry, day, start, seconds });
+            }
+        }
+    }
+    result
+}
+
+pub fn build_timesheet(
+    entries: &[TimeEntry],
+    week_start: NaiveDate,
+    group_by: TimesheetGrouping,
+    rounding_minutes: Option<u32>,
+    now: DateTime<Utc>,
+) -> Timesheet {
+    let boundaries = day_boundaries(&Local, week_start);
+    let mut rows: Vec<TimesheetRow> = Vec::new();
+
+    for segment in segments(entries, &boundaries, rounding_minutes, now) {
+        let (project, jira_key) = match group_by {
+            TimesheetGrouping::Project => (segment.entry.project.clone(), None),
+            TimesheetGrouping::Jira => (None, segment.entry.jira_key.clone()),
+            TimesheetGrouping::ProjectJira => (segment.entry.project.clone(), segment.entry.jira_key.clone()),
+        };
+
+        let row = match rows.iter().position(|r| r.project == project && r.jira_key == jira_key) {
+            Some(index) => &mut rows[index],
+            None => {
+                rows.push(TimesheetRow {
+                    project,
+                    jira_key,
+                    daily_seconds: [0; DAYS_PER_WEEK],
+                    total_seconds: 0,
+                });
+                rows.last_mut().unwrap()
+            }
+        };
+        row.daily_seconds[segment.day] += segment.seconds;
+        row.total_seconds += segment.seconds;
+    }
+
+    // Named groups alphabetically, the "no project / no key" row last
+    rows.sort_by(|a, b| {
+        (a.project.is_none(), &a.project, a.jira_key.is_none(), &a.jira_key)
+            .cmp(&(b.project.is_none(), &b.project, b.jira_key.is_none(), &b.jira_key))
+    });
+
+    let mut daily_totals = [0; DAYS_PER_WEEK];
+    for row in &rows {
+        for (total, seconds) in daily_totals.iter_mut().zip(row.daily_seconds) {
+            *total += seconds;
+        }
+    }
+
+    Timesheet {
+        week_start,
+        days: (0..DAYS_PER_WEEK as i64).map(|d| week_start + Duration::days(d)).collect(),
+        group_by,
+        rounding_minutes,
+        total_seconds: daily_totals.iter().sum(),
+        rows,
+        daily_totals,
+    }
+}
+
+/// Decimal hours with two places, the format most timesheet tools accept.
+pub fn format_hours(seconds: i64) -> String {
+    format!("{:.2}", seconds as f64 / 3600.0)
+}
+
+fn format_hms(seconds: i64) -> String {
+    format!("{:02}:{:02}:{:02}", seconds / 3600, (seconds % 3600) / 60, seconds % 60)
+}
+
+fn csv_error(e: impl std::fmt::Display) -> String {
+    format!("Failed to write CSV: {}", e)
+}
+
+fn into_csv_string(writer: csv::Writer<Vec<u8>>) -> Result<String, String> {
+    let bytes = writer.into_inner().map_err(csv_error)?;
+    String::from_utf8(bytes).map_err(csv_error)
+}
+
+fn row_labels(timesheet: &Timesheet, row: &TimesheetRow) -> Vec<String> {
+    let project = row.project.clone().unwrap_or_else(|| "(no project)".to_string());
+    let jira_key = row.jira_key.clone().unwrap_or_else(|| "(no Jira key)".to_string());
+    match timesheet.group_by {
+        TimesheetGrouping::Project => vec![project],
+        TimesheetGrouping::Jira => vec![jira_key],
+        TimesheetGrouping::ProjectJira => vec![project, jira_key],
+    }
+}
+
+fn label_headers(group_by: TimesheetGrouping) -> Vec<&'static str> {
+    match group_by {
+        TimesheetGrouping::Project => vec!["Project"],
+        TimesheetGrouping::Jira => vec!["Jira"],
+        TimesheetGrouping::ProjectJira => vec!["Project", "Jira"],
+    }
+}
+
+pub fn render_csv(timesheet: &Timesheet) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    let mut header: Vec<String> = label_headers(timesheet.group_by).iter().map(|h| h.to_string()).collect();
+    header.extend(timesheet.days.iter().map(|d| d.format("%Y-%m-%d").to_string()));
+    header.push("Total".to_string());
+    writer.write_record(&header).map_err(csv_error)?;
+
+    for row in &timesheet.rows {
+        let mut fields = row_labels(timesheet, row);
+        fields.extend(row.daily_seconds.iter().map(|s| format_hours(*s)));
+        fields.push(format_hours(row.total_seconds));
+        writer.write_record(&fields).map_err(csv_error)?;
+    }
+
+    let mut totals = vec!["Total".to_string()];
+    totals.resize(label_headers(timesheet.group_by).len(), String::new());
+    totals.extend(timesheet.daily_totals.iter().map(|s| format_hours(*s)));
+    totals.push(format_hours(timesheet.total_seconds));
+    writer.write_record(&totals).map_err(csv_error)?;
+
+    into_csv_string(writer)
+}
+
+pub fn render_markdown(timesheet: &Timesheet) -> String {
+    let week_end = timesheet.week_start + Duration::days(DAYS_PER_WEEK as i64 - 1);
+    let mut markdown = format!(
+        "# ⏱ Timesheet {} – {}\n\n",
+        timesheet.week_start.format("%Y-%m-%d"),
+        week_end.format("%Y-%m-%d")
+    );
+    if let Some(minutes) = timesheet.rounding_minutes.filter(|m| *m > 0) {
+        markdown.push_str(&format!("_Rounded to the nearest {} minutes._\n\n", minutes));
+    }
+
+    let headers = label_headers(timesheet.group_by);
+    let mut header: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    header.extend(timesheet.days.iter().map(|d| d.format("%a %d").to_string()));
+    header.push("Total".to_string());
+    markdown.push_str(&format!("| {} |\n", header.join(" | ")));
+    markdown.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+
+    for row in &timesheet.rows {
+        let mut cells: Vec<String> = row_labels(timesheet, row).iter().map(|l| l.replace('|', "\\|")).collect();
+        cells.extend(row.daily_seconds.iter().map(|s| format_hours(*s)));
+        cells.push(format!("**{}**", format_hours(row.total_seconds)));
+        markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+
+    let mut totals = vec!["**Total**".to_string()];
+    totals.resize(headers.len(), String::new());
+    totals.extend(timesheet.daily_totals.iter().map(|s| format!("**{}**", format_hours(*s))));
+    totals.push(format!("**{}**", format_hours(timesheet.total_seconds)));
+    markdown.push_str(&format!("| {} |\n", totals.join(" | ")));
+
+    markdown
+}
+
+/// Detailed per-entry CSV using the column names Toggl Track and Clockify
+/// both recognise on import. Times are local; entries spanning midnight are
+/// exported as one row per day.
+pub fn render_toggl_csv(
+    entries: &[TimeEntry],
+    week_start: NaiveDate,
+    rounding_minutes: Option<u32>,
+    email: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<String, String> {
+    let boundaries = day_boundaries(&Local, week_start);
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["Email", "Project", "Description", "Task", "Start date", "Start time", "Duration", "Tags"])
+        .map_err(csv_error)?;
+
+    for segment in segments(entries, &boundaries, rounding_minutes, now) {
+        let start = segment.start.with_timezone(&Local);
+        let description = segment.entry.notes.as_deref()
+            .or(segment.entry.jira_key.as_deref())
+            .unwrap_or("");
+        let fields = [
+            email.unwrap_or("").to_string(),
+            segment.entry.project.clone().unwrap_or_default(),
+            description.to_string(),
+            segment.entry.jira_key.clone().unwrap_or_default(),
+            start.format("%Y-%m-%d").to_string(),
+            start.format("%H:%M:%S").to_string(),
+            format_hms(segment.seconds),
+            "ScoBro".to_string(),
+        ];
+        writer.write_record(&fields).map_err(csv_error)?;
+    }
+
+    into_csv_string(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::{New_York, Sao_Paulo};
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn entry(start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> TimeEntry {
+        TimeEntry {
+            id: "entry".to_string(),
+            project: None,
+            entry_item_id: None,
+            jira_key: None,
+            start_time: start,
+            end_time: end,
+            notes: None,
+            last_heartbeat: None,
+            person: None,
+            billable: true,
+            invoice_id: None,
+            created_at: start,
+            updated_at: start,
+        }
+    }
+
+    fn seconds_by_day(segments: &[TimesheetSegment]) -> Vec<(usize, i64)> {
+        segments.iter().map(|s| (s.day, s.seconds)).collect()
+    }
+
+    #[test]
+    fn rounding_is_to_the_nearest_step_with_halves_up() {
+        assert_eq!(round_seconds(1234, None), 1234);
+        assert_eq!(round_seconds(1234, Some(0)), 1234);
+        assert_eq!(round_seconds(7 * 60 + 29, Some(15)), 0);
+        assert_eq!(round_seconds(7 * 60 + 30, Some(15)), 15 * 60);
+        assert_eq!(round_seconds(22 * 60 + 29, Some(15)), 15 * 60);
+        assert_eq!(round_seconds(22 * 60 + 30, Some(15)), 30 * 60);
+        assert_eq!(round_seconds(60 * 60, Some(6)), 60 * 60);
+    }
+
+    #[test]
+    fn days_start_at_local_midnight() {
+        assert_eq!(start_of_day(&Utc, date(2026, 10, 5)), utc(2026, 10, 5, 0, 0));
+        assert_eq!(start_of_day(&New_York, date(2026, 1, 5)), utc(2026, 1, 5, 5, 0));
+        assert_eq!(start_of_day(&New_York, date(2026, 7, 6)), utc(2026, 7, 6, 4, 0));
+    }
+
+    #[test]
+    fn day_without_midnight_starts_at_the_first_local_time() {
+        // Clocks in Sao Paulo went from 00:00 straight to 01:00 on 2018-11-04
+        assert_eq!(start_of_day(&Sao_Paulo, date(2018, 11, 4)), utc(2018, 11, 4, 3, 0));
+    }
+
+    #[test]
+    fn dst_changes_make_short_and_long_days() {
+        // Week of Monday 2026-03-02; clocks spring forward on Sunday the 8th
+        let spring = day_boundaries(&New_York, date(2026, 3, 2));
+        assert_eq!(spring.len(), DAYS_PER_WEEK + 1);
+        assert_eq!(spring[6] - spring[5], Duration::hours(24));
+        assert_eq!(spring[7] - spring[6], Duration::hours(23));
+
+        // Week of Monday 2026-10-26; clocks fall back on Sunday 1 November
+        let fall = day_boundaries(&New_York, date(2026, 10, 26));
+        assert_eq!(fall[7] - fall[6], Duration::hours(25));
+    }
+
+    #[test]
+    fn entries_are_split_at_local_midnight() {
+        let boundaries = day_boundaries(&New_York, date(2026, 10, 5));
+        // 22:00 Monday to 02:00 Tuesday New York time
+        let entries = [entry(utc(2026, 10, 6, 2, 0), Some(utc(2026, 10, 6, 6, 0)))];
+        let segments = segments(&entries, &boundaries, None, utc(2026, 10, 12, 0, 0));
+        assert_eq!(seconds_by_day(&segments), vec![(0, 2 * 3600), (1, 2 * 3600)]);
+        assert_eq!(segments[1].start, utc(2026, 10, 6, 4, 0));
+    }
+
+    #[test]
+    fn each_day_share_is_rounded_on_its_own() {
+        let boundaries = day_boundaries(&Utc, date(2026, 10, 5));
+        // 23:50 to 00:10: two ten-minute pieces, each rounding up to 15
+        let entries = [entry(utc(2026, 10, 5, 23, 50), Some(utc(2026, 10, 6, 0, 10)))];
+        let segments = segments(&entries, &boundaries, Some(15), utc(2026, 10, 12, 0, 0));
+        assert_eq!(seconds_by_day(&segments), vec![(0, 15 * 60), (1, 15 * 60)]);
+    }
+
+    #[test]
+    fn entries_across_a_dst_change_count_real_time() {
+        let boundaries = day_boundaries(&New_York, date(2026, 3, 2));
+        // Local 00:00 to 06:00 on 2026-03-08 is five hours
+        let entries = [entry(utc(2026, 3, 8, 5, 0), Some(utc(2026, 3, 8, 10, 0)))];
+        let segments = segments(&entries, &boundaries, None, utc(2026, 3, 9, 0, 0));
+        assert_eq!(seconds_by_day(&segments), vec![(6, 5 * 3600)]);
+    }
+
+    #[test]
+    fn running_timers_count_up_to_now_and_outside_time_is_ignored() {
+        let boundaries = day_boundaries(&Utc, date(2026, 10, 5));
+        let entries = [
+            entry(utc(2026, 10, 7, 9, 0), None),
+            entry(utc(2026, 10, 4, 9, 0), Some(utc(2026, 10, 4, 17, 0))),
+            entry(utc(2026, 10, 11, 23, 0), Some(utc(2026, 10, 12, 1, 0))),
+        ];
+        let segments = segments(&entries, &boundaries, None, utc(2026, 10, 7, 10, 30));
+        assert_eq!(seconds_by_day(&segments), vec![(2, 90 * 60), (6, 3600)]);
+    }
+}
@@ -0,0 +1,92 @@
+// The system tray menu and the quick-entry window. Native notifications in
+// Tauri 1 do not report clicks, so nudges point at the tray: a left click or
+// "New Entry" opens a small always-on-top window for logging right away.
+// Closing the main window only hides it; the app lives in the tray until
+// "Quit", so the scheduler and reminders keep running.
+
+use tauri::{AppHandle, CustomMenuItem, GlobalWindowEvent, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu, SystemTrayMenuItem, WindowBuilder, WindowEvent, WindowUrl};
+
+pub const QUICK_ENTRY_LABEL: &str = "quick-entry";
+const MAIN_WINDOW_LABEL: &str = "main";
+
+pub fn build() -> SystemTray {
+    let menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new("quick-entry".to_string(), "New Entry"))
+        .add_item(CustomMenuItem::new("show".to_string(), "Open Logbook"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("quit".to_string(), "Quit"));
+
+    SystemTray::new().with_menu(menu)
+}
+
+pub fn handle_event(app: &AppHandle, event: SystemTrayEvent) {
+    match event {
+        SystemTrayEvent::LeftClick { .. } => spawn_window(app, open_quick_entry),
+        SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
+            "quick-entry" => spawn_window(app, open_quick_entry),
+            "show" => spawn_window(app, open_main_window),
+            "quit" => app.exit(0),
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+/// Hide the main window instead of closing it.
+pub fn handle_window_event(event: GlobalWindowEvent) {
+    if let WindowEvent::CloseRequested { api, .. } = event.event() {
+        if event.window().label() != MAIN_WINDOW_LABEL {
+            return;
+        }
+        api.prevent_close();
+        if let Err(e) = event.window().hide() {
+            eprintln!("Failed to hide main window: {}", e);
+        }
+    }
+}
+
+/// Windows are created off the event loop thread; building one from inside
+/// an event handler deadlocks on Windows.
+fn spawn_window(app: &AppHandle, open: fn(&AppHandle) -> Result<(), String>) {
+    let app = app.clone();
+    tokio::spawn(async move {
+        if let Err(e) = open(&app) {
+            eprintln!("{}", e);
+        }
+    });
+}
+
+/// Show and focus the main window, creating it again if it was destroyed.
+fn open_main_window(app: &AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_window(MAIN_WINDOW_LABEL) {
+        return window.show()
+            .and_then(|_| window.set_focus())
+            .map_err(|e| format!("Failed to show main window: {}", e));
+    }
+
+    WindowBuilder::new(app, MAIN_WINDOW_LABEL, WindowUrl::default())
+        .title("ScoBro Logbook")
+        .inner_size(900.0, 700.0)
+        .build()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open main window: {}", e))
+}
+
+/// Focus the quick-entry window, creating it on first use. The frontend
+/// renders the entry form alone when the URL asks for this window.
+pub fn open_quick_entry(app: &AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_window(QUICK_ENTRY_LABEL) {
+        return window.show()
+            .and_then(|_| window.set_focus())
+            .map_err(|e| format!("Failed to focus quick entry: {}", e));
+    }
+
+    WindowBuilder::new(app, QUICK_ENTRY_LABEL, WindowUrl::App("index.html?window=quick-entry".into()))
+        .title("New Entry")
+        .inner_size(560.0, 640.0)
+        .always_on_top(true)
+        .center()
+        .build()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open quick entry: {}", e))
+}
@@ -0,0 +1,80 @@
+// Follow-ups on delegated work. An item can be marked as waiting on a
+// person, optionally by an expected date; the list groups what is still
+// outstanding by person and flags what is overdue. Reminders about expected
+// dates go through `reminders` like meeting action due dates do.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+
+use crate::database::{EntryItem, WaitingFor};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WaitingForEntry {
+    pub item_id: String,
+    pub item_type: String,
+    pub content: String,
+    pub project: Option<String>,
+    /// Timestamp of the entry the item was logged in.
+    pub logged_at: String,
+    pub expected_by: Option<String>,
+    pub resolved_at: Option<String>,
+    pub overdue: bool,
+    /// Whole days past the expected date, 0 unless overdue.
+    pub days_overdue: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WaitingForGroup {
+    pub person_id: String,
+    pub person: String,
+    pub overdue: usize,
+    pub entries: Vec<WaitingForEntry>,
+}
+
+/// Whole days `expected_by` is behind the local day of `now`. Something
+/// expected today is not overdue yet.
+pub fn days_overdue(expected_by: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    let expected = expected_by.with_timezone(&Local).date_naive();
+    let today = now.with_timezone(&Local).date_naive();
+    (today - expected).num_days().max(0)
+}
+
+/// Group by person, people with the most overdue first and then by name.
+/// Entries keep the order given, soonest expected first.
+pub fn group_by_person(rows: Vec<(WaitingFor, EntryItem, DateTime<Utc>)>, now: DateTime<Utc>) -> Vec<WaitingForGroup> {
+    let mut groups: BTreeMap<String, WaitingForGroup> = BTreeMap::new();
+    for (waiting, item, logged_at) in rows {
+        let days = match (waiting.expected_by, waiting.resolved_at) {
+            (Some(expected_by), None) => days_overdue(expected_by, now),
+            _ => 0,
+        };
+        let group = groups.entry(waiting.person_id.clone()).or_insert_with(|| WaitingForGroup {
+            person_id: waiting.person_id.clone(),
+            person: waiting.person_name.clone(),
+            overdue: 0,
+            entries: Vec::new(),
+        });
+        if days > 0 {
+            group.overdue += 1;
+        }
+        group.entries.push(WaitingForEntry {
+            item_id: item.id,
+            item_type: item.item_type,
+            content: item.content,
+            project: item.project,
+            logged_at: logged_at.to_rfc3339(),
+            expected_by: waiting.expected_by.map(|t| t.to_rfc3339()),
+            resolved_at: waiting.resolved_at.map(|t| t.to_rfc3339()),
+            overdue: days > 0,
+            days_overdue: days,
+        });
+    }
+
+    let mut groups: Vec<WaitingForGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| {
+        b.overdue.cmp(&a.overdue).then_with(|| a.person.to_lowercase().cmp(&b.person.to_lowercase()))
+    });
+    groups
+}
@@ -56,14 +56,36 @@ export default function App() {
     setupSmartPrompts();
   }, []);
 
-  // Set up smart prompts nudges
+  // Smart prompt nudges are scheduled by the backend, which also shows them
+  // as native notifications while the window is closed
   useEffect(() => {
-    const interval = setInterval(() => {
-      checkForSmartPromptNudge();
-    }, 60000); // Check every minute
+    let unlisten;
+    if (typeof listen === 'function') {
+      listen('smart-prompt', (event) => {
+        setSmartPromptNudge(event.payload);
+      }).then((unlistenFn) => {
+        unlisten = unlistenFn;
+      });
+    }
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, []);
 
-    return () => clearInterval(interval);
-  }, [entries]);
+  // Entries saved from the quick-entry window
+  useEffect(() => {
+    let unlisten;
+    if (typeof listen === 'function') {
+      listen('entry-created', () => {
+        loadEntries();
+      }).then((unlistenFn) => {
+        unlisten = unlistenFn;
+      });
+    }
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, []);
 
   // Set up real-time sync when authenticated
   useEffect(() => {
@@ -144,6 +166,21 @@ export default function App() {
     };
   }, []);
 
+  // Offer to log a note when a focus block ends
+  useEffect(() => {
+    let unlisten;
+    if (typeof listen === 'function') {
+      listen('focus-note-prompt', () => {
+        setShowPopup(true);
+      }).then((unlistenFn) => {
+        unlisten = unlistenFn;
+      });
+    }
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, []);
+
   const handleSaveItems = async (items) => {
     try {
       const timestamp = new Date().toISOString();
@@ -224,31 +261,9 @@ export default function App() {
   };
 
   const setupSmartPrompts = () => {
-    const config = SmartPromptsService.getNudgeConfig();
-    if (config.enabled) {
-      checkForSmartPromptNudge();
-    }
-  };
-
-  const checkForSmartPromptNudge = async () => {
-    try {
-      const config = SmartPromptsService.getNudgeConfig();
-      if (!config.enabled || SmartPromptsService.isQuietHours()) {
-        return;
-      }
-
-      const lastNudgeTime = localStorage.getItem('lastSmartPromptNudge');
-      if (!SmartPromptsService.shouldShowNudge(lastNudgeTime)) {
-        return;
-      }
-
-      const prompt = await SmartPromptsService.getSmartPrompt();
-      if (prompt.priority === 'high' || Math.random() < 0.3) { // 30% chance for medium/low priority
-        setSmartPromptNudge(prompt);
-        localStorage.setItem('lastSmartPromptNudge', new Date().toISOString());
-      }
-    } catch (error) {
-      console.error('Failed to check for smart prompt nudge:', error);
+    // Hand a config saved before nudges moved to the backend over to it
+    if (localStorage.getItem('smartPromptsConfig')) {
+      SmartPromptsService.saveNudgeConfig(SmartPromptsService.getNudgeConfig());
     }
   };
 
@@ -21,8 +21,9 @@ const ITEM_TYPES = ['Action', 'Decision', 'Note', 'Meeting'];
  *   { type, content, project, tags: [], jira: '', people: [] }
  * - onClose: function called when the popup is dismissed without
  *   saving
+ * - prompt: optional smart prompt shown under the title
  */
-export default function EntryPopup({ isOpen, onSave, onClose }) {
+export default function EntryPopup({ isOpen, onSave, onClose, prompt }) {
   const [items, setItems] = useState([]);
   const [projects, setProjects] = useState([]);
 
@@ -128,6 +129,9 @@ export default function EntryPopup({ isOpen, onSave, onClose }) {
         }}
       >
         <h2 style={{ marginBottom: '8px' }}>New Entry</h2>
+        {prompt && (
+          <p style={{ marginTop: 0, marginBottom: '8px', color: '#555' }}>{prompt}</p>
+        )}
         {items.map((item, index) => (
           <div
             key={index}
@@ -143,7 +143,7 @@ export default function MeetingsManager({ isOpen, onClose }) {
   };
 
   const meetingTypes = [
-    'meeting', 'standup', 'retrospective', 'planning', 'review', 'interview', 'workshop', 'presentation'
+    'meeting', 'standup', 'one_on_one', 'retrospective', 'planning', 'review', 'interview', 'workshop', 'presentation'
   ];
 
   const priorityLevels = [
@@ -0,0 +1,51 @@
+import React, { useEffect, useRef, useState } from 'react';
+import { emit } from '@tauri-apps/api/event';
+import { appWindow } from '@tauri-apps/api/window';
+import EntryPopup from './EntryPopup.jsx';
+import { DataService } from '../services/dataService.js';
+
+/**
+ * QuickEntryWindow is all the quick-entry window renders. The window is
+ * opened from the tray, usually after a smart-prompt nudge, so the nudge
+ * that led here is shown above the form. Saving tells the main window to
+ * reload its entries; saving or cancelling closes the window.
+ */
+export default function QuickEntryWindow() {
+  const [nudge, setNudge] = useState(null);
+  const pendingSave = useRef(null);
+
+  useEffect(() => {
+    DataService.takePendingNudge()
+      .then(setNudge)
+      .catch(() => setNudge(null));
+  }, []);
+
+  const handleSave = (items) => {
+    pendingSave.current = (async () => {
+      try {
+        const entry = await DataService.createEntry(new Date().toISOString(), items);
+        await emit('entry-created', entry);
+      } catch (error) {
+        console.error('Failed to save quick entry:', error);
+      }
+    })();
+  };
+
+  // EntryPopup closes right after handing over the items, so wait for the
+  // save before the window goes away
+  const handleClose = async () => {
+    if (pendingSave.current) {
+      await pendingSave.current;
+    }
+    await appWindow.close();
+  };
+
+  return (
+    <EntryPopup
+      isOpen
+      onSave={handleSave}
+      onClose={handleClose}
+      prompt={nudge ? nudge.prompt : null}
+    />
+  );
+}
@@ -1,12 +1,15 @@
 import React from 'react';
 import { createRoot } from 'react-dom/client';
 import App from './App.jsx';
+import QuickEntryWindow from './components/QuickEntryWindow.jsx';
 
 // Entry point for the React application. We create the root
 // container and render the App component into the DOM. This file
 // remains intentionally simple to keep the bootstrap process
-// straightforward.
+// straightforward. The quick-entry window opened from the tray loads
+// the same page and renders only the entry form.
 
 const container = document.getElementById('root');
 const root = createRoot(container);
-root.render(<App />);
\ No newline at end of file
+const isQuickEntry = new URLSearchParams(window.location.search).get('window') === 'quick-entry';
+root.render(isQuickEntry ? <QuickEntryWindow /> : <App />);
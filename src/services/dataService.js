@@ -36,11 +36,12 @@ export class DataService {
 
   /**
    * Get all entries with their items
+   * @param {Array<Object>} [fieldFilters] - Custom field filters, each { field, equals, min, max }
    * @returns {Promise<Array>} Array of entries with items
    */
-  static async getAllEntries() {
+  static async getAllEntries(fieldFilters = null) {
     try {
-      return await invoke('get_all_entries');
+      return await invoke('get_all_entries', { fieldFilters });
     } catch (error) {
       console.error('Failed to get entries:', error);
       throw error;
@@ -91,12 +92,23 @@ export class DataService {
   }
 
   /**
-   * Export all entries as CSV
+   * Export entries as CSV
+   * @param {Object} options - Optional columns (names, in order), bom, from/to (YYYY-MM-DD), project, tag, itemType
    * @returns {Promise<string>} CSV content
    */
-  static async exportEntriesCSV() {
+  static async exportEntriesCSV(options = {}) {
     try {
-      return await invoke('export_entries_csv');
+      return await invoke('export_entries_csv', {
+        options: {
+          columns: options.columns ?? null,
+          bom: options.bom || false,
+          from: options.from ?? null,
+          to: options.to ?? null,
+          project: options.project ?? null,
+          tag: options.tag ?? null,
+          item_type: options.itemType ?? null
+        }
+      });
     } catch (error) {
       console.error('Failed to export CSV:', error);
       throw error;
@@ -116,6 +128,50 @@ export class DataService {
     }
   }
 
+  /**
+   * Export the whole logbook as JSON, for backup or moving to another machine
+   * @returns {Promise<string>} JSON content
+   */
+  static async exportJSON() {
+    try {
+      return await invoke('export_json');
+    } catch (error) {
+      console.error('Failed to export JSON:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Restore a JSON export
+   * @param {string} data - Content of a file written by exportJSON
+   * @param {string} mode - 'merge' to update and add by id, 'replace' to start over from the export
+   * @returns {Promise<Object>} Rows imported per table
+   */
+  static async importJSON(data, mode = 'merge') {
+    try {
+      return await invoke('import_json', { data, mode });
+    } catch (error) {
+      console.error('Failed to import JSON:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Import entries from a CSV file; rows with the same date and time become one entry
+   * @param {string} path - Path of the CSV file
+   * @param {Object|null} mapping - Headers to read values from (date, time, item_type, content, project, tags, jira, people, fields, list_separator); the export's headers when null
+   * @param {boolean} dryRun - Only report what would be imported
+   * @returns {Promise<Object>} Row errors, a preview of the entries and, once imported, their ids
+   */
+  static async importCSV(path, mapping = null, dryRun = true) {
+    try {
+      return await invoke('import_csv', { path, mapping, dryRun });
+    } catch (error) {
+      console.error('Failed to import CSV:', error);
+      throw error;
+    }
+  }
+
   /**
    * Download exported content as a file
    * @param {string} content - File content
@@ -136,10 +192,11 @@ export class DataService {
 
   /**
    * Export entries as CSV and download
+   * @param {Object} options - Same as exportEntriesCSV
    */
-  static async exportAndDownloadCSV() {
+  static async exportAndDownloadCSV(options = {}) {
     try {
-      const csvContent = await this.exportEntriesCSV();
+      const csvContent = await this.exportEntriesCSV(options);
       const timestamp = new Date().toISOString().split('T')[0];
       this.downloadFile(csvContent, `scobro-logbook-${timestamp}.csv`, 'text/csv');
     } catch (error) {
@@ -162,6 +219,20 @@ export class DataService {
     }
   }
 
+  /**
+   * Export the whole logbook as JSON and download
+   */
+  static async exportAndDownloadJSON() {
+    try {
+      const jsonContent = await this.exportJSON();
+      const timestamp = new Date().toISOString().split('T')[0];
+      this.downloadFile(jsonContent, `scobro-logbook-${timestamp}.json`, 'application/json');
+    } catch (error) {
+      console.error('Failed to export and download JSON:', error);
+      throw error;
+    }
+  }
+
   // Project management methods
   /**
    * Create a new project
@@ -371,4 +442,1081 @@ export class DataService {
       throw error;
     }
   }
+
+  /**
+   * Link an entry item to a meeting so it appears in the minutes
+   * @param {string} meetingId - ID of the meeting
+   * @param {string} entryItemId - ID of the entry item to link
+   * @param {string|null} occurrenceStart - ISO start of a recurring meeting occurrence, or null for the series
+   * @returns {Promise<void>}
+   */
+  static async linkMeetingNote(meetingId, entryItemId, occurrenceStart = null) {
+    try {
+      return await invoke('link_meeting_note', { meetingId, entryItemId, occurrenceStart });
+    } catch (error) {
+      console.error('Failed to link meeting note:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Remove the link between an entry item and a meeting
+   * @param {string} meetingId - ID of the meeting
+   * @param {string} entryItemId - ID of the linked entry item
+   * @returns {Promise<void>}
+   */
+  static async unlinkMeetingNote(meetingId, entryItemId) {
+    try {
+      return await invoke('unlink_meeting_note', { meetingId, entryItemId });
+    } catch (error) {
+      console.error('Failed to unlink meeting note:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Generate meeting minutes
+   * @param {string} meetingId - ID of the meeting
+   * @param {string} format - 'markdown' or 'html'
+   * @param {string|null} occurrenceStart - ISO start of a recurring meeting occurrence, or null for the series
+   * @returns {Promise<string>} Rendered minutes
+   */
+  static async generateMeetingMinutes(meetingId, format = 'markdown', occurrenceStart = null) {
+    try {
+      return await invoke('generate_meeting_minutes', { meetingId, format, occurrenceStart });
+    } catch (error) {
+      console.error('Failed to generate meeting minutes:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Set or clear the recurrence rule of a meeting
+   * @param {string} meetingId - ID of the meeting
+   * @param {string|null} recurrenceRule - RFC 5545 RRULE, e.g. 'FREQ=WEEKLY;BYDAY=MO,WE,FR'
+   * @param {Array<string>} exceptions - ISO start times of skipped occurrences
+   * @returns {Promise<Object>} Updated meeting
+   */
+  static async setMeetingRecurrence(meetingId, recurrenceRule, exceptions = []) {
+    try {
+      return await invoke('set_meeting_recurrence', { meetingId, recurrenceRule, exceptions });
+    } catch (error) {
+      console.error('Failed to set meeting recurrence:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Skip a single occurrence of a recurring meeting
+   * @param {string} meetingId - ID of the meeting
+   * @param {string} occurrenceStart - ISO start time of the occurrence
+   * @returns {Promise<Object>} Updated meeting
+   */
+  static async cancelMeetingOccurrence(meetingId, occurrenceStart) {
+    try {
+      return await invoke('cancel_meeting_occurrence', { meetingId, occurrenceStart });
+    } catch (error) {
+      console.error('Failed to cancel meeting occurrence:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Expand all meetings into occurrences within a date range
+   * @param {string} rangeStart - ISO timestamp
+   * @param {string} rangeEnd - ISO timestamp
+   * @returns {Promise<Array>} Array of occurrences sorted by start time
+   */
+  static async getMeetingOccurrences(rangeStart, rangeEnd) {
+    try {
+      return await invoke('get_meeting_occurrences', { rangeStart, rangeEnd });
+    } catch (error) {
+      console.error('Failed to get meeting occurrences:', error);
+      throw error;
+    }
+  }
+
+  // Time tracking methods
+  /**
+   * Start a timer. Fails if another timer is already running.
+   * @param {Object} timer - Object with project, entry_item_id, jira_key, notes (all optional)
+   * @returns {Promise<Object>} The running time entry
+   */
+  static async startTimer(timer = {}) {
+    try {
+      return await invoke('start_timer', { request: timer });
+    } catch (error) {
+      console.error('Failed to start timer:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Stop the running timer
+   * @returns {Promise<Object>} The completed time entry
+   */
+  static async stopTimer() {
+    try {
+      return await invoke('stop_timer');
+    } catch (error) {
+      console.error('Failed to stop timer:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get the running timer, if any
+   * @returns {Promise<Object|null>} The running time entry or null
+   */
+  static async getActiveTimer() {
+    try {
+      return await invoke('get_active_timer');
+    } catch (error) {
+      console.error('Failed to get active timer:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * List time entries overlapping a date range
+   * @param {string|null} rangeStart - ISO timestamp
+   * @param {string|null} rangeEnd - ISO timestamp
+   * @param {string|null} project - Project name filter
+   * @returns {Promise<Array>} Array of time entries, oldest first
+   */
+  static async listTimeEntries(rangeStart = null, rangeEnd = null, project = null) {
+    try {
+      return await invoke('list_time_entries', { rangeStart, rangeEnd, project });
+    } catch (error) {
+      console.error('Failed to list time entries:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Edit a time entry
+   * @param {Object} timeEntry - Object with id and any of project, entry_item_id, jira_key, start_time, end_time, notes
+   * @returns {Promise<Object>} Updated time entry
+   */
+  static async editTimeEntry(timeEntry) {
+    try {
+      return await invoke('edit_time_entry', { request: timeEntry });
+    } catch (error) {
+      console.error('Failed to edit time entry:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Delete a time entry
+   * @param {string} timeEntryId - ID of the time entry to delete
+   * @returns {Promise<void>}
+   */
+  static async deleteTimeEntry(timeEntryId) {
+    try {
+      return await invoke('delete_time_entry', { timeEntryId });
+    } catch (error) {
+      console.error('Failed to delete time entry:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Check the running timer for an idle gap (app closed or machine asleep)
+   * @returns {Promise<Object|null>} Object with timer, last_heartbeat, detected_at, gap_seconds, or null
+   */
+  static async getTimerRecovery() {
+    try {
+      return await invoke('get_timer_recovery');
+    } catch (error) {
+      console.error('Failed to check timer recovery:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Resolve an idle gap on the running timer
+   * @param {string} decision - 'keep', 'trim' or 'split'
+   * @returns {Promise<Object>} Object with the stopped and running time entries
+   */
+  static async resolveTimerRecovery(decision) {
+    try {
+      return await invoke('resolve_timer_recovery', { decision });
+    } catch (error) {
+      console.error('Failed to resolve timer recovery:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get the weekly timesheet
+   * @param {string} weekStart - First day of the week, 'YYYY-MM-DD'
+   * @param {string} groupBy - 'project', 'jira' or 'project_jira'
+   * @param {number|null} roundingMinutes - Round each entry per day to this many minutes (e.g. 6 or 15)
+   * @returns {Promise<Object>} Timesheet with rows, daily totals and total seconds
+   */
+  static async getTimesheet(weekStart, groupBy = 'project', roundingMinutes = null) {
+    try {
+      return await invoke('get_timesheet', { weekStart, groupBy, roundingMinutes });
+    } catch (error) {
+      console.error('Failed to get timesheet:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Export the weekly timesheet
+   * @param {string} weekStart - First day of the week, 'YYYY-MM-DD'
+   * @param {string} groupBy - 'project', 'jira' or 'project_jira'
+   * @param {number|null} roundingMinutes - Rounding in minutes
+   * @param {string} format - 'csv', 'markdown' or 'toggl' (Toggl/Clockify import CSV)
+   * @param {string|null} email - Email address for the Toggl/Clockify Email column
+   * @returns {Promise<string>} Exported content
+   */
+  static async exportTimesheet(weekStart, groupBy = 'project', roundingMinutes = null, format = 'csv', email = null) {
+    try {
+      return await invoke('export_timesheet', { weekStart, groupBy, roundingMinutes, format, email });
+    } catch (error) {
+      console.error('Failed to export timesheet:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Set or clear a person's hourly rate on a project
+   * @param {string} projectId - Project ID
+   * @param {string} personName - Person the rate applies to
+   * @param {number|null} hourlyRate - Rate per hour, or null to use the project rate
+   * @returns {Promise<void>}
+   */
+  static async setProjectPersonRate(projectId, personName, hourlyRate = null) {
+    try {
+      return await invoke('set_project_person_rate', { projectId, personName, hourlyRate });
+    } catch (error) {
+      console.error('Failed to set person rate:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get the per-person rates of a project
+   * @param {string} projectId - Project ID
+   * @returns {Promise<Array>} Array of person rates
+   */
+  static async getProjectPersonRates(projectId) {
+    try {
+      return await invoke('get_project_person_rates', { projectId });
+    } catch (error) {
+      console.error('Failed to get person rates:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Invoice the uninvoiced billable time of a project
+   * @param {string} projectId - Project ID
+   * @param {string} periodStart - ISO timestamp, inclusive
+   * @param {string} periodEnd - ISO timestamp, exclusive
+   * @returns {Promise<Object>} The stored invoice with its Markdown and HTML
+   */
+  static async generateInvoice(projectId, periodStart, periodEnd) {
+    try {
+      return await invoke('generate_invoice', { projectId, periodStart, periodEnd });
+    } catch (error) {
+      console.error('Failed to generate invoice:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * List invoices, newest first
+   * @param {string|null} projectId - Only invoices of this project
+   * @returns {Promise<Array>} Array of invoices with their lines
+   */
+  static async listInvoices(projectId = null) {
+    try {
+      return await invoke('list_invoices', { projectId });
+    } catch (error) {
+      console.error('Failed to list invoices:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Render a stored invoice
+   * @param {string} invoiceId - Invoice ID
+   * @param {string} format - 'markdown' or 'html'
+   * @returns {Promise<string>} Rendered invoice
+   */
+  static async exportInvoice(invoiceId, format = 'markdown') {
+    try {
+      return await invoke('export_invoice', { invoiceId, format });
+    } catch (error) {
+      console.error('Failed to export invoice:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get the focus session settings
+   * @returns {Promise<Object>} Work/break lengths, cycles and note prompt flag
+   */
+  static async getFocusSettings() {
+    try {
+      return await invoke('get_focus_settings');
+    } catch (error) {
+      console.error('Failed to get focus settings:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Save the focus session settings
+   * @param {Object} settings - { workMinutes, shortBreakMinutes, longBreakMinutes, cycles, promptForNote }
+   * @returns {Promise<Object>} Saved settings
+   */
+  static async updateFocusSettings(settings) {
+    try {
+      return await invoke('update_focus_settings', {
+        settings: {
+          work_minutes: settings.workMinutes,
+          short_break_minutes: settings.shortBreakMinutes,
+          long_break_minutes: settings.longBreakMinutes,
+          cycles: settings.cycles,
+          prompt_for_note: settings.promptForNote,
+        },
+      });
+    } catch (error) {
+      console.error('Failed to update focus settings:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Start a focus session. Listen for 'focus-phase-changed' and
+   * 'focus-note-prompt' events to follow it.
+   * @param {string|null} project - Project to track focus time on
+   * @returns {Promise<Object>} Focus status
+   */
+  static async startFocusSession(project = null) {
+    try {
+      return await invoke('start_focus_session', { project });
+    } catch (error) {
+      console.error('Failed to start focus session:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Stop the running focus session
+   * @returns {Promise<Object>} Focus status
+   */
+  static async stopFocusSession() {
+    try {
+      return await invoke('stop_focus_session');
+    } catch (error) {
+      console.error('Failed to stop focus session:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * End the current focus phase now
+   * @returns {Promise<Object>} Focus status
+   */
+  static async skipFocusPhase() {
+    try {
+      return await invoke('skip_focus_phase');
+    } catch (error) {
+      console.error('Failed to skip focus phase:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get the state of the focus session
+   * @returns {Promise<Object>} Focus status
+   */
+  static async getFocusStatus() {
+    try {
+      return await invoke('get_focus_status');
+    } catch (error) {
+      console.error('Failed to get focus status:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * List completed focus blocks
+   * @param {string|null} rangeStart - ISO timestamp, inclusive
+   * @param {string|null} rangeEnd - ISO timestamp, exclusive
+   * @param {string|null} project - Only blocks on this project
+   * @returns {Promise<Array>} Array of focus blocks
+   */
+  static async listFocusSessions(rangeStart = null, rangeEnd = null, project = null) {
+    try {
+      return await invoke('list_focus_sessions', { rangeStart, rangeEnd, project });
+    } catch (error) {
+      console.error('Failed to list focus sessions:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get dashboard analytics computed in the database
+   * @param {string|null} rangeStart - ISO timestamp, inclusive
+   * @param {string|null} rangeEnd - ISO timestamp, exclusive
+   * @param {string} granularity - 'day', 'week' or 'month'
+   * @returns {Promise<Object>} Counts per period/type, top tags/people/projects, heatmap and streaks
+   */
+  static async getAnalytics(rangeStart = null, rangeEnd = null, granularity = 'day') {
+    try {
+      return await invoke('get_analytics', { rangeStart, rangeEnd, granularity });
+    } catch (error) {
+      console.error('Failed to get analytics:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get the working-days calendar used for streaks and goals
+   * @returns {Promise<Object>} { working_days: [1..7, Monday = 1], holidays: ['YYYY-MM-DD'] }
+   */
+  static async getWorkCalendar() {
+    try {
+      return await invoke('get_work_calendar');
+    } catch (error) {
+      console.error('Failed to get working-days calendar:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Save the working-days calendar
+   * @param {Object} calendar - { working_days: [1..7], holidays: ['YYYY-MM-DD'] }
+   * @returns {Promise<Object>} Saved calendar
+   */
+  static async updateWorkCalendar(calendar) {
+    try {
+      return await invoke('update_work_calendar', { calendar });
+    } catch (error) {
+      console.error('Failed to update working-days calendar:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Create a logging goal
+   * @param {Object} goal - Object with name, item_type, target_count, period
+   *   ('workday', 'week', 'sprint' or 'month') and, for sprints, sprint_days and sprint_start
+   * @returns {Promise<Object>} Created goal
+   */
+  static async createGoal(goal) {
+    try {
+      return await invoke('create_goal', { request: goal });
+    } catch (error) {
+      console.error('Failed to create goal:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get all goals
+   * @returns {Promise<Array>} Array of goals
+   */
+  static async getAllGoals() {
+    try {
+      return await invoke('get_all_goals');
+    } catch (error) {
+      console.error('Failed to get goals:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Update a goal
+   * @param {Object} goal - Object with id and fields to update
+   * @returns {Promise<Object>} Updated goal
+   */
+  static async updateGoal(goal) {
+    try {
+      return await invoke('update_goal', { request: goal });
+    } catch (error) {
+      console.error('Failed to update goal:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Delete a goal
+   * @param {string} goalId - ID of the goal to delete
+   * @returns {Promise<void>}
+   */
+  static async deleteGoal(goalId) {
+    try {
+      return await invoke('delete_goal', { goalId });
+    } catch (error) {
+      console.error('Failed to delete goal:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get progress and streaks for every goal
+   * @returns {Promise<Array>} Array of goal progress objects
+   */
+  static async getGoalProgress() {
+    try {
+      return await invoke('get_goal_progress');
+    } catch (error) {
+      console.error('Failed to get goal progress:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get the smart-prompt nudge settings used by the backend scheduler
+   * @returns {Promise<Object>} Nudge settings in the SmartPromptsService config shape
+   */
+  static async getNudgeSettings() {
+    try {
+      return await invoke('get_nudge_settings');
+    } catch (error) {
+      console.error('Failed to get nudge settings:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Save the smart-prompt nudge settings
+   * @param {Object} settings - SmartPromptsService config (enabled, morningNudges, ..., quietHours)
+   * @returns {Promise<Object>} Saved settings
+   */
+  static async updateNudgeSettings(settings) {
+    try {
+      return await invoke('update_nudge_settings', { settings });
+    } catch (error) {
+      console.error('Failed to update nudge settings:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Take the nudge that led to the quick-entry window, clearing it
+   * @returns {Promise<Object|null>} Smart prompt or null
+   */
+  static async takePendingNudge() {
+    try {
+      return await invoke('take_pending_nudge');
+    } catch (error) {
+      console.error('Failed to take pending nudge:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Open (or focus) the quick-entry window
+   * @returns {Promise<void>}
+   */
+  static async openQuickEntry() {
+    try {
+      return await invoke('open_quick_entry');
+    } catch (error) {
+      console.error('Failed to open quick entry:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get the due-date reminder settings
+   * @returns {Promise<Object>} Settings { enabled, day_before, morning_of, overdue, hour }
+   */
+  static async getReminderSettings() {
+    try {
+      return await invoke('get_reminder_settings');
+    } catch (error) {
+      console.error('Failed to get reminder settings:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Save the due-date reminder settings
+   * @param {Object} settings - Settings { enabled, day_before, morning_of, overdue, hour }
+   * @returns {Promise<Object>} Saved settings
+   */
+  static async updateReminderSettings(settings) {
+    try {
+      return await invoke('update_reminder_settings', { settings });
+    } catch (error) {
+      console.error('Failed to update reminder settings:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * List open actions with a due date and their reminder state
+   * @returns {Promise<Array>} Array of reminder objects, soonest due first
+   */
+  static async listReminders() {
+    try {
+      return await invoke('list_reminders');
+    } catch (error) {
+      console.error('Failed to list reminders:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Snooze a reminder until a later time
+   * @param {string} targetKind - Kind of the reminded item, e.g. 'meeting_action'
+   * @param {string} targetId - ID of the reminded item
+   * @param {string} until - ISO timestamp to remind again at
+   * @returns {Promise<Object>} Updated reminder
+   */
+  static async snoozeReminder(targetKind, targetId, until) {
+    try {
+      return await invoke('snooze_reminder', { targetKind, targetId, until });
+    } catch (error) {
+      console.error('Failed to snooze reminder:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Stop reminding about an item's current due date
+   * @param {string} targetKind - Kind of the reminded item, e.g. 'meeting_action'
+   * @param {string} targetId - ID of the reminded item
+   * @returns {Promise<void>}
+   */
+  static async dismissReminder(targetKind, targetId) {
+    try {
+      return await invoke('dismiss_reminder', { targetKind, targetId });
+    } catch (error) {
+      console.error('Failed to dismiss reminder:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Mark a meeting action open or done
+   * @param {string} actionId - ID of the action
+   * @param {string} status - 'open' or 'done'
+   * @returns {Promise<void>}
+   */
+  static async updateMeetingActionStatus(actionId, status) {
+    try {
+      return await invoke('update_meeting_action_status', { actionId, status });
+    } catch (error) {
+      console.error('Failed to update action status:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Generate a digest report for a period, grouped by project
+   * @param {string} rangeStart - ISO timestamp the period starts at
+   * @param {string} rangeEnd - ISO timestamp the period ends before
+   * @param {string} [template] - Template name, e.g. 'digest.md' (default) or 'digest.html'
+   * @returns {Promise<Object>} { template, content, digest }
+   */
+  static async generateDigest(rangeStart, rangeEnd, template = null) {
+    try {
+      return await invoke('generate_digest', { rangeStart, rangeEnd, template });
+    } catch (error) {
+      console.error('Failed to generate digest:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * List the digest templates and the directory they can be edited in
+   * @returns {Promise<Object>} { directory, templates }
+   */
+  static async listDigestTemplates() {
+    try {
+      return await invoke('list_digest_templates');
+    } catch (error) {
+      console.error('Failed to list digest templates:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Generate Yesterday / Today / Blockers standup notes
+   * @param {string} [date] - YYYY-MM-DD, today if omitted
+   * @returns {Promise<Object>} { text, standup }
+   */
+  static async generateStandup(date = null) {
+    try {
+      return await invoke('generate_standup', { date });
+    } catch (error) {
+      console.error('Failed to generate standup:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * List the decision register, numbered in the order decisions were logged
+   * @param {string} [status] - 'proposed', 'accepted' or 'superseded'
+   * @param {string} [project] - Only decisions for this project
+   * @returns {Promise<Array>} Decisions with context, status and supersedes links
+   */
+  static async listDecisions(status = null, project = null) {
+    try {
+      return await invoke('list_decisions', { status, project });
+    } catch (error) {
+      console.error('Failed to list decisions:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Set the context, status and superseded decision of a Decision item
+   * @param {Object} request - { item_id, context, status, supersedes_id }; omitted fields stay as they are, an empty context or supersedes_id clears it
+   * @returns {Promise<Object>} The updated decision
+   */
+  static async updateDecision(request) {
+    try {
+      return await invoke('update_decision', { request });
+    } catch (error) {
+      console.error('Failed to update decision:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Write every decision as a numbered ADR Markdown file
+   * @param {string} directory - Directory to write the ADRs into
+   * @returns {Promise<Object>} { directory, files }
+   */
+  static async exportDecisionsAdr(directory) {
+    try {
+      return await invoke('export_decisions_adr', { directory });
+    } catch (error) {
+      console.error('Failed to export decisions:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Build a Markdown agenda for a 1:1, optionally creating the meeting
+   * @param {string} personId - Person the 1:1 is with
+   * @param {string} [since] - ISO timestamp to cover from; defaults to the last 1:1
+   * @param {string} [scheduleAt] - ISO start time to create the 1:1 at
+   * @returns {Promise<Object>} { markdown, agenda, meeting }
+   */
+  static async buildOneOnOneAgenda(personId, since = null, scheduleAt = null) {
+    try {
+      return await invoke('build_one_on_one_agenda', { personId, since, scheduleAt });
+    } catch (error) {
+      console.error('Failed to build 1:1 agenda:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Mark an item as waiting on a person
+   * @param {string} itemId - Entry item ID
+   * @param {string} person - Who the item is waiting on
+   * @param {string} [expectedBy] - YYYY-MM-DD or ISO timestamp it is expected by
+   * @returns {Promise<Object>} The waiting-for record
+   */
+  static async setWaitingFor(itemId, person, expectedBy = null) {
+    try {
+      return await invoke('set_waiting_for', { itemId, person, expectedBy });
+    } catch (error) {
+      console.error('Failed to set waiting-for:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Mark a waiting-for item as received
+   * @param {string} itemId - Entry item ID
+   * @returns {Promise<void>}
+   */
+  static async resolveWaitingFor(itemId) {
+    try {
+      return await invoke('resolve_waiting_for', { itemId });
+    } catch (error) {
+      console.error('Failed to resolve waiting-for:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Stop tracking an item as waiting on someone
+   * @param {string} itemId - Entry item ID
+   * @returns {Promise<void>}
+   */
+  static async clearWaitingFor(itemId) {
+    try {
+      return await invoke('clear_waiting_for', { itemId });
+    } catch (error) {
+      console.error('Failed to clear waiting-for:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * List what we are waiting on, grouped by person, most overdue first
+   * @param {boolean} [includeResolved] - Also list resolved items
+   * @returns {Promise<Array>} Groups of { person_id, person, overdue, entries }
+   */
+  static async listWaitingFor(includeResolved = false) {
+    try {
+      return await invoke('list_waiting_for', { includeResolved });
+    } catch (error) {
+      console.error('Failed to list waiting-for items:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Append a step to an item's checklist
+   * @param {string} itemId - Entry item ID
+   * @param {string} text - What the step is
+   * @param {string} [assignee] - Who does it
+   * @returns {Promise<Object>} The new checklist row
+   */
+  static async addChecklistRow(itemId, text, assignee = null) {
+    try {
+      return await invoke('add_checklist_row', { itemId, text, assignee });
+    } catch (error) {
+      console.error('Failed to add checklist row:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Flip a checklist row between done and not done
+   * @param {string} rowId - Checklist row ID
+   * @returns {Promise<Object>} The updated row
+   */
+  static async toggleChecklistRow(rowId) {
+    try {
+      return await invoke('toggle_checklist_row', { rowId });
+    } catch (error) {
+      console.error('Failed to toggle checklist row:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Reorder an item's checklist
+   * @param {string} itemId - Entry item ID
+   * @param {Array<string>} orderedIds - Every row ID of the item in the new order
+   * @returns {Promise<Array>} The checklist in its new order
+   */
+  static async reorderChecklistRows(itemId, orderedIds) {
+    try {
+      return await invoke('reorder_checklist_rows', { itemId, orderedIds });
+    } catch (error) {
+      console.error('Failed to reorder checklist:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Delete a checklist row
+   * @param {string} rowId - Checklist row ID
+   * @returns {Promise<void>}
+   */
+  static async deleteChecklistRow(rowId) {
+    try {
+      return await invoke('delete_checklist_row', { rowId });
+    } catch (error) {
+      console.error('Failed to delete checklist row:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Define a custom field for entry items
+   * @param {Object} request - { name, field_type: 'text'|'number'|'date'|'enum', options }
+   * @returns {Promise<Object>} The created field
+   */
+  static async createCustomField(request) {
+    try {
+      return await invoke('create_custom_field', { request });
+    } catch (error) {
+      console.error('Failed to create custom field:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Get all custom field definitions
+   * @returns {Promise<Array>} Fields with id, name, field_type and options
+   */
+  static async getCustomFields() {
+    try {
+      return await invoke('get_custom_fields');
+    } catch (error) {
+      console.error('Failed to get custom fields:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Rename a custom field or change its enum options
+   * @param {string} id - Field ID
+   * @param {Object} request - { name, options }
+   * @returns {Promise<Object>} The updated field
+   */
+  static async updateCustomField(id, request) {
+    try {
+      return await invoke('update_custom_field', { id, request });
+    } catch (error) {
+      console.error('Failed to update custom field:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Delete a custom field and every item's value for it
+   * @param {string} id - Field ID
+   * @returns {Promise<void>}
+   */
+  static async deleteCustomField(id) {
+    try {
+      return await invoke('delete_custom_field', { id });
+    } catch (error) {
+      console.error('Failed to delete custom field:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Change when an entry was logged
+   * @param {string} entryId - Entry ID
+   * @param {string} timestamp - New ISO timestamp
+   * @returns {Promise<Object>} The updated entry
+   */
+  static async updateEntryTimestamp(entryId, timestamp) {
+    try {
+      return await invoke('update_entry_timestamp', { entryId, timestamp });
+    } catch (error) {
+      console.error('Failed to update entry timestamp:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Move an item into another entry; an emptied entry is deleted
+   * @param {string} itemId - Entry item ID
+   * @param {string} targetEntryId - Entry to move the item into
+   * @returns {Promise<Object>} The target entry
+   */
+  static async moveItem(itemId, targetEntryId) {
+    try {
+      return await invoke('move_item', { itemId, targetEntryId });
+    } catch (error) {
+      console.error('Failed to move item:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Split some items off an entry into a new entry
+   * @param {string} entryId - Entry to split
+   * @param {Array<string>} itemIds - Items for the new entry
+   * @param {string} [timestamp] - ISO timestamp of the new entry; defaults to the original's
+   * @returns {Promise<Array>} [original entry, new entry]
+   */
+  static async splitEntry(entryId, itemIds, timestamp = null) {
+    try {
+      return await invoke('split_entry', { entryId, itemIds, timestamp });
+    } catch (error) {
+      console.error('Failed to split entry:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Merge entries into one, keeping the target's timestamp
+   * @param {string} targetEntryId - Entry to merge into
+   * @param {Array<string>} sourceEntryIds - Entries whose items move and which are deleted
+   * @returns {Promise<Object>} The merged entry
+   */
+  static async mergeEntries(targetEntryId, sourceEntryIds) {
+    try {
+      return await invoke('merge_entries', { targetEntryId, sourceEntryIds });
+    } catch (error) {
+      console.error('Failed to merge entries:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Put an entry's items in a new order
+   * @param {string} entryId - Entry whose items are reordered
+   * @param {Array<string>} orderedIds - Every item id of the entry, in the new order
+   * @returns {Promise<Object>} The updated entry
+   */
+  static async reorderItems(entryId, orderedIds) {
+    try {
+      return await invoke('reorder_items', { entryId, orderedIds });
+    } catch (error) {
+      console.error('Failed to reorder items:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Apply the same changes to many items in one step
+   * @param {Array<string>} itemIds - Items to change
+   * @param {Object} ops - addTags, removeTags, setProject ('' clears), addPeople, setType, moveToEntry, delete
+   * @returns {Promise<Object>} Batch id for undo and a result per item
+   */
+  static async bulkUpdateItems(itemIds, ops) {
+    try {
+      return await invoke('bulk_update_items', {
+        itemIds,
+        ops: {
+          add_tags: ops.addTags || [],
+          remove_tags: ops.removeTags || [],
+          set_project: ops.setProject ?? null,
+          add_people: ops.addPeople || [],
+          set_type: ops.setType ?? null,
+          move_to_entry: ops.moveToEntry ?? null,
+          delete: ops.delete || false
+        }
+      });
+    } catch (error) {
+      console.error('Failed to bulk update items:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Undo the most recent bulk update
+   * @param {string} batchId - Batch id returned by bulkUpdateItems
+   * @returns {Promise<Array<string>>} Ids of the restored items
+   */
+  static async undoBulkUpdate(batchId) {
+    try {
+      return await invoke('undo_bulk_update', { batchId });
+    } catch (error) {
+      console.error('Failed to undo bulk update:', error);
+      throw error;
+    }
+  }
+
+  /**
+   * Suggest values while typing, best match and most used lately first
+   * @param {string} kind - 'tag', 'person', 'project' or 'jira'
+   * @param {string} prefix - Text typed so far; matched fuzzily
+   * @param {number} limit - Most suggestions to return (default 10)
+   * @returns {Promise<Array>} Suggestions with use counts and frecency
+   */
+  static async autocomplete(kind, prefix, limit = null) {
+    try {
+      return await invoke('autocomplete', { kind, prefix, limit });
+    } catch (error) {
+      console.error('Failed to get suggestions:', error);
+      throw error;
+    }
+  }
 }
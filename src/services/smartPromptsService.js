@@ -1,3 +1,5 @@
+import { DataService } from './dataService.js';
+
 /**
  * SmartPromptsService - AI-powered suggestions and time-based nudges
  * 
@@ -320,10 +322,14 @@ export class SmartPromptsService {
   }
 
   /**
-   * Save nudge configuration
+   * Save nudge configuration. The backend scheduler sends the nudges, so
+   * the config is mirrored to the database as well.
    */
   static saveNudgeConfig(config) {
     localStorage.setItem('smartPromptsConfig', JSON.stringify(config));
+    DataService.updateNudgeSettings(config).catch(() => {
+      // Already logged by DataService; localStorage still has the config
+    });
   }
 
   /**